@@ -1,6 +1,9 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+/// Default `--max-file-size`: 2 MiB, well above hand-written sources.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 2 * 1024 * 1024;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "virgil-cli",
@@ -16,6 +19,11 @@ pub struct Cli {
     #[arg(long, global = true, conflicts_with = "verbose")]
     pub quiet: bool,
 
+    /// Never draw progress bars (they're already off when stderr isn't a
+    /// terminal or with --log-format json).
+    #[arg(long, global = true)]
+    pub no_progress: bool,
+
     /// Log output format.
     #[arg(long, global = true, value_enum, default_value_t = LogFormat::Compact)]
     pub log_format: LogFormat,
@@ -30,6 +38,23 @@ pub enum LogFormat {
     Json,
 }
 
+/// Commands `json-schema` can describe.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SchemaTarget {
+    Query,
+    Diff,
+    ApiDiff,
+    Schema,
+    Read,
+}
+
+/// File formats `projects export` can write.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ArtifactFormat {
+    ArrowIpc,
+    Ndjson,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Manage and query projects
@@ -38,6 +63,82 @@ pub enum Command {
         command: ProjectCommand,
     },
 
+    /// Parse one source buffer from stdin, or one file from disk, and
+    /// print its symbols, imports and comments as JSON. No project or
+    /// store is involved, so editors can use it as an on-the-fly outline
+    /// provider.
+    ParseOne {
+        /// File to parse from disk instead of reading stdin
+        file: Option<PathBuf>,
+
+        /// Language of the buffer, by name (rust) or extension (rs).
+        /// Required for stdin; detected from the file otherwise
+        #[arg(short, long, required_unless_present = "file")]
+        language: Option<String>,
+
+        /// Path to report for the buffer (defaults to `<stdin>`)
+        #[arg(long, default_value = "<stdin>", conflicts_with = "file")]
+        path: String,
+
+        /// Pretty-print the JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Build a fact store straight from a `.tar`, `.tar.gz`/`.tgz` or
+    /// `.zip` archive, reading its entries in memory instead of
+    /// extracting them. Paths are relative to the archive root;
+    /// symlinks and oversized entries are skipped. Prints a build
+    /// summary like `projects build`. Registering the archive with
+    /// `projects create --path <ARCHIVE>` makes it queryable by name.
+    ParseArchive {
+        /// Archive to read
+        archive: PathBuf,
+
+        /// Store file to build; an existing one is replaced
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Comma-separated language filter, as for `projects create`
+        #[arg(short, long)]
+        lang: Option<String>,
+
+        /// Skip entries larger than this many bytes; 0 disables the cap
+        #[arg(long, default_value_t = DEFAULT_MAX_FILE_SIZE)]
+        max_file_size: u64,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Print how a store file was produced: the virgil version that
+    /// built it, when, from which root, over which languages and how
+    /// many files, plus its schema version. Opens the store read-only,
+    /// so it works on a store copied away from its project.
+    Info {
+        /// Store file to describe, e.g. one written by `parse-archive`
+        store: PathBuf,
+
+        /// Pretty-print the JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Print the JSON Schema of a command's output payload: the
+    /// `result` of `projects query`/`diff`/`api-diff`/`read`, or the
+    /// `tables` of `projects schema`. Generated from the structs the
+    /// commands serialize, so it always matches what they print.
+    JsonSchema {
+        /// Command whose output to describe
+        #[arg(value_enum)]
+        command: SchemaTarget,
+
+        /// Pretty-print the JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
     /// Expose an already-parsed project over a local HTTP API.
     ///
     /// Serves read-only queries against the warm DuckDB store at
@@ -82,9 +183,15 @@ pub enum ProjectCommand {
         #[arg(short, long)]
         exclude: Vec<String>,
 
-        /// Comma-separated language filter (ts,tsx,js,jsx,c,h,cpp,cc,cxx,hpp,cs,rs,py,pyi,go,java,php)
+        /// Comma-separated language filter (ts,tsx,js,jsx,c,h,cpp,cc,cxx,hpp,cs,rs,py,pyi,go,java,php,sh;
+        /// yaml,yml,json,toml are only indexed when listed)
         #[arg(short, long)]
         lang: Option<String>,
+
+        /// Print the files and bytes each language would contribute, and
+        /// the files left out, without registering the project
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// List registered projects
@@ -96,6 +203,203 @@ pub enum ProjectCommand {
         name: String,
     },
 
+    /// Build the stores of several registered projects in one process.
+    ///
+    /// Projects are built --jobs at a time, each into the store `query`
+    /// uses, with grammars compiled once and shared across them. Stores
+    /// that are already current are left alone unless --rebuild is
+    /// given. Prints one summary per project and exits non-zero if any
+    /// failed to build.
+    Build {
+        /// Project names
+        names: Vec<String>,
+
+        /// Also build the projects named in this file, one per line
+        /// (blank lines and `#` comments are skipped)
+        #[arg(long, value_name = "FILE")]
+        from_file: Option<PathBuf>,
+
+        /// Projects built concurrently
+        #[arg(long, default_value_t = 2)]
+        jobs: usize,
+
+        /// Wipe and rebuild stores that are already current
+        #[arg(long)]
+        rebuild: bool,
+
+        /// Skip source files larger than this many bytes; 0 disables the cap
+        #[arg(long, default_value_t = DEFAULT_MAX_FILE_SIZE)]
+        max_file_size: u64,
+
+        /// Only extract these symbol kinds, as for `projects query`
+        #[arg(long)]
+        symbol_kinds: Option<String>,
+
+        /// Only keep symbols whose name matches this regex, as for
+        /// `projects query`
+        #[arg(long)]
+        symbol_name_regex: Option<String>,
+
+        /// Directory of `<lang>.symbols.scm` query overrides, as for
+        /// `projects query`
+        #[arg(long)]
+        query_dir: Option<PathBuf>,
+
+        /// Import specifier prefix to classify as internal (repeatable),
+        /// as for `projects query`
+        #[arg(long = "internal-prefix")]
+        internal_prefixes: Vec<String>,
+
+        /// Cap the parse worker threads and DuckDB's threads per build;
+        /// 0 lets rayon and DuckDB pick one per core
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+
+        /// Cap DuckDB's memory per build, in MiB
+        #[arg(long)]
+        memory_limit: Option<u64>,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Compare two parsed projects: added/removed files, symbols and
+    /// imports, plus symbols whose span length or export status changed.
+    ///
+    /// Both projects must already be parsed (like `serve`, diff never
+    /// builds). Typical use: register the same repo twice at two
+    /// checkouts, query each once to build it, then diff.
+    Diff {
+        /// Project holding the earlier snapshot
+        before: String,
+
+        /// Project holding the later snapshot
+        after: String,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Describe a parsed project's tables: row counts and, per column,
+    /// type, null count and distinct count. Text columns with few
+    /// distinct values (kind, language, error_type, ...) list each value
+    /// with its count.
+    Schema {
+        /// Project name (must already be parsed)
+        name: String,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Exit non-zero when a parsed project has parse failures, or with
+    /// --baseline only when it has failures the baseline doesn't list.
+    /// Prints the failures that count.
+    CheckErrors {
+        /// Project name (must already be parsed)
+        name: String,
+
+        /// `parse_error.ndjson` from `projects export --artifact-format
+        /// ndjson`; failures matching one of its rows by file path and
+        /// error type are ignored
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Write a parsed project's file, symbol, span, raw_import, comment
+    /// and parse_error tables to a directory, one file per table, for
+    /// tools that can't open the DuckDB store.
+    Export {
+        /// Project name (must already be parsed)
+        name: String,
+
+        /// Directory to write the files into (created if missing)
+        #[arg(long, short)]
+        output: PathBuf,
+
+        /// Arrow IPC (`<table>.arrow`) or NDJSON (`<table>.ndjson`)
+        #[arg(long, value_enum, default_value_t = ArtifactFormat::ArrowIpc)]
+        artifact_format: ArtifactFormat,
+
+        /// Write file paths as absolute paths under the root the store
+        /// was built from, instead of workspace-relative ones
+        #[arg(long)]
+        absolute_paths: bool,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Print a file, or a line range of it, by its workspace-relative
+    /// path as stored in `file.path` / `symbol.file_path`.
+    ///
+    /// The path is joined against the root the project's store was built
+    /// from, falling back to the registered project root when it hasn't
+    /// been built yet.
+    Read {
+        /// Project name
+        name: String,
+
+        /// Workspace-relative file path
+        path: String,
+
+        /// First line to print (1-based)
+        #[arg(long)]
+        start_line: Option<usize>,
+
+        /// Last line to print (inclusive)
+        #[arg(long)]
+        end_line: Option<usize>,
+
+        /// Prefix each line with its line number
+        #[arg(long)]
+        numbers: bool,
+
+        /// Mark this line with `>` in the line-number gutter
+        #[arg(long, value_name = "LINE", requires = "numbers")]
+        highlight: Option<usize>,
+
+        /// Resolve the path against this directory instead
+        #[arg(long)]
+        root: Option<PathBuf>,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
+    /// Compare the exported API of two parsed projects.
+    ///
+    /// Exported symbols are matched by file, qualified name and kind;
+    /// each change is `added`, `removed` or `signature_changed`. Removals
+    /// and signature changes are breaking: the command exits non-zero
+    /// when any are found unless --allow-breaking is given, so it can gate
+    /// a CI semver check.
+    #[command(name = "api-diff")]
+    ApiDiff {
+        /// Project holding the earlier snapshot
+        before: String,
+
+        /// Project holding the later snapshot
+        after: String,
+
+        /// Report breaking changes without failing
+        #[arg(long)]
+        allow_breaking: bool,
+
+        /// Pretty-print JSON output
+        #[arg(long)]
+        pretty: bool,
+    },
+
     /// Query a project using SQL (with PGQ extensions for graph templates)
     ///
     /// Pass the query via exactly one of:
@@ -105,7 +409,8 @@ pub enum ProjectCommand {
     ///
     /// Bind parameters with --param key=value (repeatable). Integers and
     /// booleans are auto-coerced; everything else binds as a string.
-    /// Templates reference parameters as $name.
+    /// Templates reference parameters as $name. For SQL built around
+    /// untrusted input, use `?` placeholders with --arg <value> instead.
     ///
     /// Queries that return columns (file, line, severity, pattern, message)
     /// are auto-formatted as audit findings; any other shape prints as rows.
@@ -145,10 +450,108 @@ pub enum ProjectCommand {
         #[arg(long = "param", value_parser = parse_key_value)]
         params: Vec<(String, String)>,
 
+        /// Value bound to the next positional `?` placeholder (repeatable,
+        /// in order). Bound by DuckDB, not spliced into the SQL, so it is
+        /// safe for untrusted strings. Count must match the placeholders.
+        #[arg(long = "arg")]
+        args: Vec<String>,
+
         /// Force a fresh rebuild of the cached fact store.
         #[arg(long)]
         rebuild: bool,
 
+        /// Return at most this many rows (findings, or top-level outline
+        /// nodes). The output then carries `total_count`, the size of the
+        /// whole result, so callers can tell whether they've seen it all.
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Skip this many rows before the page starts.
+        #[arg(long, default_value_t = 0, requires = "limit")]
+        offset: usize,
+
+        /// Print only the number of rows (findings, or top-level outline
+        /// nodes) as `count`, without the rows. SQL queries are wrapped
+        /// in a `COUNT(*)` so the result set is never materialised.
+        #[arg(long, conflicts_with = "limit")]
+        count: bool,
+
+        /// Reuse the stored output of an identical earlier query (same
+        /// SQL or template, params, args and paging) as long as no
+        /// source file's size or mtime has changed since. Off by default.
+        #[arg(long)]
+        cache: bool,
+
+        /// Directory for --cache entries (defaults to a per-project
+        /// directory next to the fact store)
+        #[arg(long, requires = "cache")]
+        cache_dir: Option<PathBuf>,
+
+        /// Only extract these symbol kinds (comma-separated, e.g.
+        /// function,class,method). Takes effect when the store is
+        /// built; combine with --rebuild to change an existing store.
+        #[arg(long)]
+        symbol_kinds: Option<String>,
+
+        /// Only keep symbols whose name matches this regex (e.g.
+        /// `^handle_`). Checked before the build starts; takes effect
+        /// when the store is built, like --symbol-kinds.
+        #[arg(long)]
+        symbol_name_regex: Option<String>,
+
+        /// Directory of tree-sitter symbol query overrides named
+        /// `<lang>.symbols.scm` (e.g. `rust.symbols.scm`). Languages
+        /// without a file keep the built-in query. Takes effect when the
+        /// store is built, like --symbol-kinds.
+        #[arg(long)]
+        query_dir: Option<PathBuf>,
+
+        /// Import specifier prefix to classify as internal (repeatable),
+        /// for path aliases such as `@app/*` that would otherwise look
+        /// like packages. Takes effect when the store is built, like
+        /// --symbol-kinds.
+        #[arg(long = "internal-prefix")]
+        internal_prefixes: Vec<String>,
+
+        /// Skip files larger than this many bytes (minified bundles,
+        /// generated data). Skipped files are recorded in the
+        /// `parse_error` table as `skipped_too_large`. 0 disables the cap.
+        #[arg(long, default_value_t = DEFAULT_MAX_FILE_SIZE)]
+        max_file_size: u64,
+
+        /// Exit non-zero when any file failed to read or parse cleanly.
+        /// The query still runs and its output is printed first.
+        #[arg(long)]
+        fail_on_error: bool,
+
+        /// Cap the worker threads used to parse and build the store, and
+        /// DuckDB's threads for the query. 0 (the default) lets rayon
+        /// and DuckDB pick one per core. Lower values trade throughput
+        /// for less contention on shared machines such as CI runners.
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+
+        /// Cap DuckDB's memory for the build and the query, in MiB.
+        /// Large aggregations spill to disk or fail cleanly instead of
+        /// exhausting memory on small CI runners.
+        #[arg(long)]
+        memory_limit: Option<u64>,
+
+        /// Another already-built project to query alongside this one
+        /// (repeatable). Its store is attached read-only and each core
+        /// table gains an `all_<table>` view (`all_symbol`, `all_file`,
+        /// ...) over every project, with a `dataset` column naming the
+        /// project each row came from. Must already be built; --cache
+        /// keys results by when each one was.
+        #[arg(long = "with-project", value_name = "NAME")]
+        with_projects: Vec<String>,
+
+        /// DuckDB extension to INSTALL and LOAD before the query runs
+        /// (repeatable), e.g. `json` or `spatial`. Only fts, icu, inet,
+        /// json, parquet and spatial are accepted.
+        #[arg(long = "load-extension", value_name = "NAME")]
+        load_extensions: Vec<String>,
+
         /// Pretty-print JSON output
         #[arg(long)]
         pretty: bool,
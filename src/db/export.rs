@@ -0,0 +1,248 @@
+//! Export a built store's core tables for consumers that can't open a
+//! DuckDB file: one Arrow IPC file or NDJSON file per table.
+//!
+//! Exports are snapshots for other tools; queries keep running against
+//! the store itself. Rows are written in a total order (`ORDER BY ALL`)
+//! so two exports of the same store are byte-identical. Paths are
+//! workspace-relative as stored, or prefixed with the build root for
+//! tools that open files directly.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use arrow::record_batch::RecordBatch;
+use serde::Serialize;
+
+use super::DbStore;
+
+/// Tables written by [`export`], in order, each with its file path
+/// column.
+pub const EXPORTED_TABLES: &[(&str, &str)] = &[
+    ("file", "path"),
+    ("symbol", "file_path"),
+    ("span", "file_path"),
+    ("raw_import", "file_path"),
+    ("comment", "file_path"),
+    ("parse_error", "file_path"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Arrow IPC file format (`.arrow`), readable by pyarrow, polars, ...
+    ArrowIpc,
+    /// One JSON object per row (`.ndjson`).
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::ArrowIpc => "arrow",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedTable {
+    pub table: String,
+    pub path: PathBuf,
+    pub rows: usize,
+}
+
+/// Write every table in [`EXPORTED_TABLES`] to `dir` as
+/// `<table>.<extension>`, creating `dir` if needed. Existing files of
+/// the same name are replaced. With `absolute_paths`, path columns hold
+/// `<build root>/<path>` instead; symbol ids keep the relative form.
+pub fn export(
+    store: &DbStore,
+    dir: &Path,
+    format: ExportFormat,
+    absolute_paths: bool,
+) -> Result<Vec<ExportedTable>> {
+    let root = match absolute_paths {
+        true => match store.build_root()? {
+            Some(root) => Some(root.to_string_lossy().replace('\'', "''")),
+            None => bail!("the store records no build root; rebuild it to export absolute paths"),
+        },
+        false => None,
+    };
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    EXPORTED_TABLES
+        .iter()
+        .map(|(table, path_column)| {
+            let select = match &root {
+                Some(root) => format!(
+                    "SELECT * REPLACE ('{root}/' || {path_column} AS {path_column}) \
+                     FROM {table} ORDER BY ALL"
+                ),
+                None => format!("SELECT * FROM {table} ORDER BY ALL"),
+            };
+            let path = dir.join(format!("{table}.{}", format.extension()));
+            let rows = export_table(store, table, &select, &path, format)
+                .with_context(|| format!("failed to export {table} to {}", path.display()))?;
+            Ok(ExportedTable {
+                table: table.to_string(),
+                path,
+                rows,
+            })
+        })
+        .collect()
+}
+
+fn export_table(
+    store: &DbStore,
+    table: &str,
+    select: &str,
+    path: &Path,
+    format: ExportFormat,
+) -> Result<usize> {
+    let (schema, batches) = store.with_conn(|conn| -> Result<_> {
+        let mut stmt = conn
+            .prepare(select)
+            .map_err(|e| anyhow!("failed to query {table}: {e}"))?;
+        let arrow = stmt
+            .query_arrow([])
+            .map_err(|e| anyhow!("failed to query {table}: {e}"))?;
+        let schema = arrow.get_schema();
+        Ok((schema, arrow.collect::<Vec<RecordBatch>>()))
+    })?;
+    let rows = batches.iter().map(RecordBatch::num_rows).sum();
+
+    let out = BufWriter::new(File::create(path)?);
+    match format {
+        ExportFormat::ArrowIpc => {
+            let mut writer = arrow::ipc::writer::FileWriter::try_new(out, &schema)?;
+            for batch in &batches {
+                writer.write(batch)?;
+            }
+            writer.finish()?;
+        }
+        ExportFormat::Ndjson => {
+            let mut writer = arrow::json::LineDelimitedWriter::new(out);
+            for batch in &batches {
+                writer.write(batch)?;
+            }
+            writer.finish()?;
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use arrow::array::{Array, StringArray};
+
+    use super::*;
+    use crate::graph::builder::GraphBuilder;
+    use crate::language::Language;
+    use crate::storage::workspace::Workspace;
+
+    fn rust_store(dir: &Path) -> DbStore {
+        std::fs::write(dir.join("lib.rs"), "pub fn hello() {}\npub struct World;\n").unwrap();
+        let langs = [Language::Rust];
+        let ws = Workspace::load(dir, &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        crate::db::populate(&store, &graph, Some(&ws)).unwrap();
+        store
+    }
+
+    fn ndjson_column(path: &Path, column: &str) -> Vec<String> {
+        std::io::BufReader::new(File::open(path).unwrap())
+            .lines()
+            .map(|line| {
+                let row: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+                row[column].as_str().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn symbols_round_trip_through_arrow_ipc() {
+        let src = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let store = rust_store(src.path());
+        let exported = export(&store, out.path(), ExportFormat::ArrowIpc, false).unwrap();
+        assert_eq!(
+            exported
+                .iter()
+                .map(|t| t.table.as_str())
+                .collect::<Vec<_>>(),
+            EXPORTED_TABLES.iter().map(|(t, _)| *t).collect::<Vec<_>>()
+        );
+
+        let reader = arrow::ipc::reader::FileReader::try_new(
+            File::open(out.path().join("symbol.arrow")).unwrap(),
+            None,
+        )
+        .unwrap();
+        let mut names = Vec::new();
+        for batch in reader {
+            let batch = batch.unwrap();
+            let column = batch.column_by_name("name").unwrap();
+            let column = column.as_any().downcast_ref::<StringArray>().unwrap();
+            names.extend((0..column.len()).map(|i| column.value(i).to_string()));
+        }
+        names.sort();
+        assert_eq!(names, vec!["World", "hello"]);
+        let symbol = exported.iter().find(|t| t.table == "symbol").unwrap();
+        assert_eq!(symbol.rows, 2);
+    }
+
+    #[test]
+    fn symbols_round_trip_through_ndjson() {
+        let src = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let store = rust_store(src.path());
+        export(&store, out.path(), ExportFormat::Ndjson, false).unwrap();
+
+        let file = File::open(out.path().join("symbol.ndjson")).unwrap();
+        let mut rows: Vec<(String, String)> = std::io::BufReader::new(file)
+            .lines()
+            .map(|line| {
+                let row: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+                (
+                    row["name"].as_str().unwrap().to_string(),
+                    row["kind"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        rows.sort();
+        assert_eq!(
+            rows,
+            vec![
+                ("World".to_string(), "struct".to_string()),
+                ("hello".to_string(), "function".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn absolute_paths_prefix_the_build_root() {
+        let src = tempfile::tempdir().unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let store = rust_store(src.path());
+
+        export(&store, out.path(), ExportFormat::Ndjson, false).unwrap();
+        assert_eq!(
+            ndjson_column(&out.path().join("file.ndjson"), "path"),
+            vec!["lib.rs"]
+        );
+
+        export(&store, out.path(), ExportFormat::Ndjson, true).unwrap();
+        let root = store.build_root().unwrap().unwrap();
+        let expected = format!("{}/lib.rs", root.display());
+        assert!(Path::new(&expected).is_absolute());
+        assert_eq!(
+            ndjson_column(&out.path().join("file.ndjson"), "path"),
+            vec![expected.clone()]
+        );
+        let symbol_files = ndjson_column(&out.path().join("symbol.ndjson"), "file_path");
+        assert_eq!(symbol_files, vec![expected.clone(), expected]);
+    }
+}
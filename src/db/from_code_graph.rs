@@ -11,7 +11,7 @@
 //! - `is_warm_compatible` — `DbStore::open_persistent` already
 //!   version-checks via `build_meta`; warm reuse is "fresh = false".
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use anyhow::Result;
 use duckdb::types::Value;
@@ -28,7 +28,8 @@ use super::{DbStore, DbWriter};
 /// / throws / field_types are now emitted file-locally during absorb,
 /// so this phase only:
 ///   - resolves staged `raw_inheritance` rows into `extends` / `implements`
-///   - records workspace file metadata
+///     and records every named base in `base_type`
+///   - records the workspace root, build provenance and file metadata
 ///   - resolves call sites into `call_edge`
 pub fn populate(store: &DbStore, _graph: &CodeGraph, workspace: Option<&Workspace>) -> Result<()> {
     info!(
@@ -41,6 +42,8 @@ pub fn populate(store: &DbStore, _graph: &CodeGraph, workspace: Option<&Workspac
     }
     if let Some(ws) = workspace {
         let mut writer = DbWriter::new();
+        writer.push_build_meta("root", &ws.root().to_string_lossy());
+        record_provenance(ws, &mut writer);
         record_build_meta_files(ws, &mut writer);
         writer.flush(store)?;
     }
@@ -127,6 +130,18 @@ fn resolve_inheritance(store: &DbStore) -> Result<()> {
                 app.append_row(duckdb::params![c, p])?;
             }
         }
+        // `extends`/`implements` only keep bases that resolve; record
+        // every named base so subclasses of external types are findable.
+        conn.execute(
+            "INSERT INTO base_type \
+             SELECT DISTINCT child.id, ri.parent_leaf, ri.kind \
+             FROM raw_inheritance ri \
+             JOIN symbol child \
+               ON child.file_path = ri.file_path \
+              AND child.name = ri.child_name \
+              AND child.kind = ri.child_kind",
+            [],
+        )?;
         // Staging table is no longer needed; drop it to free pages.
         conn.execute("DELETE FROM raw_inheritance", [])?;
         Ok(())
@@ -186,7 +201,7 @@ fn resolve_and_emit_call_edges(store: &DbStore, writer: &mut DbWriter) -> Result
 
     let sym_rows = store.run_query(
         "SELECT id, name, file_path, kind, exported, parent_id FROM symbol \
-         WHERE kind IN ('function', 'method', 'arrow_function', 'macro')",
+         WHERE kind IN ('function', 'method', 'arrow_function', 'component', 'macro')",
         std::collections::BTreeMap::new(),
     )?;
     let mut intra: HashMap<(String, String), Vec<(String, String)>> =
@@ -430,21 +445,54 @@ pub fn is_doc_comment(kind: &str, text: &str) -> bool {
         || trimmed.starts_with("/*!")
 }
 
-pub fn detect_todo_kind(text: &str) -> Option<&'static str> {
-    for kind in ["TODO", "FIXME", "XXX", "HACK"] {
-        if text.contains(kind) {
-            return Some(match kind {
-                "TODO" => "TODO",
-                "FIXME" => "FIXME",
-                "XXX" => "XXX",
-                "HACK" => "HACK",
-                _ => unreachable!(),
-            });
+/// Code-debt markers recognised by [`detect_todo`], stored upper-case in
+/// `comment.todo_kind`.
+const TODO_MARKERS: &[&str] = &["TODO", "FIXME", "HACK", "XXX", "NOTE"];
+
+/// Find the first code-debt marker in a comment and the text after it.
+///
+/// Markers match case-insensitively on word boundaries, so `TODOS` or
+/// `xxxl` don't count. `NOTE` only counts as `NOTE:` — bare "note" is
+/// ordinary prose. The captured text skips an optional `(owner)` tag
+/// and `:`/`-` separator and stops at the end of the marker's line.
+pub fn detect_todo(text: &str) -> Option<(&'static str, Option<String>)> {
+    let bytes = text.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    for start in 0..bytes.len() {
+        if start > 0 && is_word(bytes[start - 1]) {
+            continue;
+        }
+        for &marker in TODO_MARKERS {
+            let end = start + marker.len();
+            let Some(candidate) = text.get(start..end) else {
+                continue;
+            };
+            if !candidate.eq_ignore_ascii_case(marker)
+                || bytes.get(end).is_some_and(|&b| is_word(b))
+                || (marker == "NOTE" && bytes.get(end) != Some(&b':'))
+            {
+                continue;
+            }
+            return Some((marker, todo_text(&text[end..])));
         }
     }
     None
 }
 
+fn todo_text(rest: &str) -> Option<String> {
+    let mut rest = rest.lines().next().unwrap_or("");
+    if let Some(tagged) = rest.strip_prefix('(')
+        && let Some(close) = tagged.find(')')
+    {
+        rest = &tagged[close + 1..];
+    }
+    let rest = rest
+        .trim_start_matches(|c: char| c == ':' || c == '-' || c.is_whitespace())
+        .trim_end();
+    let rest = rest.strip_suffix("*/").unwrap_or(rest).trim_end();
+    (!rest.is_empty()).then(|| rest.to_string())
+}
+
 /// Canonical String id for a symbol per ADR-0002.
 pub fn symbol_id(
     file_path: &str,
@@ -468,6 +516,24 @@ pub fn type_id(language: &str, file_path: &str, display_name: &str) -> String {
     format!("type:{h:016x}")
 }
 
+/// Record how the store was produced, so a copied store file still says
+/// which virgil built it, when, and over which languages and files.
+fn record_provenance(workspace: &Workspace, writer: &mut DbWriter) {
+    let languages: BTreeSet<&str> = workspace
+        .files()
+        .iter()
+        .filter_map(|path| workspace.file_language(path))
+        .map(|lang| lang.as_str())
+        .collect();
+    writer.push_build_meta("virgil_version", env!("CARGO_PKG_VERSION"));
+    writer.push_build_meta("built_at", &chrono::Utc::now().to_rfc3339());
+    writer.push_build_meta(
+        "languages",
+        &languages.into_iter().collect::<Vec<_>>().join(","),
+    );
+    writer.push_build_meta("file_count", &workspace.file_count().to_string());
+}
+
 fn record_build_meta_files(workspace: &Workspace, writer: &mut DbWriter) {
     let root = workspace.root();
     let on_disk = root.exists();
@@ -535,3 +601,40 @@ fn find_nolint(line: &str) -> Option<&str> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn todo_marker_with_text() {
+        assert_eq!(
+            detect_todo("// TODO: fix this"),
+            Some(("TODO", Some("fix this".to_string())))
+        );
+    }
+
+    #[test]
+    fn todo_marker_is_case_insensitive_with_owner_tag() {
+        assert_eq!(
+            detect_todo("# fixme(alice) - handle EOF"),
+            Some(("FIXME", Some("handle EOF".to_string())))
+        );
+        assert_eq!(detect_todo("/* HACK */"), Some(("HACK", None)));
+    }
+
+    #[test]
+    fn todo_marker_needs_word_boundaries() {
+        assert_eq!(detect_todo("// list of TODOS"), None);
+        assert_eq!(detect_todo("// size XXXL"), None);
+    }
+
+    #[test]
+    fn note_needs_a_colon() {
+        assert_eq!(detect_todo("// note that this is fine"), None);
+        assert_eq!(
+            detect_todo("// NOTE: order matters"),
+            Some(("NOTE", Some("order matters".to_string())))
+        );
+    }
+}
@@ -5,6 +5,7 @@
 //! writer, the populate tail, and a queries helper. There is no
 //! `incremental` module (deferred — cold + warm only).
 
+pub mod export;
 pub mod from_code_graph;
 pub mod queries;
 pub mod schema;
@@ -12,7 +13,7 @@ pub mod store;
 pub mod writer;
 
 pub use from_code_graph::populate;
-pub use store::{DbStore, cache_dir_for_db};
+pub use store::{DbStore, LOADABLE_EXTENSIONS, StoreOptions, cache_dir_for_db, check_extension};
 pub use writer::DbWriter;
 
 /// Bump when the schema in [`schema`] changes shape in a way that
@@ -26,4 +27,25 @@ pub use writer::DbWriter;
 ///   construct (for_statement, if_statement, …) instead of generic "block".
 /// - 4: add `local_type` (local variable -> declared/inferred type name)
 ///   for type-aware call resolution.
-pub const SCHEMA_VERSION: u32 = 4;
+/// - 5: add `file.line_count` for size filters on the `files` template.
+/// - 6: add `comment.todo_text`; `todo_kind` matching is now
+///   case-insensitive on word boundaries and recognises `NOTE:`.
+/// - 7: add `file.encoding`; UTF-16 and Latin-1 sources are decoded
+///   instead of being skipped.
+/// - 8: add `parse_error` (files that failed to read or parse cleanly).
+/// - 9: add `raw_import.external_kind` (`builtin` / `package` for JS/TS).
+/// - 10: add `symbol.is_test` (test file, or inside a Rust `#[cfg(test)]`
+///   module).
+/// - 11: add `raw_import.is_external`, overridable with `--internal-prefix`.
+/// - 12: add `comment.clean_text` (delimiters and gutters stripped).
+/// - 13: add `raw_import.imported_name` / `local_name` (one row per
+///   imported binding, `*` for whole-module imports).
+/// - 14: add `symbol.loc` (non-blank, non-comment lines in the span).
+/// - 15: add `typescript_attrs.decorators`.
+/// - 16: add `base_type` (base-clause names kept even when unresolved).
+/// - 17: add `parse_error.detail`; files decoded as Latin-1 are recorded
+///   as `encoding` errors.
+/// - 18: add `comment.start_line` / `end_line`.
+/// - 19: add `annotation` (Java annotations with their argument text).
+/// - 20: add `symbol.is_deprecated`.
+pub const SCHEMA_VERSION: u32 = 20;
@@ -17,7 +17,9 @@ pub fn create_statements() -> &'static [&'static str] {
         "CREATE TABLE file (\
             path VARCHAR PRIMARY KEY, \
             language VARCHAR NOT NULL, \
-            repo_id VARCHAR NOT NULL\
+            repo_id VARCHAR NOT NULL, \
+            line_count BIGINT NOT NULL, \
+            encoding VARCHAR NOT NULL\
          )",
         "CREATE TABLE symbol (\
             id VARCHAR PRIMARY KEY, \
@@ -32,7 +34,10 @@ pub fn create_statements() -> &'static [&'static str] {
             is_static BOOLEAN NOT NULL, \
             is_abstract BOOLEAN NOT NULL, \
             is_mutable BOOLEAN NOT NULL, \
-            exported BOOLEAN NOT NULL\
+            exported BOOLEAN NOT NULL, \
+            is_test BOOLEAN NOT NULL, \
+            is_deprecated BOOLEAN NOT NULL, \
+            loc BIGINT\
          )",
         // span: positional metadata per entity. entity_id is a
         // symbol/comment/call-site id.
@@ -115,6 +120,14 @@ pub fn create_statements() -> &'static [&'static str] {
             interface_id VARCHAR NOT NULL, \
             PRIMARY KEY (impl_id, interface_id)\
          )",
+        // Every base type named in a class's base clause, by its leaf
+        // name, whether or not it resolves to a workspace symbol.
+        "CREATE TABLE base_type (\
+            symbol_id VARCHAR NOT NULL, \
+            name VARCHAR NOT NULL, \
+            kind VARCHAR NOT NULL, \
+            PRIMARY KEY (symbol_id, name, kind)\
+         )",
         "CREATE TABLE imports (\
             importer_file_id VARCHAR NOT NULL, \
             imported_id VARCHAR NOT NULL, \
@@ -125,8 +138,12 @@ pub fn create_statements() -> &'static [&'static str] {
             file_path VARCHAR NOT NULL, \
             position BIGINT NOT NULL, \
             raw_path VARCHAR NOT NULL, \
+            imported_name VARCHAR NOT NULL, \
+            local_name VARCHAR NOT NULL, \
             language VARCHAR NOT NULL, \
             kind VARCHAR NOT NULL, \
+            is_external BOOLEAN NOT NULL, \
+            external_kind VARCHAR, \
             PRIMARY KEY (file_path, position)\
          )",
         // ─── signatures & types ────────────────────────────────────────────
@@ -180,9 +197,13 @@ pub fn create_statements() -> &'static [&'static str] {
             kind VARCHAR NOT NULL, \
             is_doc BOOLEAN NOT NULL, \
             text VARCHAR NOT NULL, \
+            clean_text VARCHAR, \
             todo_kind VARCHAR, \
+            todo_text VARCHAR, \
             start_byte BIGINT NOT NULL, \
-            end_byte BIGINT NOT NULL\
+            end_byte BIGINT NOT NULL, \
+            start_line BIGINT NOT NULL, \
+            end_line BIGINT NOT NULL\
          )",
         // ─── per-language attribute tables (populated lazily by language) ──
         "CREATE TABLE rust_attrs (\
@@ -202,7 +223,8 @@ pub fn create_statements() -> &'static [&'static str] {
             symbol_id VARCHAR PRIMARY KEY, \
             is_readonly BOOLEAN NOT NULL, \
             is_optional BOOLEAN NOT NULL, \
-            type_parameters VARCHAR[] NOT NULL\
+            type_parameters VARCHAR[] NOT NULL, \
+            decorators VARCHAR[] NOT NULL\
          )",
         "CREATE TABLE cpp_attrs (\
             symbol_id VARCHAR PRIMARY KEY, \
@@ -249,6 +271,15 @@ pub fn create_statements() -> &'static [&'static str] {
             is_synchronized BOOLEAN NOT NULL, \
             throws_clause VARCHAR[] NOT NULL\
          )",
+        // One row per Java annotation, in source order, with the text
+        // between its parentheses (null for a marker annotation).
+        "CREATE TABLE annotation (\
+            symbol_id VARCHAR NOT NULL, \
+            position BIGINT NOT NULL, \
+            name VARCHAR NOT NULL, \
+            arguments VARCHAR, \
+            PRIMARY KEY (symbol_id, position)\
+         )",
         // ─── staging tables (parse-time, dropped after resolve) ───────────
         // Inheritance is the one extractor output that needs cross-file
         // symbol-id resolution. Workers write rows here during absorb;
@@ -277,6 +308,13 @@ pub fn create_statements() -> &'static [&'static str] {
             suppressed_pattern VARCHAR NOT NULL, \
             PRIMARY KEY (file_path, line)\
          )",
+        "CREATE TABLE parse_error (\
+            file_path VARCHAR PRIMARY KEY, \
+            language VARCHAR NOT NULL, \
+            error_type VARCHAR NOT NULL, \
+            size_bytes BIGINT NOT NULL, \
+            detail VARCHAR\
+         )",
         // ─── metadata ──────────────────────────────────────────────────────
         "CREATE TABLE build_meta (\
             key VARCHAR PRIMARY KEY, \
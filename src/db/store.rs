@@ -14,8 +14,37 @@ use duckdb::Connection;
 use duckdb::types::Value;
 
 use super::SCHEMA_VERSION;
+use super::export::EXPORTED_TABLES;
 use super::schema;
 
+/// DuckDB extensions a query session may load with
+/// [`DbStore::with_extensions`]. The flag refuses anything else, and
+/// `with_extensions` then locks the session's configuration so inline
+/// `INSTALL`/`LOAD` statements in a query can't load more.
+pub const LOADABLE_EXTENSIONS: &[&str] = &["fts", "icu", "inet", "json", "parquet", "spatial"];
+
+/// Error unless `name` is in [`LOADABLE_EXTENSIONS`].
+pub fn check_extension(name: &str) -> Result<()> {
+    if LOADABLE_EXTENSIONS.contains(&name) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "extension '{name}' is not allowed; expected one of: {}",
+            LOADABLE_EXTENSIONS.join(", ")
+        ))
+    }
+}
+
+/// DuckDB resource caps applied with [`DbStore::with_options`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StoreOptions {
+    /// `SET memory_limit`, in MiB. DuckDB spills or fails a query past
+    /// it instead of growing until the OS kills the process.
+    pub memory_limit_mb: Option<u64>,
+    /// `SET threads`: worker threads DuckDB may use per query.
+    pub threads: Option<usize>,
+}
+
 /// A DuckDB database handle.
 pub struct DbStore {
     conn: Mutex<Connection>,
@@ -69,6 +98,108 @@ impl DbStore {
         Ok(store)
     }
 
+    /// Open an already-built store read-only, so a stray `INSERT` or
+    /// `DROP` in a user query fails instead of changing the store. The
+    /// schema version is not checked; callers open stores they (or a
+    /// previous `open_persistent`) just validated.
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        let config = duckdb::Config::default()
+            .access_mode(duckdb::AccessMode::ReadOnly)
+            .map_err(|e| anyhow!("failed to configure read-only access: {e}"))?;
+        let conn = Connection::open_with_flags(path, config).map_err(|e| {
+            anyhow!(
+                "failed to open duckdb store read-only at {}: {e}",
+                path.display()
+            )
+        })?;
+        let store = Self {
+            conn: Mutex::new(conn),
+            fresh: false,
+        };
+        store.load_duckpgq()?;
+        Ok(store)
+    }
+
+    /// Apply `options` to this connection's session.
+    pub fn with_options(self, options: StoreOptions) -> Result<Self> {
+        {
+            let conn = self.conn.lock().unwrap();
+            if let Some(mb) = options.memory_limit_mb {
+                conn.execute_batch(&format!("SET memory_limit = '{mb}MB';"))
+                    .map_err(|e| anyhow!("failed to set memory_limit to {mb}MB: {e}"))?;
+            }
+            if let Some(threads) = options.threads {
+                conn.execute_batch(&format!("SET threads = {threads};"))
+                    .map_err(|e| anyhow!("failed to set threads to {threads}: {e}"))?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// `INSTALL` and `LOAD` each of `names`, which must all be in
+    /// [`LOADABLE_EXTENSIONS`], on this connection, then turn off
+    /// community and automatic extension loading and lock the
+    /// configuration. Call it last: no `SET` succeeds afterwards, and
+    /// a user query's own `INSTALL`/`LOAD` of anything not already
+    /// loaded fails.
+    pub fn with_extensions(self, names: &[String]) -> Result<Self> {
+        for name in names {
+            check_extension(name)?;
+        }
+        {
+            let conn = self.conn.lock().unwrap();
+            for name in names {
+                install_extension(&conn, name)?;
+                conn.execute_batch(&format!("LOAD {name};"))
+                    .map_err(|e| anyhow!("LOAD {name} failed: {e}"))?;
+            }
+            conn.execute_batch(
+                "SET allow_community_extensions = false; \
+                 SET autoinstall_known_extensions = false; \
+                 SET autoload_known_extensions = false; \
+                 SET lock_configuration = true;",
+            )
+            .map_err(|e| anyhow!("failed to lock extension loading: {e}"))?;
+        }
+        Ok(self)
+    }
+
+    /// Attach other built stores read-only and create one `all_<table>`
+    /// temp view per [`EXPORTED_TABLES`] table, unioning this store's
+    /// rows (labelled `name`) with each attached store's (labelled with
+    /// its own name) under a leading `dataset` column. Columns are
+    /// matched by name, so a store built by an older version reads as
+    /// nulls for columns it lacks.
+    pub fn with_datasets(self, name: &str, others: &[(String, PathBuf)]) -> Result<Self> {
+        if others.is_empty() {
+            return Ok(self);
+        }
+        {
+            let conn = self.conn.lock().unwrap();
+            let quote = |s: &str| s.replace('\'', "''");
+            for (i, (label, path)) in others.iter().enumerate() {
+                let path = path.to_string_lossy();
+                conn.execute_batch(&format!(
+                    "ATTACH '{}' AS dataset_{i} (READ_ONLY);",
+                    quote(&path)
+                ))
+                .map_err(|e| anyhow!("failed to attach {label} ({path}): {e}"))?;
+            }
+            for (table, _) in EXPORTED_TABLES {
+                let mut select = format!("SELECT '{}' AS dataset, * FROM {table}", quote(name));
+                for (i, (label, _)) in others.iter().enumerate() {
+                    select.push_str(&format!(
+                        " UNION ALL BY NAME SELECT '{}' AS dataset, * FROM dataset_{i}.{table}",
+                        quote(label)
+                    ));
+                }
+                conn.execute_batch(&format!("CREATE TEMP VIEW all_{table} AS {select};"))
+                    .map_err(|e| anyhow!("failed to create all_{table}: {e}"))?;
+            }
+        }
+        Ok(self)
+    }
+
     fn try_reopen(path: &Path) -> Result<Option<Self>> {
         if path.is_dir() {
             return Ok(None);
@@ -104,6 +235,38 @@ impl DbStore {
         self.fresh
     }
 
+    /// Workspace root the store was built from, recorded in
+    /// `build_meta(root)` by `populate`. `None` for stores populated
+    /// without a workspace.
+    pub fn build_root(&self) -> Result<Option<PathBuf>> {
+        let rows = self.run_query(
+            "SELECT value FROM build_meta WHERE key = 'root'",
+            BTreeMap::new(),
+        )?;
+        Ok(rows.rows.first().and_then(|row| match &row[0] {
+            Value::Text(root) => Some(PathBuf::from(root)),
+            _ => None,
+        }))
+    }
+
+    /// Every `build_meta` entry: the schema version, the workspace root
+    /// and the provenance `populate` records (`virgil_version`,
+    /// `built_at`, `languages`, `file_count`).
+    pub fn build_meta(&self) -> Result<BTreeMap<String, String>> {
+        let rows = self.run_query(
+            "SELECT key, value FROM build_meta ORDER BY key",
+            BTreeMap::new(),
+        )?;
+        Ok(rows
+            .rows
+            .iter()
+            .filter_map(|row| match (&row[0], &row[1]) {
+                (Value::Text(key), Value::Text(value)) => Some((key.clone(), value.clone())),
+                _ => None,
+            })
+            .collect())
+    }
+
     /// Open a new sibling connection to the already-opened database via
     /// `Connection::try_clone`. Used by serve mode to build a pool of
     /// read connections, one per concurrent query worker — DuckDB
@@ -188,14 +351,34 @@ impl DbStore {
     /// `column_count` on a prepared-but-not-yet-queried statement
     /// panics in duckdb 1.2 — the schema isn't bound until execution.
     pub fn run_query(&self, sql: &str, params: BTreeMap<String, Value>) -> Result<QueryRows> {
+        self.run_query_with_args(sql, params, &[])
+    }
+
+    /// [`run_query`](Self::run_query) plus positional `?` placeholders,
+    /// bound by DuckDB rather than inlined — for callers passing
+    /// untrusted strings. `args.len()` must match the placeholder count.
+    /// Not usable inside `GRAPH_TABLE` (see [`inline_named_params`]).
+    pub fn run_query_with_args(
+        &self,
+        sql: &str,
+        params: BTreeMap<String, Value>,
+        args: &[Value],
+    ) -> Result<QueryRows> {
         let conn = self.conn.lock().unwrap();
         let stripped = strip_sql_comments(sql);
         let inlined = inline_named_params(&stripped, &params);
         let mut stmt = conn
             .prepare(&inlined)
             .map_err(|e| anyhow!("duckdb prepare failed: {e}\nsql: {inlined}"))?;
+        let expected = stmt.parameter_count();
+        if expected != args.len() {
+            return Err(anyhow!(
+                "query has {expected} `?` placeholder(s) but {} positional argument(s) were given",
+                args.len()
+            ));
+        }
         let mut rows = stmt
-            .query([])
+            .query(duckdb::params_from_iter(args.iter()))
             .map_err(|e| anyhow!("duckdb query failed: {e}\nsql: {inlined}"))?;
         let mut out: Vec<Vec<Value>> = Vec::new();
         let mut headers: Vec<String> = Vec::new();
@@ -407,6 +590,16 @@ fn ensure_duckpgq_installed(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// `INSTALL name`, serialised across the process for the same reason
+/// as [`ensure_duckpgq_installed`]. Already-installed extensions are a
+/// no-op for DuckDB.
+fn install_extension(conn: &Connection, name: &str) -> Result<()> {
+    static INSTALLING: Mutex<()> = Mutex::new(());
+    let _guard = INSTALLING.lock().unwrap();
+    conn.execute_batch(&format!("INSTALL {name};"))
+        .map_err(|e| anyhow!("INSTALL {name} failed: {e}"))
+}
+
 /// Cache file path for a workspace identified by `id`. Returns
 /// `~/.cache/virgil/<hash>.duckdb`.
 pub fn cache_dir_for_db(id: &str) -> Result<PathBuf> {
@@ -417,7 +610,9 @@ pub fn cache_dir_for_db(id: &str) -> Result<PathBuf> {
     Ok(base.join(format!("{hash:016x}.duckdb")))
 }
 
-fn stable_hash(s: &str) -> u64 {
+/// FNV-1a 64 of `s`: stable across runs and platforms, unlike
+/// `DefaultHasher`, so it can name files on disk.
+pub fn stable_hash(s: &str) -> u64 {
     // FNV-1a 64. Same hash function as cozo::cache_dir_for so the
     // cache_dir_for("foo") path differs from cache_dir_for_db("foo")
     // only by file extension.
@@ -448,6 +643,37 @@ mod tests {
         assert_eq!(rows.rows[0][0], Value::Text(SCHEMA_VERSION.to_string()));
     }
 
+    #[test]
+    fn positional_args_are_bound_not_inlined() {
+        let store = DbStore::open_in_memory().expect("open");
+        store
+            .run_script(
+                "INSERT INTO symbol VALUES \
+                 ('a.ts|1|0|login|function', 'function', 'login', 'login', \
+                  'typescript', 'public', 'a.ts', NULL, \
+                  false, false, false, false, true, false, false, NULL)",
+                BTreeMap::new(),
+            )
+            .expect("insert");
+        let sql = "SELECT file_path FROM symbol WHERE name = ?";
+
+        let rows = store
+            .run_query_with_args(sql, BTreeMap::new(), &[Value::Text("login".into())])
+            .expect("query");
+        assert_eq!(rows.rows, vec![vec![Value::Text("a.ts".into())]]);
+
+        // A quote-laden value is data, not SQL.
+        let rows = store
+            .run_query_with_args(sql, BTreeMap::new(), &[Value::Text("x' OR '1'='1".into())])
+            .expect("query");
+        assert!(rows.rows.is_empty());
+
+        let err = store
+            .run_query_with_args(sql, BTreeMap::new(), &[])
+            .unwrap_err();
+        assert!(err.to_string().contains("1 `?` placeholder"), "{err}");
+    }
+
     #[test]
     fn persistent_store_round_trips() {
         let dir = tempdir().unwrap();
@@ -460,7 +686,7 @@ mod tests {
                 "INSERT INTO symbol VALUES \
                  ('a.ts|1|0|login|function', 'function', 'login', 'login', \
                   'typescript', 'public', 'a.ts', NULL, \
-                  false, false, false, false, true)",
+                  false, false, false, false, true, false, false, NULL)",
                 BTreeMap::new(),
             )
             .expect("insert");
@@ -475,6 +701,33 @@ mod tests {
         assert_eq!(rows.rows[0][0], Value::Text("login".into()));
     }
 
+    #[test]
+    fn read_only_store_honours_options_and_rejects_writes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.duckdb");
+        drop(DbStore::open_persistent(&path).expect("open fresh"));
+
+        let store = DbStore::open_read_only(&path)
+            .expect("open read-only")
+            .with_options(StoreOptions {
+                memory_limit_mb: Some(64),
+                threads: Some(1),
+            })
+            .expect("apply options");
+        let rows = store
+            .run_query("SELECT count(*) FROM range(100000)", BTreeMap::new())
+            .expect("query under a low memory limit");
+        assert_eq!(rows.rows, vec![vec![Value::BigInt(100000)]]);
+
+        let err = store
+            .run_script(
+                "INSERT INTO build_meta(key, value) VALUES ('k', 'v')",
+                BTreeMap::new(),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{err}");
+    }
+
     #[test]
     fn pgq_match_walks_call_edges() {
         // Seeds three symbols + two call_edges (a→b→c) and asks PGQ
@@ -486,11 +739,11 @@ mod tests {
             .run_script(
                 "INSERT INTO symbol VALUES \
                    ('a', 'function', 'a', 'a', 'rust', 'public', 'lib.rs', NULL, \
-                    false, false, false, false, true), \
+                    false, false, false, false, true, false, false, NULL), \
                    ('b', 'function', 'b', 'b', 'rust', 'public', 'lib.rs', NULL, \
-                    false, false, false, false, true), \
+                    false, false, false, false, true, false, false, NULL), \
                    ('c', 'function', 'c', 'c', 'rust', 'public', 'lib.rs', NULL, \
-                    false, false, false, false, true)",
+                    false, false, false, false, true, false, false, NULL)",
                 BTreeMap::new(),
             )
             .expect("insert symbols");
@@ -536,7 +789,7 @@ mod tests {
         // Smoke-check that each .sql template under
         // src/queries/builtin/ at least parses cleanly through DuckDB
         // (and through duckpgq for the PGQ-flavored ones). Runs each
-        // template against an empty store with a dummy $name binding;
+        // template against an empty store with dummy $name/$file/$depth bindings;
         // expects zero rows but no error. Reports every failure so we
         // see the full picture in one run.
         let store = DbStore::open_in_memory().expect("open");
@@ -548,13 +801,15 @@ mod tests {
             .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("sql"))
             .collect();
         paths.sort();
-        assert_eq!(paths.len(), 7, "expected 7 .sql templates");
+        assert_eq!(paths.len(), 8, "expected 8 .sql templates");
         let mut failures = Vec::new();
         for path in &paths {
             let sql = std::fs::read_to_string(path)
                 .unwrap_or_else(|e| panic!("read {}: {e}", path.display()));
             let mut params = BTreeMap::new();
             params.insert("name".to_string(), Value::Text("__dummy__".to_string()));
+            params.insert("file".to_string(), Value::Text("__dummy__".to_string()));
+            params.insert("depth".to_string(), Value::BigInt(2));
             if let Err(e) = store.run_query(&sql, params) {
                 failures.push(format!(
                     "  {} -> {e}",
@@ -581,9 +836,9 @@ mod tests {
             .run_script(
                 "INSERT INTO symbol VALUES \
                    ('a', 'function', 'a', 'a', 'rust', 'public', 'lib.rs', NULL, \
-                    false, false, false, false, true), \
+                    false, false, false, false, true, false, false, NULL), \
                    ('b', 'function', 'b', 'b', 'rust', 'public', 'lib.rs', NULL, \
-                    false, false, false, false, true)",
+                    false, false, false, false, true, false, false, NULL)",
                 BTreeMap::new(),
             )
             .expect("insert");
@@ -618,4 +873,97 @@ mod tests {
         assert_ne!(a, b);
         assert!(a.to_str().unwrap().ends_with(".duckdb"));
     }
+
+    #[test]
+    fn loads_allowed_extensions_and_refuses_others() {
+        let store = DbStore::open_in_memory()
+            .unwrap()
+            .with_extensions(&["json".to_string()])
+            .expect("load json");
+        let rows = store
+            .run_query(
+                "SELECT json_extract_string('{\"lang\": \"rust\"}', '$.lang')",
+                BTreeMap::new(),
+            )
+            .expect("query");
+        assert_eq!(rows.rows, vec![vec![Value::Text("rust".into())]]);
+
+        let err = DbStore::open_in_memory()
+            .unwrap()
+            .with_extensions(&["httpfs".to_string()])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("not allowed"), "{err}");
+    }
+
+    #[test]
+    fn query_sql_cannot_load_other_extensions() {
+        let store = DbStore::open_in_memory()
+            .unwrap()
+            .with_extensions(&[])
+            .expect("lock");
+        for sql in [
+            "SET allow_community_extensions = true",
+            "SET autoload_known_extensions = true",
+            "INSTALL h3 FROM community",
+            "LOAD h3",
+        ] {
+            assert!(
+                store.run_script(sql, BTreeMap::new()).is_err(),
+                "{sql} should fail"
+            );
+        }
+        let rows = store.run_query("SELECT 1", BTreeMap::new()).expect("query");
+        assert_eq!(rows.rows, vec![vec![Value::Int(1)]]);
+    }
+
+    #[test]
+    fn datasets_union_rows_under_a_dataset_label() {
+        let dir = tempdir().unwrap();
+        let insert = |path: &Path, names: &[&str]| {
+            let store = DbStore::open_persistent(path).expect("open");
+            for name in names {
+                store
+                    .run_script(
+                        &format!(
+                            "INSERT INTO symbol VALUES \
+                             ('lib.rs|1|0|{name}|function', 'function', '{name}', '{name}', \
+                              'rust', 'public', 'lib.rs', NULL, \
+                              false, false, false, false, true, false, false, NULL)"
+                        ),
+                        BTreeMap::new(),
+                    )
+                    .expect("insert");
+            }
+        };
+        let api = dir.path().join("api.duckdb");
+        let web = dir.path().join("web.duckdb");
+        insert(&api, &["serve", "route"]);
+        insert(&web, &["render"]);
+
+        let store = DbStore::open_read_only(&api)
+            .unwrap()
+            .with_datasets("api", &[("web".to_string(), web.clone())])
+            .expect("attach");
+        let rows = store
+            .run_query(
+                "SELECT dataset, count(*) FROM all_symbol GROUP BY dataset ORDER BY dataset",
+                BTreeMap::new(),
+            )
+            .expect("query");
+        assert_eq!(
+            rows.rows,
+            vec![
+                vec![Value::Text("api".into()), Value::BigInt(2)],
+                vec![Value::Text("web".into()), Value::BigInt(1)],
+            ]
+        );
+        let rows = store
+            .run_query(
+                "SELECT dataset FROM all_symbol WHERE name = 'render'",
+                BTreeMap::new(),
+            )
+            .expect("query");
+        assert_eq!(rows.rows, vec![vec![Value::Text("web".into())]]);
+    }
 }
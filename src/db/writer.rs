@@ -46,6 +46,7 @@ pub struct DbWriter {
     comment: Vec<Row>,
     file_classification: Vec<Row>,
     nolint: Vec<Row>,
+    parse_error: Vec<Row>,
     build_meta: Vec<Row>,
     build_meta_files: Vec<Row>,
     occurrence: Vec<Row>,
@@ -61,6 +62,7 @@ pub struct DbWriter {
     php_attrs: Vec<Row>,
     c_attrs: Vec<Row>,
     java_attrs: Vec<Row>,
+    annotation: Vec<Row>,
 }
 
 impl DbWriter {
@@ -90,6 +92,7 @@ impl DbWriter {
         self.file_classification
             .append(&mut other.file_classification);
         self.nolint.append(&mut other.nolint);
+        self.parse_error.append(&mut other.parse_error);
         self.build_meta.append(&mut other.build_meta);
         self.build_meta_files.append(&mut other.build_meta_files);
         self.occurrence.append(&mut other.occurrence);
@@ -105,11 +108,24 @@ impl DbWriter {
         self.php_attrs.append(&mut other.php_attrs);
         self.c_attrs.append(&mut other.c_attrs);
         self.java_attrs.append(&mut other.java_attrs);
+        self.annotation.append(&mut other.annotation);
     }
 
-    pub fn push_file(&mut self, path: &str, language: &str, repo_id: &str) {
-        self.file
-            .push(vec![text(path), text(language), text(repo_id)]);
+    pub fn push_file(
+        &mut self,
+        path: &str,
+        language: &str,
+        repo_id: &str,
+        line_count: i64,
+        encoding: &str,
+    ) {
+        self.file.push(vec![
+            text(path),
+            text(language),
+            text(repo_id),
+            Value::BigInt(line_count),
+            text(encoding),
+        ]);
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -128,6 +144,9 @@ impl DbWriter {
         is_abstract: bool,
         is_mutable: bool,
         exported: bool,
+        is_test: bool,
+        is_deprecated: bool,
+        loc: Option<i64>,
     ) {
         self.symbol.push(vec![
             text(id),
@@ -143,6 +162,9 @@ impl DbWriter {
             Value::Boolean(is_abstract),
             Value::Boolean(is_mutable),
             Value::Boolean(exported),
+            Value::Boolean(is_test),
+            Value::Boolean(is_deprecated),
+            loc.map_or(Value::Null, big),
         ]);
     }
 
@@ -257,20 +279,29 @@ impl DbWriter {
             .push(vec![text(importer_file_id), text(imported_id)]);
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn push_raw_import(
         &mut self,
         file_path: &str,
         position: i64,
         raw_path: &str,
+        imported_name: &str,
+        local_name: &str,
         language: &str,
         kind: &str,
+        is_external: bool,
+        external_kind: Option<&str>,
     ) {
         self.raw_import.push(vec![
             text(file_path),
             big(position),
             text(raw_path),
+            text(imported_name),
+            text(local_name),
             text(language),
             text(kind),
+            Value::Boolean(is_external),
+            opt_text(external_kind),
         ]);
     }
 
@@ -338,9 +369,13 @@ impl DbWriter {
         kind: &str,
         is_doc: bool,
         text_body: &str,
+        clean_text: Option<&str>,
         todo_kind: Option<&str>,
+        todo_text: Option<&str>,
         start_byte: i64,
         end_byte: i64,
+        start_line: i64,
+        end_line: i64,
     ) {
         self.comment.push(vec![
             text(id),
@@ -349,9 +384,13 @@ impl DbWriter {
             text(kind),
             Value::Boolean(is_doc),
             text(text_body),
+            opt_text(clean_text),
             opt_text(todo_kind),
+            opt_text(todo_text),
             big(start_byte),
             big(end_byte),
+            big(start_line),
+            big(end_line),
         ]);
     }
 
@@ -375,6 +414,23 @@ impl DbWriter {
             .push(vec![text(file_path), big(line), text(suppressed_pattern)]);
     }
 
+    pub fn push_parse_error(
+        &mut self,
+        file_path: &str,
+        language: &str,
+        error_type: &str,
+        size_bytes: i64,
+        detail: Option<&str>,
+    ) {
+        self.parse_error.push(vec![
+            text(file_path),
+            text(language),
+            text(error_type),
+            big(size_bytes),
+            opt_text(detail),
+        ]);
+    }
+
     pub fn push_build_meta(&mut self, key: &str, value: &str) {
         self.build_meta.push(vec![text(key), text(value)]);
     }
@@ -497,12 +553,14 @@ impl DbWriter {
         is_readonly: bool,
         is_optional: bool,
         type_parameters: &[String],
+        decorators: &[String],
     ) {
         self.typescript_attrs.push(vec![
             text(symbol_id),
             Value::Boolean(is_readonly),
             Value::Boolean(is_optional),
             list_text(type_parameters),
+            list_text(decorators),
         ]);
     }
 
@@ -616,6 +674,21 @@ impl DbWriter {
         ]);
     }
 
+    pub fn push_annotation(
+        &mut self,
+        symbol_id: &str,
+        position: i64,
+        name: &str,
+        arguments: Option<&str>,
+    ) {
+        self.annotation.push(vec![
+            text(symbol_id),
+            big(position),
+            text(name),
+            opt_text(arguments),
+        ]);
+    }
+
     /// Flush every buffered relation to `store`. Empty buffers are
     /// skipped.
     ///
@@ -651,6 +724,7 @@ impl DbWriter {
                 &mut self.file_classification,
             )?;
             flush_table(conn, "nolint", 2, &mut self.nolint)?;
+            flush_table(conn, "parse_error", 1, &mut self.parse_error)?;
             flush_table(conn, "build_meta", 1, &mut self.build_meta)?;
             flush_table(conn, "build_meta_files", 1, &mut self.build_meta_files)?;
             flush_table(conn, "occurrence", 1, &mut self.occurrence)?;
@@ -671,6 +745,7 @@ impl DbWriter {
             flush_table_with_arrays(conn, "php_attrs", 1, &mut self.php_attrs)?;
             flush_table_with_arrays(conn, "c_attrs", 1, &mut self.c_attrs)?;
             flush_table_with_arrays(conn, "java_attrs", 1, &mut self.java_attrs)?;
+            flush_table(conn, "annotation", 2, &mut self.annotation)?;
             Ok(())
         })
     }
@@ -857,7 +932,7 @@ mod tests {
         let store = DbStore::open_in_memory().expect("open");
         let mut writer = DbWriter::new();
 
-        writer.push_file("src/a.ts", "typescript", "");
+        writer.push_file("src/a.ts", "typescript", "", 1, "utf-8");
         writer.push_symbol(
             "src/a.ts|1|0|login|function",
             "function",
@@ -872,6 +947,9 @@ mod tests {
             false,
             false,
             true,
+            false,
+            false,
+            None,
         );
         writer.push_symbol(
             "src/a.ts|11|0|checkPassword|function",
@@ -887,6 +965,9 @@ mod tests {
             false,
             false,
             false,
+            false,
+            false,
+            None,
         );
         writer.push_calls(
             "src/a.ts|1|0|login|function",
@@ -934,7 +1015,7 @@ mod tests {
     fn writer_pushes_attrs_with_list_columns() {
         let store = DbStore::open_in_memory().expect("open");
         let mut w = DbWriter::new();
-        w.push_file("src/lib.rs", "rust", "");
+        w.push_file("src/lib.rs", "rust", "", 1, "utf-8");
         w.push_symbol(
             "src/lib.rs|1|0|foo|function",
             "function",
@@ -949,6 +1030,9 @@ mod tests {
             false,
             false,
             true,
+            false,
+            false,
+            None,
         );
         w.push_rust_attrs(
             "src/lib.rs|1|0|foo|function",
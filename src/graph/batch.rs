@@ -0,0 +1,245 @@
+//! Build the stores of several projects in one process (`projects
+//! build`).
+//!
+//! A bounded set of worker threads takes projects off a shared queue;
+//! each build still parses its own files in parallel on the rayon pool.
+//! Every build shares one [`CompiledQueries`], so a grammar's queries
+//! are compiled once however many projects use it. A project that fails
+//! is reported in its summary and doesn't stop the others.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::Result;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::db::{self, DbStore, StoreOptions};
+use crate::language::Language;
+use crate::storage::workspace::Workspace;
+
+use super::builder::{BuildOptions, CompiledQueries, GraphBuilder};
+use super::parse_errors;
+
+/// One project to build: its source root and the store file it builds
+/// into.
+pub struct BuildTarget {
+    pub project: String,
+    pub root: PathBuf,
+    pub languages: Vec<Language>,
+    pub store_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    /// Projects built at once; at least 1.
+    pub jobs: usize,
+    /// Wipe existing stores instead of keeping warm ones.
+    pub rebuild: bool,
+    pub max_file_size: Option<u64>,
+    pub store_options: StoreOptions,
+    /// Applied to every cold build, as `projects query` applies them.
+    pub build: BuildOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BuildSummary {
+    pub project: String,
+    pub store: PathBuf,
+    /// `cold` (built now), `warm` (store already current, left alone)
+    /// or `failed`.
+    pub state: &'static str,
+    pub files: usize,
+    pub parse_errors: usize,
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Build every target's store, `options.jobs` at a time. Summaries come
+/// back in `targets` order.
+pub fn build_all(targets: &[BuildTarget], options: &BatchOptions) -> Vec<BuildSummary> {
+    let queries = Arc::new(CompiledQueries::new());
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<BuildSummary>>> =
+        Mutex::new(targets.iter().map(|_| None).collect());
+    let workers = options.jobs.clamp(1, targets.len().max(1));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(target) = targets.get(index) else {
+                        break;
+                    };
+                    let summary = build_one(target, options, &queries);
+                    results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(summary);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn build_one(
+    target: &BuildTarget,
+    options: &BatchOptions,
+    queries: &Arc<CompiledQueries>,
+) -> BuildSummary {
+    let start = Instant::now();
+    let outcome = try_build(target, options, queries);
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    let (state, files, parse_errors, error) = match outcome {
+        Ok((state, files, parse_errors)) => (state, files, parse_errors, None),
+        Err(e) => ("failed", 0, 0, Some(format!("{e:#}"))),
+    };
+    info!(project = %target.project, state, files, elapsed_ms, "project build finished");
+    BuildSummary {
+        project: target.project.clone(),
+        store: target.store_path.clone(),
+        state,
+        files,
+        parse_errors,
+        elapsed_ms,
+        error,
+    }
+}
+
+/// `(state, files, parse_errors)` of one build. A warm store reports
+/// the file count its build recorded, so the tree isn't walked again.
+fn try_build(
+    target: &BuildTarget,
+    options: &BatchOptions,
+    queries: &Arc<CompiledQueries>,
+) -> Result<(&'static str, usize, usize)> {
+    if options.rebuild && target.store_path.exists() {
+        std::fs::remove_file(&target.store_path)?;
+    }
+    let store =
+        DbStore::open_persistent(&target.store_path)?.with_options(options.store_options)?;
+    if !store.fresh() {
+        let unapplied = options.build.unapplied_flags();
+        if !unapplied.is_empty() {
+            warn!(
+                project = %target.project,
+                flags = %unapplied.join(", "),
+                "store already built; pass --rebuild to apply these flags"
+            );
+        }
+        let files = store
+            .build_meta()?
+            .get("file_count")
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0);
+        let errors = parse_errors::load(&store)?;
+        return Ok(("warm", files, errors.len()));
+    }
+    let workspace = Workspace::load(&target.root, &target.languages, options.max_file_size)?;
+    let graph = GraphBuilder::new(&workspace, &target.languages)
+        .with_options(&options.build)
+        .with_compiled_queries(Arc::clone(queries))
+        .build(&store)?;
+    db::populate(&store, &graph, Some(&workspace))?;
+    Ok(("cold", workspace.file_count(), graph.parse_errors.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_each_root_into_its_own_store() {
+        let repos = tempfile::tempdir().unwrap();
+        let stores = tempfile::tempdir().unwrap();
+        let targets: Vec<BuildTarget> = ["alpha", "beta"]
+            .iter()
+            .map(|name| {
+                let root = repos.path().join(name);
+                std::fs::create_dir(&root).unwrap();
+                std::fs::write(root.join("lib.rs"), format!("pub fn {name}_entry() {{}}\n"))
+                    .unwrap();
+                BuildTarget {
+                    project: name.to_string(),
+                    root,
+                    languages: vec![Language::Rust],
+                    store_path: stores.path().join(name).join("store.duckdb"),
+                }
+            })
+            .collect();
+        let options = BatchOptions {
+            jobs: 2,
+            ..BatchOptions::default()
+        };
+
+        let summaries = build_all(&targets, &options);
+        let states: Vec<(&str, &str)> = summaries
+            .iter()
+            .map(|s| (s.project.as_str(), s.state))
+            .collect();
+        assert_eq!(states, vec![("alpha", "cold"), ("beta", "cold")]);
+
+        for target in &targets {
+            let store = DbStore::open_persistent(&target.store_path).unwrap();
+            let rows = store
+                .run_query(
+                    "SELECT name FROM symbol WHERE kind = 'function'",
+                    Default::default(),
+                )
+                .unwrap();
+            assert_eq!(
+                rows.rows,
+                vec![vec![duckdb::types::Value::Text(format!(
+                    "{}_entry",
+                    target.project
+                ))]]
+            );
+        }
+
+        let again = build_all(&targets, &options);
+        assert!(again.iter().all(|s| s.state == "warm"), "{again:?}");
+        assert!(again.iter().all(|s| s.files == 1), "{again:?}");
+    }
+
+    #[test]
+    fn applies_build_options_to_cold_builds() {
+        let repo = tempfile::tempdir().unwrap();
+        let stores = tempfile::tempdir().unwrap();
+        std::fs::write(
+            repo.path().join("lib.rs"),
+            "pub struct Keep;\npub fn drop_me() {}\n",
+        )
+        .unwrap();
+        let target = BuildTarget {
+            project: "only-structs".to_string(),
+            root: repo.path().to_path_buf(),
+            languages: vec![Language::Rust],
+            store_path: stores.path().join("store.duckdb"),
+        };
+        let options = BatchOptions {
+            jobs: 1,
+            build: BuildOptions {
+                symbol_kinds: Some(crate::models::parse_symbol_kinds("struct").unwrap()),
+                ..BuildOptions::default()
+            },
+            ..BatchOptions::default()
+        };
+
+        let summaries = build_all(std::slice::from_ref(&target), &options);
+        assert_eq!(summaries[0].state, "cold", "{summaries:?}");
+        let store = DbStore::open_persistent(&target.store_path).unwrap();
+        let rows = store
+            .run_query("SELECT name FROM symbol", Default::default())
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![vec![duckdb::types::Value::Text("Keep".to_string())]]
+        );
+    }
+}
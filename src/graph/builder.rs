@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
@@ -10,7 +11,7 @@ use tree_sitter::Query;
 
 use crate::classify::{is_barrel_file, is_test_file};
 use crate::db::from_code_graph::{
-    detect_todo_kind, extract_nolints, is_doc_comment, is_generated_marker, symbol_id, type_id,
+    detect_todo, extract_nolints, is_doc_comment, is_generated_marker, symbol_id, type_id,
 };
 use crate::db::{DbStore, DbWriter};
 use crate::graph::GraphNode;
@@ -22,8 +23,10 @@ use crate::models::{
     ReferencesBucket, ReturnsTypeRow, SymbolInfo, SymbolKind, ThrowsRow, TypeRow,
 };
 use crate::parser;
+use crate::storage::encoding::{self, SourceEncoding};
 use crate::storage::workspace::Workspace;
 
+use super::parse_errors::{ParseError, ParseErrorKind};
 use super::{CodeGraph, Spur, Symbols};
 
 /// Flush the streaming writer every this many files. Caps peak writer
@@ -32,6 +35,10 @@ use super::{CodeGraph, Spur, Symbols};
 /// high defeats streaming.
 const STREAM_FLUSH_EVERY_N_FILES: u32 = 200;
 
+/// Advance the parse progress bar every this many absorbed files rather
+/// than per file, so redraw bookkeeping stays off the absorb hot path.
+const PROGRESS_TICK_EVERY_N_FILES: u64 = 32;
+
 /// Eager import resolution. Build-time resolver maps each
 /// `*raw_import{module_specifier}` to a concrete file path using the
 /// per-language `languages::resolve_import` logic, then emits
@@ -72,6 +79,19 @@ struct AbsorbedSymbol {
 struct FileGraphData {
     path: String,
     language: Language,
+    size_bytes: u64,
+    /// The tree has `ERROR`/`MISSING` nodes. Still absorbed, but also
+    /// recorded as a `syntax_error` parse error.
+    has_syntax_errors: bool,
+    /// Offset of the first invalid UTF-8 byte when the file was decoded
+    /// as Latin-1; recorded as an `encoding` parse error.
+    invalid_utf8_at: Option<usize>,
+    /// Package / file-level namespace prepended to top-level qualified
+    /// names (`com.example.`); see [`languages::qualified_name_prefix`].
+    qname_prefix: Option<String>,
+    /// Byte ranges of test code (Rust `#[cfg(test)]` modules); symbols
+    /// inside are `is_test` even when the file itself isn't.
+    test_ranges: Vec<(u32, u32)>,
     symbols: Vec<SymbolInfo>,
     comments: Vec<CommentInfo>,
     imports: Vec<ImportInfo>,
@@ -145,11 +165,100 @@ struct SharedAbsorb {
     /// Files absorbed since the last flush. Triggers a `writer.flush`
     /// every `STREAM_FLUSH_EVERY_N_FILES` to cap peak memory.
     files_since_flush: u32,
+    parse_errors: Vec<ParseError>,
+}
+
+/// Compiled symbol / import / comment queries per language. A build
+/// compiles only the languages its workspace contains; sharing one
+/// instance across builds (`projects build` over several projects)
+/// compiles each language once per process instead of once per
+/// project. All builds sharing it must use the same `--query-dir`.
+#[derive(Default)]
+pub struct CompiledQueries {
+    maps: Mutex<QueryMaps>,
+}
+
+#[derive(Default)]
+struct QueryMaps {
+    symbol: HashMap<Language, Arc<Query>>,
+    import: HashMap<Language, Arc<Query>>,
+    comment: HashMap<Language, Arc<Query>>,
+}
+
+impl CompiledQueries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The queries for `langs`, compiling any not seen before.
+    fn for_languages(&self, langs: &[Language], query_dir: Option<&Path>) -> Result<QueryMaps> {
+        let mut maps = self.maps.lock().unwrap_or_else(|e| e.into_inner());
+        for &lang in langs {
+            if maps.symbol.contains_key(&lang) {
+                continue;
+            }
+            maps.symbol
+                .insert(lang, languages::compile_symbol_query_from(lang, query_dir)?);
+            maps.import
+                .insert(lang, languages::compile_import_query(lang)?);
+            if let Ok(q) = languages::compile_comment_query(lang) {
+                maps.comment.insert(lang, q);
+            }
+        }
+        let pick = |map: &HashMap<Language, Arc<Query>>| {
+            langs
+                .iter()
+                .filter_map(|lang| Some((*lang, Arc::clone(map.get(lang)?))))
+                .collect()
+        };
+        Ok(QueryMaps {
+            symbol: pick(&maps.symbol),
+            import: pick(&maps.import),
+            comment: pick(&maps.comment),
+        })
+    }
+}
+
+/// Extraction settings a store is built with (`--symbol-kinds`,
+/// `--symbol-name-regex`, `--query-dir`, `--internal-prefix`), shared by
+/// `projects query` and `projects build` so both produce the same store
+/// from the same flags.
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    pub symbol_kinds: Option<HashSet<SymbolKind>>,
+    pub symbol_name_regex: Option<regex::Regex>,
+    pub query_dir: Option<PathBuf>,
+    pub internal_prefixes: Vec<String>,
+}
+
+impl BuildOptions {
+    /// Flags that were given but only take effect on a fresh build.
+    pub fn unapplied_flags(&self) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        if self.symbol_kinds.is_some() {
+            flags.push("--symbol-kinds");
+        }
+        if self.symbol_name_regex.is_some() {
+            flags.push("--symbol-name-regex");
+        }
+        if self.query_dir.is_some() {
+            flags.push("--query-dir");
+        }
+        if !self.internal_prefixes.is_empty() {
+            flags.push("--internal-prefix");
+        }
+        flags
+    }
 }
 
 pub struct GraphBuilder<'a> {
     workspace: &'a Workspace,
     languages: &'a [Language],
+    symbol_kinds: Option<HashSet<SymbolKind>>,
+    symbol_name_regex: Option<regex::Regex>,
+    query_dir: Option<PathBuf>,
+    internal_prefixes: Vec<String>,
+    queries: Option<Arc<CompiledQueries>>,
 }
 
 impl<'a> GraphBuilder<'a> {
@@ -157,9 +266,66 @@ impl<'a> GraphBuilder<'a> {
         Self {
             workspace,
             languages,
+            symbol_kinds: None,
+            symbol_name_regex: None,
+            query_dir: None,
+            internal_prefixes: Vec::new(),
+            queries: None,
         }
     }
 
+    /// Keep only symbols of these kinds. Applied straight after
+    /// extraction, so calls made from a dropped function are recorded as
+    /// top-level calls and its parameters vanish with it.
+    pub fn with_symbol_kinds(mut self, kinds: HashSet<SymbolKind>) -> Self {
+        self.symbol_kinds = Some(kinds);
+        self
+    }
+
+    /// Keep only symbols whose name matches `re`, filtered alongside
+    /// [`Self::with_symbol_kinds`] and with the same effect on calls and
+    /// parameters of dropped symbols.
+    pub fn with_symbol_name_regex(mut self, re: regex::Regex) -> Self {
+        self.symbol_name_regex = Some(re);
+        self
+    }
+
+    /// Read `<lang>.symbols.scm` overrides of the built-in symbol
+    /// queries from `dir`; languages without one keep the built-in.
+    pub fn with_query_dir(mut self, dir: PathBuf) -> Self {
+        self.query_dir = Some(dir);
+        self
+    }
+
+    /// Treat imports whose specifier starts with one of `prefixes`
+    /// (`@app/*`, `~/`) as internal, overriding the per-language
+    /// heuristic. See [`ImportInfo::apply_internal_prefixes`].
+    pub fn with_internal_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.internal_prefixes = prefixes;
+        self
+    }
+
+    /// Apply every setting in `options`.
+    pub fn with_options(mut self, options: &BuildOptions) -> Self {
+        if let Some(kinds) = &options.symbol_kinds {
+            self = self.with_symbol_kinds(kinds.clone());
+        }
+        if let Some(re) = &options.symbol_name_regex {
+            self = self.with_symbol_name_regex(re.clone());
+        }
+        if let Some(dir) = &options.query_dir {
+            self = self.with_query_dir(dir.clone());
+        }
+        self.with_internal_prefixes(options.internal_prefixes.clone())
+    }
+
+    /// Take compiled queries from `queries`, shared with other builds,
+    /// instead of compiling a private set.
+    pub fn with_compiled_queries(mut self, queries: Arc<CompiledQueries>) -> Self {
+        self.queries = Some(queries);
+        self
+    }
+
     pub fn build(&self, store: &DbStore) -> Result<CodeGraph> {
         let total_files = self.workspace.file_count();
         info!(
@@ -186,19 +352,15 @@ impl<'a> GraphBuilder<'a> {
             "compiling grammars for languages actually present"
         );
 
-        let mut symbol_queries: HashMap<Language, Arc<Query>> = HashMap::new();
-        let mut import_queries: HashMap<Language, Arc<Query>> = HashMap::new();
-        let mut comment_queries: HashMap<Language, Arc<Query>> = HashMap::new();
-        for &lang in &present_langs {
-            symbol_queries.insert(lang, languages::compile_symbol_query(lang)?);
-            import_queries.insert(lang, languages::compile_import_query(lang)?);
-            if let Ok(q) = languages::compile_comment_query(lang) {
-                comment_queries.insert(lang, q);
+        let maps = match &self.queries {
+            Some(shared) => shared.for_languages(&present_langs, self.query_dir.as_deref())?,
+            None => {
+                CompiledQueries::new().for_languages(&present_langs, self.query_dir.as_deref())?
             }
-        }
-        let symbol_queries = Arc::new(symbol_queries);
-        let import_queries = Arc::new(import_queries);
-        let comment_queries = Arc::new(comment_queries);
+        };
+        let symbol_queries = Arc::new(maps.symbol);
+        let import_queries = Arc::new(maps.import);
+        let comment_queries = Arc::new(maps.comment);
 
         // Step 2: Build known_files set
         let known_files: HashSet<String> = self.workspace.files().iter().cloned().collect();
@@ -229,7 +391,11 @@ impl<'a> GraphBuilder<'a> {
         // Sharing the writer keeps memory near baseline; the absorb
         // critical section is short (just appends to Vecs) so the
         // mutex doesn't dominate wall time.
+        //
+        // Sized from the global pool so `--threads` (which configures
+        // the global pool in `main`) bounds parsing too.
         let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(rayon::current_num_threads())
             .stack_size(4 * 1024 * 1024)
             .build()
             .unwrap_or_else(|_| rayon::ThreadPoolBuilder::new().build().unwrap());
@@ -260,6 +426,7 @@ impl<'a> GraphBuilder<'a> {
 
         let (
             mut stream_writer,
+            mut parse_errors,
             deferred_imports,
             deferred_calls,
             file_symbols_by_name,
@@ -277,10 +444,16 @@ impl<'a> GraphBuilder<'a> {
             );
             let _enter = span.enter();
 
+            // Rayon workers don't inherit the entered span, so the bar is
+            // driven through an explicit handle, not `Span::current()`.
+            let progress = &span;
             let parsed_ref = &parsed;
             let absorbed_ref = &absorbed_files;
             let repo_id_ref = repo_id.as_str();
             let interner = &shared_symbols;
+            let symbol_kinds = self.symbol_kinds.as_ref();
+            let symbol_name_regex = self.symbol_name_regex.as_ref();
+            let internal_prefixes = self.internal_prefixes.as_slice();
 
             // One shared writer + cross-file scratch, behind a mutex.
             // The lock is held only across `absorb_file_data` (Vec
@@ -294,20 +467,81 @@ impl<'a> GraphBuilder<'a> {
                 file_exports_by_name: HashMap::new(),
                 file_known_spurs: HashSet::new(),
                 files_since_flush: 0,
+                parse_errors: Vec::new(),
             });
+            {
+                let mut state = shared.lock().expect("shared absorb mutex poisoned");
+                for (rel_path, lang, size_bytes) in workspace.oversized_files() {
+                    if self.languages.contains(lang) {
+                        // Streamed, so a huge data file is never held in
+                        // memory; the size alone is kept when it can't be
+                        // read (archive entries, races with deletion).
+                        let lines = std::fs::File::open(workspace.root().join(rel_path))
+                            .and_then(|file| count_lines(std::io::BufReader::new(file)))
+                            .ok();
+                        record_parse_error(
+                            &mut state,
+                            rel_path,
+                            *lang,
+                            ParseErrorKind::SkippedTooLarge,
+                            *size_bytes,
+                            lines.map(|n| format!("{n} lines")),
+                        );
+                    }
+                }
+            }
 
             pool.install(|| -> Result<()> {
                 grouped_files_ref
                     .par_iter()
                     .try_for_each(|&(lang, rel_path)| -> Result<()> {
-                        let Some(data) =
-                            parse_one_file(lang, rel_path, workspace, &sym_q, &imp_q, &com_q)
-                        else {
-                            return Ok(());
+                        let parsed = parse_one_file(
+                            lang,
+                            rel_path,
+                            workspace,
+                            &sym_q,
+                            &imp_q,
+                            &com_q,
+                            symbol_kinds,
+                            symbol_name_regex,
+                            internal_prefixes,
+                        );
+                        let data = match parsed {
+                            Ok(data) => data,
+                            Err(kind) => {
+                                let size_bytes = std::fs::metadata(workspace.root().join(rel_path))
+                                    .map(|m| m.len())
+                                    .unwrap_or(0);
+                                let mut state =
+                                    shared.lock().expect("shared absorb mutex poisoned");
+                                record_parse_error(
+                                    &mut state, rel_path, lang, kind, size_bytes, None,
+                                );
+                                return Ok(());
+                            }
                         };
                         parsed_ref.fetch_add(1, Ordering::Relaxed);
                         let mut state = shared.lock().expect("shared absorb mutex poisoned");
                         let state = &mut *state;
+                        if data.has_syntax_errors {
+                            record_parse_error(
+                                state,
+                                rel_path,
+                                lang,
+                                ParseErrorKind::Syntax,
+                                data.size_bytes,
+                                None,
+                            );
+                        } else if let Some(at) = data.invalid_utf8_at {
+                            record_parse_error(
+                                state,
+                                rel_path,
+                                lang,
+                                ParseErrorKind::Encoding,
+                                data.size_bytes,
+                                Some(format!("invalid UTF-8 at byte {at}; decoded as Latin-1")),
+                            );
+                        }
                         absorb_file_data(
                             interner,
                             data,
@@ -320,8 +554,10 @@ impl<'a> GraphBuilder<'a> {
                             &mut state.file_known_spurs,
                             &mut state.writer,
                         );
-                        absorbed_ref.fetch_add(1, Ordering::Relaxed);
-                        tracing::Span::current().pb_inc(1);
+                        let absorbed = absorbed_ref.fetch_add(1, Ordering::Relaxed) + 1;
+                        if absorbed % PROGRESS_TICK_EVERY_N_FILES == 0 {
+                            progress.pb_set_position(absorbed);
+                        }
                         state.files_since_flush += 1;
                         if state.files_since_flush >= STREAM_FLUSH_EVERY_N_FILES {
                             state.writer.flush(store)?;
@@ -338,8 +574,10 @@ impl<'a> GraphBuilder<'a> {
                 file_symbols_by_name,
                 file_exports_by_name,
                 file_known_spurs,
+                parse_errors,
                 ..
             } = shared.into_inner().expect("shared absorb mutex poisoned");
+            span.pb_set_position(absorbed_files.load(Ordering::Relaxed));
             // Flush the writer's tail rows before cross-file resolution
             // runs — keeps populate's later phases from racing with
             // leftover per-file rows.
@@ -349,10 +587,12 @@ impl<'a> GraphBuilder<'a> {
                 absorbed = absorbed_files.load(Ordering::Relaxed),
                 deferred_imports = deferred_imports.len(),
                 deferred_calls = deferred_calls.len(),
+                parse_errors = parse_errors.len(),
                 "parse + absorb done"
             );
             (
                 stream_writer,
+                parse_errors,
                 deferred_imports,
                 deferred_calls,
                 file_symbols_by_name,
@@ -363,7 +603,10 @@ impl<'a> GraphBuilder<'a> {
         // CodeGraph is a vestigial wrapper around the shared interner
         // after the SQL-staging refactor; still returned to keep the
         // public API stable for callers that take a `&CodeGraph`.
-        let graph = CodeGraph::with_symbols(shared_symbols);
+        let mut graph = CodeGraph::with_symbols(shared_symbols);
+        // Workers record in completion order; sort for a stable report.
+        parse_errors.sort_by(|a, b| a.path.cmp(&b.path));
+        graph.parse_errors = parse_errors;
 
         let _resolve_span = info_span!("graph.resolve_refs").entered();
 
@@ -465,6 +708,7 @@ impl<'a> GraphBuilder<'a> {
                         SymbolKind::Function
                             | SymbolKind::Method
                             | SymbolKind::ArrowFunction
+                            | SymbolKind::Component
                             | SymbolKind::Macro
                     )
                 });
@@ -510,6 +754,7 @@ impl<'a> GraphBuilder<'a> {
 
 /// Parse a single file and produce its `FileGraphData`. Runs on a rayon
 /// worker; the parser instance is local and dropped on return.
+#[allow(clippy::too_many_arguments)]
 fn parse_one_file(
     lang: Language,
     rel_path: &str,
@@ -517,21 +762,60 @@ fn parse_one_file(
     symbol_queries: &HashMap<Language, Arc<Query>>,
     import_queries: &HashMap<Language, Arc<Query>>,
     comment_queries: &HashMap<Language, Arc<Query>>,
-) -> Option<FileGraphData> {
-    let sym_query = symbol_queries.get(&lang)?;
-    let imp_query = import_queries.get(&lang)?;
+    symbol_kinds: Option<&HashSet<SymbolKind>>,
+    symbol_name_regex: Option<&regex::Regex>,
+    internal_prefixes: &[String],
+) -> std::result::Result<FileGraphData, ParseErrorKind> {
+    let (Some(sym_query), Some(imp_query)) = (symbol_queries.get(&lang), import_queries.get(&lang))
+    else {
+        return Err(ParseErrorKind::ParserUnavailable);
+    };
 
-    let mut ts_parser = parser::create_parser(lang).ok()?;
-    let source = workspace.read_file(rel_path)?;
-    let tree = ts_parser.parse(&*source, None)?;
+    let mut ts_parser =
+        parser::create_parser(lang).map_err(|_| ParseErrorKind::ParserUnavailable)?;
+    let source = workspace.read_file(rel_path).ok_or(ParseErrorKind::Read)?;
+    let tree =
+        parser::parse_source(&mut ts_parser, &source, lang).ok_or(ParseErrorKind::ParseFailed)?;
+    let has_syntax_errors = tree.root_node().has_error();
+    let invalid_utf8_at = match workspace.file_encoding(rel_path) {
+        Some(SourceEncoding::Latin1) => encoding::invalid_utf8_offset(&source),
+        _ => None,
+    };
 
-    let symbols = languages::extract_symbols(&tree, source.as_bytes(), sym_query, rel_path, lang);
-    let imports = languages::extract_imports(&tree, source.as_bytes(), imp_query, rel_path, lang);
+    let mut symbols =
+        languages::extract_symbols(&tree, source.as_bytes(), sym_query, rel_path, lang);
+    if let Some(kinds) = symbol_kinds {
+        symbols.retain(|s| kinds.contains(&s.kind));
+    }
+    if let Some(re) = symbol_name_regex {
+        symbols.retain(|s| re.is_match(&s.name));
+    }
+    let mut imports =
+        languages::extract_imports(&tree, source.as_bytes(), imp_query, rel_path, lang);
+    if !internal_prefixes.is_empty() {
+        for import in &mut imports {
+            import.apply_internal_prefixes(internal_prefixes);
+        }
+    }
+    let qname_prefix = languages::qualified_name_prefix(&tree, source.as_bytes(), lang);
+    let test_ranges = languages::test_ranges(&tree, source.as_bytes(), lang);
     let comments = if let Some(cq) = comment_queries.get(&lang) {
         languages::extract_comments(&tree, source.as_bytes(), cq, rel_path, lang)
     } else {
         Vec::new()
     };
+    let comment_ranges: Vec<(u32, u32)> = comments
+        .iter()
+        .map(|c| (c.start_byte, c.end_byte))
+        .collect();
+    for sym in &mut symbols {
+        sym.loc = Some(code_line_count(
+            source.as_bytes(),
+            sym.start_byte,
+            sym.end_byte,
+            &comment_ranges,
+        ));
+    }
 
     let call_node_types = call_expression_types(lang);
     let mut call_sites = Vec::new();
@@ -552,6 +836,7 @@ fn parse_one_file(
                 SymbolKind::Function
                     | SymbolKind::Method
                     | SymbolKind::ArrowFunction
+                    | SymbolKind::Component
                     | SymbolKind::Macro
             )
         })
@@ -583,9 +868,14 @@ fn parse_one_file(
     let references =
         languages::extract_references(&tree, source.as_bytes(), rel_path, lang, &symbols);
 
-    Some(FileGraphData {
+    Ok(FileGraphData {
         path: rel_path.to_string(),
         language: lang,
+        size_bytes: source.len() as u64,
+        has_syntax_errors,
+        invalid_utf8_at,
+        qname_prefix,
+        test_ranges,
         symbols,
         comments,
         imports,
@@ -601,6 +891,51 @@ fn parse_one_file(
     })
 }
 
+/// Record a failed or partially parsed file, both on the returned
+/// graph and in the `parse_error` table.
+fn record_parse_error(
+    state: &mut SharedAbsorb,
+    rel_path: &str,
+    lang: Language,
+    kind: ParseErrorKind,
+    size_bytes: u64,
+    detail: Option<String>,
+) {
+    state.writer.push_parse_error(
+        rel_path,
+        lang.as_str(),
+        kind.as_str(),
+        size_bytes as i64,
+        detail.as_deref(),
+    );
+    state.parse_errors.push(ParseError {
+        path: rel_path.to_string(),
+        language: lang.as_str().to_string(),
+        error_type: kind.as_str().to_string(),
+        size_bytes,
+        detail,
+    });
+}
+
+/// Lines in `reader`, counted as `str::lines` would (a trailing line
+/// without a newline counts), reading one buffer at a time instead of
+/// the whole file.
+fn count_lines<R: std::io::BufRead>(mut reader: R) -> std::io::Result<u64> {
+    let mut lines = 0;
+    let mut last = None;
+    loop {
+        let buf = reader.fill_buf()?;
+        let Some(&end) = buf.last() else {
+            break;
+        };
+        lines += buf.iter().filter(|&&b| b == b'\n').count() as u64;
+        last = Some(end);
+        let len = buf.len();
+        reader.consume(len);
+    }
+    Ok(lines + u64::from(last.is_some_and(|b| b != b'\n')))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn absorb_file_data(
     interner: &Symbols,
@@ -617,6 +952,8 @@ fn absorb_file_data(
     let FileGraphData {
         path,
         language,
+        qname_prefix,
+        test_ranges,
         symbols,
         comments,
         imports,
@@ -629,6 +966,7 @@ fn absorb_file_data(
         throws,
         attrs,
         references,
+        ..
     } = data;
 
     let path_spur = interner.intern(&path);
@@ -638,15 +976,23 @@ fn absorb_file_data(
     // *file row + classification + nolints. These used to be emitted by
     // `from_code_graph::emit_node` for `NodeWeight::File`; folding them
     // into absorb lets the File "node" exist only as a Cozo row.
-    stream_writer.push_file(&path, language_str, repo_id);
     let src_for_marker = workspace.read_file(&path);
+    let line_count = src_for_marker
+        .as_ref()
+        .map(|src| src.lines().count() as i64)
+        .unwrap_or(0);
+    let encoding = workspace
+        .file_encoding(&path)
+        .unwrap_or(SourceEncoding::Utf8);
+    stream_writer.push_file(&path, language_str, repo_id, line_count, encoding.as_str());
     let is_generated = src_for_marker
         .as_ref()
         .map(|src| is_generated_marker(src))
         .unwrap_or(false);
+    let file_is_test = is_test_file(&path);
     stream_writer.push_file_classification(
         &path,
-        is_test_file(&path),
+        file_is_test,
         is_barrel_file(&path),
         is_generated,
     );
@@ -703,6 +1049,7 @@ fn absorb_file_data(
             SymbolKind::Function
                 | SymbolKind::Method
                 | SymbolKind::ArrowFunction
+                | SymbolKind::Component
                 | SymbolKind::Macro
         ) {
             local_id_by_line.insert(sym.start_line, id.clone());
@@ -755,13 +1102,21 @@ fn absorb_file_data(
         open.push((i, sym.end_byte));
     }
 
-    // Compute qualified_name in outer-first order.
+    // Compute qualified_name in outer-first order. Top-level symbols
+    // carry the file's package/namespace prefix, if any.
+    let prefix = qname_prefix.as_deref().unwrap_or("");
     let mut qnames: Vec<String> = vec![String::new(); symbols.len()];
     for &i in &order {
         let sym = &symbols[i];
         qnames[i] = match parent_of[i] {
-            Some(p) => format!("{}{}{}", &qnames[p], sep, sym.name),
-            None => sym.name.clone(),
+            Some(p) => {
+                let sep =
+                    languages::qname_member_separator(language, symbols[p].kind).unwrap_or(sep);
+                format!("{}{}{}", &qnames[p], sep, sym.name)
+            }
+            // A statement-form PHP namespace is the prefix itself.
+            None if sym.kind == SymbolKind::Namespace => sym.name.clone(),
+            None => format!("{prefix}{}", sym.name),
         };
     }
 
@@ -771,6 +1126,10 @@ fn absorb_file_data(
     // here removes the need for the adjacency lists.
     for (i, sym) in symbols.iter().enumerate() {
         let parent_id = parent_of[i].map(|p| symbol_ids[p].as_str());
+        let is_test = file_is_test
+            || test_ranges
+                .iter()
+                .any(|&(start, end)| start <= sym.start_byte && sym.end_byte <= end);
         stream_writer.push_symbol(
             &symbol_ids[i],
             sym.kind.to_string().as_str(),
@@ -785,6 +1144,9 @@ fn absorb_file_data(
             sym.is_abstract,
             sym.is_mutable,
             sym.is_exported,
+            is_test,
+            sym.is_deprecated,
+            sym.loc.map(i64::from),
         );
         stream_writer.push_span(
             &symbol_ids[i],
@@ -798,18 +1160,46 @@ fn absorb_file_data(
         );
     }
 
-    // Emit comment rows file-locally. `documents_id` is resolved
-    // against the same file's name_to_id map. Pre-refactor this lived
-    // on `graph.comments` and was emitted by `emit_comments` in the
-    // populate phase against `graph.symbol_ids_by_name`.
+    // Emit comment rows file-locally. `documents_id` is the innermost
+    // symbol of the associated name whose range contains the comment (a
+    // Python docstring sits inside its function), else the first one
+    // that starts after it (`///` above `new` in two impls), so
+    // same-named symbols in one file each get their own doc; the file's
+    // name_to_id map is the fallback.
+    // Pre-refactor this lived on `graph.comments` and was emitted by
+    // `emit_comments` in the populate phase against
+    // `graph.symbol_ids_by_name`. Ranges are indexed per name once so a
+    // comment costs a binary search, not a scan of every symbol.
+    let mut ranges_by_name: HashMap<&str, Vec<(u32, u32, &str)>> = HashMap::new();
+    for (sym, id) in symbols.iter().zip(&symbol_ids) {
+        ranges_by_name.entry(sym.name.as_str()).or_default().push((
+            sym.start_byte,
+            sym.end_byte,
+            id.as_str(),
+        ));
+    }
+    for ranges in ranges_by_name.values_mut() {
+        ranges.sort_by_key(|(start, _, _)| *start);
+    }
     for (i, c) in comments.iter().enumerate() {
         let id = format!("{}|{}|{}|comment", path, c.start_byte, i);
-        let documents_id = c
-            .associated_symbol
-            .as_ref()
-            .and_then(|name| name_to_id.get(name.as_str()).copied());
+        let documents_id = c.associated_symbol.as_ref().and_then(|name| {
+            ranges_by_name
+                .get(name.as_str())
+                .and_then(|ranges| {
+                    let opened = ranges.partition_point(|(start, _, _)| *start <= c.start_byte);
+                    let enclosing = ranges[..opened]
+                        .iter()
+                        .rev()
+                        .find(|(_, end, _)| *end >= c.end_byte);
+                    let next = ranges.partition_point(|(start, _, _)| *start < c.end_byte);
+                    enclosing.or_else(|| ranges.get(next)).map(|(_, _, id)| *id)
+                })
+                .or_else(|| name_to_id.get(name.as_str()).copied())
+        });
         let is_doc = is_doc_comment(&c.kind, &c.text);
-        let todo_kind = detect_todo_kind(&c.text);
+        let todo = detect_todo(&c.text);
+        let clean_text = c.clean_text();
         stream_writer.push_comment(
             &id,
             documents_id,
@@ -817,9 +1207,13 @@ fn absorb_file_data(
             &c.kind,
             is_doc,
             &c.text,
-            todo_kind,
+            (!clean_text.is_empty()).then_some(clean_text.as_str()),
+            todo.as_ref().map(|(kind, _)| *kind),
+            todo.as_ref().and_then(|(_, text)| text.as_deref()),
             c.start_byte as i64,
             c.end_byte as i64,
+            c.start_line as i64,
+            c.end_line as i64,
         );
     }
 
@@ -829,13 +1223,17 @@ fn absorb_file_data(
     // populate phase to walk later (issue 08 incremental-refresh path
     // reads from Cozo).
     let lang_str = language.as_str();
-    for (idx, import) in imports.iter().enumerate() {
+    for import in &imports {
         stream_writer.push_raw_import(
             &path,
-            idx as i64,
+            import.order as i64,
             &import.module_specifier,
+            &import.imported_name,
+            &import.local_name,
             lang_str,
             &import.kind,
+            import.is_external,
+            import.external_kind.as_deref(),
         );
     }
     if RESOLVE_IMPORTS_EAGERLY {
@@ -1008,6 +1406,7 @@ fn absorb_file_data(
             r.is_readonly,
             r.is_optional,
             &r.type_parameters,
+            &r.decorators,
         );
     }
     for r in &attrs.cpp {
@@ -1051,6 +1450,19 @@ fn absorb_file_data(
             r.is_synchronized,
             &r.throws_clause,
         );
+        for (position, (name, arguments)) in r
+            .annotations
+            .iter()
+            .zip(&r.annotation_arguments)
+            .enumerate()
+        {
+            stream_writer.push_annotation(
+                &r.symbol_id,
+                position as i64,
+                name,
+                arguments.as_deref(),
+            );
+        }
     }
     // Issue #16: stream occurrence/scope/binding facts directly. Each
     // row is self-contained (ids are file-local) and the resolver reads
@@ -1194,9 +1606,47 @@ pub fn find_node_at_line(
     None
 }
 
+/// Lines of `source[start..end]` holding at least one non-whitespace
+/// byte outside every comment range: a symbol's size without blank
+/// lines, comment-only lines and doc blocks. `comment_ranges` must be in
+/// source order, as the comment query yields them.
+fn code_line_count(source: &[u8], start: u32, end: u32, comment_ranges: &[(u32, u32)]) -> u32 {
+    let end = (end as usize).min(source.len());
+    let mut comments = comment_ranges
+        .iter()
+        .filter(|&&(c_start, c_end)| c_end as usize > start as usize && (c_start as usize) < end)
+        .peekable();
+    let mut count = 0;
+    let mut line_has_code = false;
+    for offset in start as usize..end {
+        while comments
+            .next_if(|&&(_, c_end)| c_end as usize <= offset)
+            .is_some()
+        {}
+        let byte = source[offset];
+        if byte == b'\n' {
+            count += u32::from(line_has_code);
+            line_has_code = false;
+            continue;
+        }
+        let in_comment = comments
+            .peek()
+            .is_some_and(|&&(c_start, _)| c_start as usize <= offset);
+        if !in_comment && !byte.is_ascii_whitespace() {
+            line_has_code = true;
+        }
+    }
+    count + u32::from(line_has_code)
+}
+
 fn call_expression_types(language: Language) -> Vec<&'static str> {
     match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => {
             vec!["call_expression", "new_expression"]
         }
         Language::Rust => vec!["call_expression", "method_call_expression"],
@@ -1216,6 +1666,10 @@ fn call_expression_types(language: Language) -> Vec<&'static str> {
             "scoped_call_expression",
             "nullsafe_member_call_expression",
         ],
+        // Every simple command is a potential function call; builtins
+        // and external programs just never resolve to a symbol.
+        Language::Bash => vec!["command"],
+        Language::Yaml | Language::Json | Language::Toml => Vec::new(),
     }
 }
 
@@ -1418,6 +1872,48 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn file_dependencies_template_follows_imports_to_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.ts"), "import { b } from './b';\n").unwrap();
+        std::fs::write(
+            dir.path().join("b.ts"),
+            "import { c } from './c';\nexport const b = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("c.ts"),
+            "import { b } from './a';\nexport const c = 1;\n",
+        )
+        .unwrap();
+        let store = build_into_store(dir.path(), &[Language::TypeScript]);
+        let sql = crate::queries::templates::load_sql_template("file_dependencies").unwrap();
+        let run = |depth: i64| {
+            let params: BTreeMap<String, Value> = [
+                ("file".to_string(), Value::Text("a.ts".to_string())),
+                ("depth".to_string(), Value::BigInt(depth)),
+            ]
+            .into_iter()
+            .collect();
+            store.run_query(sql, params).unwrap().rows
+        };
+
+        assert_eq!(
+            run(1),
+            vec![vec![Value::Text("b.ts".into()), Value::BigInt(1)]]
+        );
+        let two = vec![
+            vec![Value::Text("b.ts".into()), Value::BigInt(1)],
+            vec![Value::Text("c.ts".into()), Value::BigInt(2)],
+        ];
+        assert_eq!(run(2), two);
+        // c.ts imports a.ts back; the cycle must not loop or re-add the root.
+        assert_eq!(run(10), two);
+        // An oversized depth stops at the 64-hop cap instead of walking the
+        // cycle forever.
+        assert_eq!(run(1_000_000), two);
+    }
+
     #[test]
     fn test_call_resolution_scoped_to_imports_same_name_different_files() {
         // Two files each define `init`; a third file calls `init` and
@@ -1540,4 +2036,631 @@ mod tests {
             vec!["internal/util/helper.go".to_string()]
         );
     }
+
+    #[test]
+    fn symbol_kinds_filter_keeps_only_listed_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "function helper() {}\nclass Widget {}\n",
+        )
+        .unwrap();
+        let langs = [Language::TypeScript];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let kinds = crate::models::parse_symbol_kinds("class").unwrap();
+        let graph = GraphBuilder::new(&ws, &langs)
+            .with_symbol_kinds(kinds)
+            .build(&store)
+            .unwrap();
+        fcg::populate(&store, &graph, Some(&ws)).unwrap();
+
+        let rows = store
+            .run_query("SELECT name, kind FROM symbol", BTreeMap::new())
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![vec![
+                Value::Text("Widget".to_string()),
+                Value::Text("class".to_string())
+            ]]
+        );
+    }
+
+    #[test]
+    fn symbol_name_regex_keeps_only_matching_names() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("app.ts"),
+            "function handle_click() {}\nfunction render() {}\n",
+        )
+        .unwrap();
+        let langs = [Language::TypeScript];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &langs)
+            .with_symbol_name_regex(regex::Regex::new("^handle_").unwrap())
+            .build(&store)
+            .unwrap();
+        fcg::populate(&store, &graph, Some(&ws)).unwrap();
+
+        let rows = store
+            .run_query("SELECT name FROM symbol", BTreeMap::new())
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![vec![Value::Text("handle_click".to_string())]]
+        );
+    }
+
+    #[test]
+    fn utf16_le_source_with_bom_is_parsed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(
+            "def greet():\n    pass\n"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
+        std::fs::write(dir.path().join("a.py"), bytes).unwrap();
+        let store = build_into_store(dir.path(), &[Language::Python]);
+
+        let rows = store
+            .run_query(
+                "SELECT s.name, sp.start_line, f.encoding FROM symbol s \
+                 JOIN span sp ON sp.entity_id = s.id \
+                 JOIN file f ON f.path = s.file_path \
+                 WHERE s.kind = 'function'",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(rows.rows.len(), 1);
+        assert_eq!(rows.rows[0][0], Value::Text("greet".to_string()));
+        assert_eq!(rows.rows[0][1], Value::BigInt(1));
+        assert_eq!(rows.rows[0][2], Value::Text("utf-16le".to_string()));
+    }
+
+    #[test]
+    fn template_output_is_identical_across_cold_builds() {
+        // Rows land in DuckDB in rayon completion order, which differs
+        // between builds. Template output must not depend on it.
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..32 {
+            std::fs::write(
+                dir.path().join(format!("m{i}.rs")),
+                "pub fn init() {}\npub fn init_more() { init(); }\n",
+            )
+            .unwrap();
+        }
+        let sql = crate::queries::templates::load_sql_template("find_function_by_name").unwrap();
+        let params: BTreeMap<String, Value> =
+            [("name".to_string(), Value::Text("init".to_string()))]
+                .into_iter()
+                .collect();
+        let run = || {
+            let store = build_into_store(dir.path(), &[Language::Rust]);
+            store.run_query(sql, params.clone()).unwrap().rows
+        };
+        let first = run();
+        assert_eq!(first.len(), 32);
+        assert_eq!(first, run());
+    }
+
+    #[test]
+    fn syntax_errors_are_recorded_but_file_is_still_absorbed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ok.rs"), "fn fine() {}\n").unwrap();
+        std::fs::write(dir.path().join("broken.rs"), "fn kept() {}\nfn broken( {\n").unwrap();
+        let ws = Workspace::load(dir.path(), &[Language::Rust], None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &[Language::Rust])
+            .build(&store)
+            .unwrap();
+
+        assert_eq!(graph.parse_errors.len(), 1);
+        assert_eq!(graph.parse_errors[0].path, "broken.rs");
+        assert_eq!(graph.parse_errors[0].error_type, "syntax_error");
+
+        let persisted = crate::graph::parse_errors::load(&store).unwrap();
+        assert_eq!(persisted, graph.parse_errors);
+        let rows = store
+            .run_query(
+                "SELECT name FROM symbol WHERE file_path = 'broken.rs' AND name = 'kept'",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(rows.rows.len(), 1);
+    }
+
+    #[test]
+    fn internal_prefixes_reclassify_aliased_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("app.ts"),
+            "import { debounce } from '@app/utils';\nimport React from 'react';\n",
+        )
+        .unwrap();
+        let langs = [Language::TypeScript];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs)
+            .with_internal_prefixes(vec!["@app/*".to_string()])
+            .build(&store)
+            .unwrap();
+        let rows = store
+            .run_query(
+                "SELECT raw_path, is_external FROM raw_import ORDER BY position",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![
+                vec![Value::Text("@app/utils".to_string()), Value::Boolean(false)],
+                vec![Value::Text("react".to_string()), Value::Boolean(true)],
+            ]
+        );
+    }
+
+    #[test]
+    fn query_dir_overrides_the_builtin_symbol_query() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "struct Kept;\nfn dropped() {}\n").unwrap();
+        let queries = tempfile::tempdir().unwrap();
+        std::fs::write(
+            queries.path().join("rust.symbols.scm"),
+            "(struct_item name: (type_identifier) @name) @definition\n",
+        )
+        .unwrap();
+        let ws = Workspace::load(dir.path(), &[Language::Rust], None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &[Language::Rust])
+            .with_query_dir(queries.path().to_path_buf())
+            .build(&store)
+            .unwrap();
+        let rows = store
+            .run_query(
+                "SELECT name FROM symbol WHERE kind IN ('struct', 'function') ORDER BY name",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(rows.rows.len(), 1);
+        assert_eq!(rows.rows[0][0], Value::Text("Kept".to_string()));
+
+        std::fs::write(
+            queries.path().join("rust.symbols.scm"),
+            "(struct_item @name",
+        )
+        .unwrap();
+        let err = GraphBuilder::new(&ws, &[Language::Rust])
+            .with_query_dir(queries.path().to_path_buf())
+            .build(&DbStore::open_in_memory().unwrap())
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("does not compile"), "{err:#}");
+    }
+
+    #[test]
+    fn package_and_file_namespace_qualify_symbol_names() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Foo.java"),
+            "package com.example;\n\npublic class Foo {\n    void bar() {}\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Svc.cs"),
+            "namespace App.Services;\n\npublic class Svc {\n    public void Run() {}\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("User.php"),
+            "<?php\nnamespace App\\Models;\n\nclass User {\n    function save() {}\n}\n",
+        )
+        .unwrap();
+        let langs = [Language::Java, Language::CSharp, Language::Php];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        let rows = store
+            .run_query(
+                "SELECT qualified_name FROM symbol \
+                 WHERE kind IN ('class', 'method') ORDER BY qualified_name",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        let names: Vec<Value> = rows.rows.into_iter().map(|r| r[0].clone()).collect();
+        assert_eq!(
+            names,
+            [
+                "App.Services.Svc",
+                "App.Services.Svc.Run",
+                "App\\Models\\User",
+                "App\\Models\\User::save",
+                "com.example.Foo",
+                "com.example.Foo.bar",
+            ]
+            .map(|n| Value::Text(n.to_string()))
+        );
+    }
+
+    #[test]
+    fn nested_types_own_their_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Outer.java"),
+            "class Outer {\n    void outerMethod() {}\n\n    static class Inner {\n        void innerMethod() {}\n    }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Shell.cs"),
+            "class Shell {\n    void ShellMethod() {}\n    class Core {\n        void CoreMethod() {}\n    }\n}\n",
+        )
+        .unwrap();
+        let langs = [Language::Java, Language::CSharp];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        let rows = store
+            .run_query(
+                "SELECT s.name, p.name, s.qualified_name FROM symbol s \
+                 LEFT JOIN symbol p ON p.id = s.parent_id \
+                 WHERE s.kind IN ('class', 'method') ORDER BY s.qualified_name",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        let parent_of = |name: &str| {
+            let row = rows
+                .rows
+                .iter()
+                .find(|r| r[0] == Value::Text(name.to_string()))
+                .unwrap_or_else(|| panic!("{name} missing"));
+            (row[1].clone(), row[2].clone())
+        };
+        let text = |s: &str| Value::Text(s.to_string());
+        assert_eq!(parent_of("Outer"), (Value::Null, text("Outer")));
+        assert_eq!(
+            parent_of("outerMethod"),
+            (text("Outer"), text("Outer.outerMethod"))
+        );
+        assert_eq!(parent_of("Inner"), (text("Outer"), text("Outer.Inner")));
+        assert_eq!(
+            parent_of("innerMethod"),
+            (text("Inner"), text("Outer.Inner.innerMethod"))
+        );
+        assert_eq!(parent_of("Core"), (text("Shell"), text("Shell.Core")));
+        assert_eq!(
+            parent_of("CoreMethod"),
+            (text("Core"), text("Shell.Core.CoreMethod"))
+        );
+    }
+
+    #[test]
+    fn files_over_max_size_are_skipped_and_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.js"), "function small() {}\n").unwrap();
+        let bundle = format!("function big() {{}}\n{}", "var x=1;".repeat(512));
+        std::fs::write(dir.path().join("bundle.js"), &bundle).unwrap();
+        let langs = [Language::JavaScript];
+        let ws = Workspace::load(dir.path(), &langs, Some(1024)).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+
+        assert_eq!(graph.parse_errors.len(), 1);
+        assert_eq!(graph.parse_errors[0].path, "bundle.js");
+        assert_eq!(graph.parse_errors[0].error_type, "skipped_too_large");
+        assert_eq!(graph.parse_errors[0].size_bytes, bundle.len() as u64);
+        assert_eq!(graph.parse_errors[0].detail.as_deref(), Some("2 lines"));
+
+        let rows = store
+            .run_query("SELECT name FROM symbol ORDER BY name", BTreeMap::new())
+            .unwrap();
+        assert_eq!(rows.rows, vec![vec![Value::Text("small".to_string())]]);
+    }
+
+    #[test]
+    fn streamed_line_count_matches_str_lines() {
+        for content in ["", "one", "one\n", "one\ntwo\r\nthree", "\n\nfour\n\n"] {
+            // A tiny buffer forces the count across many chunks.
+            let reader = std::io::BufReader::with_capacity(2, content.as_bytes());
+            assert_eq!(
+                count_lines(reader).unwrap(),
+                content.lines().count() as u64,
+                "{content:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_utf8_files_are_recorded_as_encoding_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ok.py"), "def ok():\n    pass\n").unwrap();
+        std::fs::write(
+            dir.path().join("legacy.py"),
+            b"# caf\xE9\ndef legacy():\n    pass\n",
+        )
+        .unwrap();
+        let langs = [Language::Python];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+
+        assert_eq!(graph.parse_errors.len(), 1);
+        assert_eq!(graph.parse_errors[0].path, "legacy.py");
+        assert_eq!(graph.parse_errors[0].error_type, "encoding");
+        let rows = store
+            .run_query(
+                "SELECT error_type, detail FROM parse_error",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![vec![
+                Value::Text("encoding".to_string()),
+                Value::Text("invalid UTF-8 at byte 5; decoded as Latin-1".to_string()),
+            ]]
+        );
+        // Still absorbed, and not a failure for --fail-on-error.
+        let summary =
+            crate::graph::parse_errors::ParseErrorSummary::from_errors(&graph.parse_errors);
+        assert_eq!(summary.failures(), 0);
+        assert!(summary.check(true).is_ok());
+    }
+
+    #[test]
+    fn symbols_in_test_files_are_flagged_is_test() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("foo.ts"), "export function prod() {}\n").unwrap();
+        std::fs::write(
+            dir.path().join("foo.test.ts"),
+            "export function checkProd() {}\n",
+        )
+        .unwrap();
+        let langs = [Language::TypeScript];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        let rows = store
+            .run_query(
+                "SELECT name, is_test FROM symbol ORDER BY name",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![
+                vec![Value::Text("checkProd".to_string()), Value::Boolean(true)],
+                vec![Value::Text("prod".to_string()), Value::Boolean(false)],
+            ]
+        );
+    }
+
+    #[test]
+    fn stub_and_module_variant_files_are_indexed() {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, source) in [
+            (
+                "api.pyi",
+                "class Client:\n    def get(self, url: str) -> bytes: ...\n",
+            ),
+            ("a.mts", "export function esm(): void {}\n"),
+            ("b.cts", "export function cjs(): void {}\n"),
+            ("c.mjs", "export function esmJs() {}\n"),
+            ("d.cjs", "function cjsJs() {}\n"),
+        ] {
+            std::fs::write(dir.path().join(file), source).unwrap();
+        }
+        let langs = [Language::Python, Language::TypeScript, Language::JavaScript];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        let rows = store
+            .run_query(
+                "SELECT file_path, name, language FROM symbol \
+                 WHERE kind <> 'parameter' ORDER BY file_path, name",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        let text = |s: &str| Value::Text(s.to_string());
+        assert_eq!(
+            rows.rows,
+            vec![
+                vec![text("a.mts"), text("esm"), text("typescript")],
+                vec![text("api.pyi"), text("Client"), text("python")],
+                vec![text("api.pyi"), text("get"), text("python")],
+                vec![text("b.cts"), text("cjs"), text("typescript")],
+                vec![text("c.mjs"), text("esmJs"), text("javascript")],
+                vec![text("d.cjs"), text("cjsJs"), text("javascript")],
+            ]
+        );
+    }
+
+    #[test]
+    fn rust_cfg_test_module_symbols_are_flagged_is_test() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "pub fn prod() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn checks_prod() {}\n}\n",
+        )
+        .unwrap();
+        let langs = [Language::Rust];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        let rows = store
+            .run_query(
+                "SELECT name, is_test FROM symbol WHERE kind = 'function' ORDER BY name",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![
+                vec![Value::Text("checks_prod".to_string()), Value::Boolean(true)],
+                vec![Value::Text("prod".to_string()), Value::Boolean(false)],
+            ]
+        );
+    }
+
+    #[test]
+    fn fields_record_their_parent_type() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("point.rs"),
+            "pub struct Point {\n    pub x: i32,\n    y: i32,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("user.ts"),
+            "interface User {\n  id: number;\n  name: string;\n}\n",
+        )
+        .unwrap();
+        let langs = [Language::Rust, Language::TypeScript];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        let rows = store
+            .run_query(
+                "SELECT s.name, p.name FROM symbol s \
+                 JOIN symbol p ON p.id = s.parent_id \
+                 WHERE s.kind = 'field' ORDER BY s.name",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        let text = |s: &str| Value::Text(s.to_string());
+        assert_eq!(
+            rows.rows,
+            vec![
+                vec![text("id"), text("User")],
+                vec![text("name"), text("User")],
+                vec![text("x"), text("Point")],
+                vec![text("y"), text("Point")],
+            ]
+        );
+    }
+
+    #[test]
+    fn populate_records_build_provenance() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "pub fn hello() {}\n").unwrap();
+        std::fs::write(dir.path().join("tool.py"), "def run():\n    pass\n").unwrap();
+        let store = build_into_store(dir.path(), &[Language::Rust, Language::Python]);
+
+        let meta = store.build_meta().unwrap();
+        assert_eq!(meta["virgil_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            meta["root"],
+            dir.path().canonicalize().unwrap().to_string_lossy()
+        );
+        assert_eq!(meta["languages"], "python,rust");
+        assert_eq!(meta["file_count"], "2");
+        assert!(chrono::DateTime::parse_from_rfc3339(&meta["built_at"]).is_ok());
+    }
+
+    #[test]
+    fn symbol_ids_are_stable_and_docs_point_at_their_own_symbol() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "pub struct A;\npub struct B;\nimpl A {\n    /// Makes an A.\n    pub fn new() -> A { A }\n}\nimpl B {\n    /// Makes a B.\n    pub fn new() -> B { B }\n}\n",
+        )
+        .unwrap();
+        let langs = [Language::Rust];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let docs = || {
+            let store = DbStore::open_in_memory().unwrap();
+            GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+            store
+                .run_query(
+                    "SELECT c.clean_text, s.id, sp.start_line FROM comment c \
+                     JOIN symbol s ON s.id = c.documents_id \
+                     JOIN span sp ON sp.entity_id = s.id \
+                     ORDER BY sp.start_line",
+                    BTreeMap::new(),
+                )
+                .unwrap()
+                .rows
+        };
+        let first = docs();
+        assert_eq!(first, docs(), "ids differ between two builds");
+        let summary: Vec<(Value, Value)> = first
+            .iter()
+            .map(|row| (row[0].clone(), row[2].clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Value::Text("Makes an A.".to_string()), Value::BigInt(5)),
+                (Value::Text("Makes a B.".to_string()), Value::BigInt(9)),
+            ]
+        );
+    }
+
+    #[test]
+    fn docstrings_point_at_their_enclosing_same_named_symbol() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("models.py"),
+            "class A:\n    def __init__(self):\n        \"\"\"Makes an A.\"\"\"\n\n\
+             class B:\n    def __init__(self):\n        \"\"\"Makes a B.\"\"\"\n",
+        )
+        .unwrap();
+        let store = build_into_store(dir.path(), &[Language::Python]);
+        let rows = store
+            .run_query(
+                "SELECT c.start_line, sp.start_line FROM comment c \
+                 JOIN symbol s ON s.id = c.documents_id \
+                 JOIN span sp ON sp.entity_id = s.id \
+                 WHERE s.name = '__init__' ORDER BY c.start_line",
+                BTreeMap::new(),
+            )
+            .unwrap()
+            .rows;
+        assert_eq!(
+            rows,
+            vec![
+                vec![Value::BigInt(3), Value::BigInt(2)],
+                vec![Value::BigInt(7), Value::BigInt(6)],
+            ]
+        );
+    }
+
+    #[test]
+    fn vue_script_block_is_parsed_with_file_line_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Greeting.vue"),
+            "<template>\n  <p>{{ greet('you') }}</p>\n</template>\n\n<script lang=\"ts\">\nimport { ref } from 'vue';\n\nexport function greet(name: string): string {\n  return `hi ${name}`;\n}\n</script>\n\n<style>\np { color: red; }\n</style>\n",
+        )
+        .unwrap();
+        let store = build_into_store(dir.path(), &[Language::Vue]);
+        let rows = |sql: &str| store.run_query(sql, BTreeMap::new()).unwrap().rows;
+
+        assert_eq!(
+            rows("SELECT path, language FROM file"),
+            vec![vec![
+                Value::Text("Greeting.vue".to_string()),
+                Value::Text("vue".to_string()),
+            ]]
+        );
+        assert_eq!(
+            rows(
+                "SELECT s.name, s.is_exported, sp.start_line FROM symbol s \
+                 JOIN span sp ON sp.entity_id = s.id WHERE s.kind = 'function'"
+            ),
+            vec![vec![
+                Value::Text("greet".to_string()),
+                Value::Boolean(true),
+                Value::BigInt(8),
+            ]]
+        );
+        assert_eq!(
+            rows("SELECT raw_path, language FROM raw_import"),
+            vec![vec![
+                Value::Text("vue".to_string()),
+                Value::Text("vue".to_string()),
+            ]]
+        );
+        assert!(rows("SELECT * FROM parse_error").is_empty());
+    }
 }
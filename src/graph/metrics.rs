@@ -233,7 +233,7 @@ fn walk_all<F: FnMut(Node)>(node: Node, cursor: &mut tree_sitter::TreeCursor, f:
 /// Return the `ControlFlowConfig` for a given language.
 pub fn control_flow_config_for_language(lang: Language) -> ControlFlowConfig {
     match lang {
-        Language::TypeScript | Language::Tsx => ts_config(),
+        Language::TypeScript | Language::Tsx | Language::Vue | Language::Svelte => ts_config(),
         Language::JavaScript | Language::Jsx => js_config(),
         Language::Rust => rust_config(),
         Language::Python => python_config(),
@@ -243,6 +243,8 @@ pub fn control_flow_config_for_language(lang: Language) -> ControlFlowConfig {
         Language::Cpp => cpp_config(),
         Language::CSharp => csharp_config(),
         Language::Php => php_config(),
+        Language::Bash => bash_config(),
+        Language::Yaml | Language::Json | Language::Toml => data_config(),
     }
 }
 
@@ -518,13 +520,53 @@ fn php_config() -> ControlFlowConfig {
     }
 }
 
+fn bash_config() -> ControlFlowConfig {
+    ControlFlowConfig {
+        decision_point_kinds: &[
+            "if_statement",
+            "elif_clause",
+            "for_statement",
+            "c_style_for_statement",
+            "while_statement",
+            "case_item",
+        ],
+        nesting_increments: &[
+            "if_statement",
+            "for_statement",
+            "c_style_for_statement",
+            "while_statement",
+            "case_statement",
+        ],
+        flat_increments: &["elif_clause", "else_clause"],
+        // Only `[[ a && b ]]` test expressions carry an `operator` field;
+        // `cmd1 && cmd2` command lists don't and are not counted.
+        logical_operators: &["&&", "||", "-a", "-o"],
+        binary_expression_kind: "binary_expression",
+        ternary_kind: Some("ternary_expression"),
+        comment_kinds: &["comment"],
+    }
+}
+
+/// Config formats have no control flow; every metric stays at its base.
+fn data_config() -> ControlFlowConfig {
+    ControlFlowConfig {
+        decision_point_kinds: &[],
+        nesting_increments: &[],
+        flat_increments: &[],
+        logical_operators: &[],
+        binary_expression_kind: "",
+        ternary_kind: None,
+        comment_kinds: &["comment"],
+    }
+}
+
 // ── Function body locating helpers ──────────────────────────────────────────
 
 /// Per-language function node kinds for finding function bodies by line number.
 pub fn function_node_kinds_for_language(lang: Language) -> &'static [&'static str] {
     match lang {
         Language::Rust => &["function_item"],
-        Language::TypeScript | Language::Tsx => &[
+        Language::TypeScript | Language::Tsx | Language::Vue | Language::Svelte => &[
             "function_declaration",
             "method_definition",
             "arrow_function",
@@ -544,6 +586,8 @@ pub fn function_node_kinds_for_language(lang: Language) -> &'static [&'static st
         Language::Cpp => &["function_definition"],
         Language::CSharp => &["method_declaration", "constructor_declaration"],
         Language::Php => &["function_definition", "method_declaration"],
+        Language::Bash => &["function_definition"],
+        Language::Yaml | Language::Json | Language::Toml => &[],
     }
 }
 
@@ -1,6 +1,8 @@
+pub mod batch;
 pub mod builder;
 pub mod intern;
 pub mod metrics;
+pub mod parse_errors;
 
 pub use intern::{Spur, Symbols};
 
@@ -17,9 +19,11 @@ pub enum GraphNode {
 /// `absorb_file_data` and inheritance is staged in the `raw_inheritance`
 /// table for a post-parse SQL resolver. Only the interner survives
 /// here — used by the deferred-imports/calls resolution loop and the
-/// per-file lookup maps inside the builder.
+/// per-file lookup maps inside the builder, alongside the files that
+/// failed to parse cleanly.
 pub struct CodeGraph {
     pub symbols: Symbols,
+    pub parse_errors: Vec<parse_errors::ParseError>,
 }
 
 impl Default for CodeGraph {
@@ -34,7 +38,10 @@ impl CodeGraph {
     }
 
     pub fn with_symbols(symbols: Symbols) -> Self {
-        Self { symbols }
+        Self {
+            symbols,
+            parse_errors: Vec::new(),
+        }
     }
 
     /// Reduce step in the parallel builder. The interner is shared via
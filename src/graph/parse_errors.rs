@@ -0,0 +1,305 @@
+//! Per-file parse failures recorded during a cold build.
+//!
+//! The builder never aborts on a bad file: unreadable sources and files
+//! tree-sitter cannot parse are skipped, and files whose tree contains
+//! `ERROR`/`MISSING` nodes are still absorbed. Files over the size cap
+//! are skipped before reading. Each case is recorded as a
+//! [`ParseError`], persisted to the `parse_error` table so a warm store
+//! can report the same summary, and rolled up by [`ParseErrorSummary`].
+//! [`new_failures`] compares them against a baseline so CI can fail on
+//! new errors only.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::db::DbStore;
+use crate::queries::runner::{value_to_i64, value_to_string};
+
+/// How many of the largest failing files the summary names.
+const TOP_FAILING_FILES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The file could not be read or decoded.
+    Read,
+    /// No parser or extraction query is available for the language.
+    ParserUnavailable,
+    /// tree-sitter returned no tree at all.
+    ParseFailed,
+    /// The tree contains `ERROR`/`MISSING` nodes. The file is still
+    /// absorbed; symbols outside the broken region are kept.
+    Syntax,
+    /// Larger than `--max-file-size`; never read. Not a failure, so
+    /// `--fail-on-error` ignores it.
+    SkippedTooLarge,
+    /// Not valid UTF-8, so decoded as Latin-1 and still absorbed; the
+    /// detail names the byte offset of the first invalid sequence. Not a
+    /// failure either, and a file with syntax errors is recorded as
+    /// [`ParseErrorKind::Syntax`] instead.
+    Encoding,
+}
+
+impl ParseErrorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParseErrorKind::Read => "read_error",
+            ParseErrorKind::ParserUnavailable => "parser_unavailable",
+            ParseErrorKind::ParseFailed => "parse_failed",
+            ParseErrorKind::Syntax => "syntax_error",
+            ParseErrorKind::SkippedTooLarge => "skipped_too_large",
+            ParseErrorKind::Encoding => "encoding",
+        }
+    }
+}
+
+/// Whether an `error_type` is a real failure rather than a file skipped
+/// for size or decoded as Latin-1.
+pub fn is_failure(error_type: &str) -> bool {
+    ![ParseErrorKind::SkippedTooLarge, ParseErrorKind::Encoding]
+        .iter()
+        .any(|kind| kind.as_str() == error_type)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParseError {
+    pub path: String,
+    pub language: String,
+    pub error_type: String,
+    pub size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ParseErrorSummary {
+    pub total: usize,
+    pub by_type: BTreeMap<String, usize>,
+    pub by_language: BTreeMap<String, usize>,
+    /// Largest failing files first, at most [`TOP_FAILING_FILES`].
+    pub largest: Vec<ParseError>,
+}
+
+impl ParseErrorSummary {
+    pub fn from_errors(errors: &[ParseError]) -> Self {
+        let mut summary = ParseErrorSummary {
+            total: errors.len(),
+            ..Default::default()
+        };
+        for err in errors {
+            *summary.by_type.entry(err.error_type.clone()).or_default() += 1;
+            *summary.by_language.entry(err.language.clone()).or_default() += 1;
+        }
+        let mut largest = errors.to_vec();
+        largest.sort_by(|a, b| {
+            b.size_bytes
+                .cmp(&a.size_bytes)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        largest.truncate(TOP_FAILING_FILES);
+        summary.largest = largest;
+        summary
+    }
+
+    /// Emit the breakdown on stderr through the tracing subscriber.
+    /// Silent when nothing failed.
+    pub fn log(&self) {
+        if self.total == 0 {
+            return;
+        }
+        warn!(total = self.total, "files had parse errors");
+        for (error_type, count) in &self.by_type {
+            warn!(error_type = %error_type, count, "parse errors by type");
+        }
+        for (language, count) in &self.by_language {
+            warn!(language = %language, count, "parse errors by language");
+        }
+        for err in &self.largest {
+            warn!(
+                path = %err.path,
+                error_type = %err.error_type,
+                size_bytes = err.size_bytes,
+                "largest failing file"
+            );
+        }
+    }
+
+    /// Recorded entries that are real failures, i.e. everything except
+    /// files deliberately skipped for size and files decoded as Latin-1.
+    pub fn failures(&self) -> usize {
+        self.by_type
+            .iter()
+            .filter(|(error_type, _)| is_failure(error_type))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// `--fail-on-error`: turn any recorded failure into a command error.
+    pub fn check(&self, fail_on_error: bool) -> Result<()> {
+        let failures = self.failures();
+        if fail_on_error && failures > 0 {
+            anyhow::bail!(
+                "{failures} file(s) had parse errors; rerun without --fail-on-error to ignore them"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Read the errors a previous cold build persisted into `store`.
+pub fn load(store: &DbStore) -> Result<Vec<ParseError>> {
+    let rows = store
+        .run_query(
+            "SELECT file_path, language, error_type, size_bytes, detail FROM parse_error \
+             ORDER BY file_path",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query parse errors: {e}"))?;
+    Ok(rows
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(ParseError {
+                path: value_to_string(&row[0])?,
+                language: value_to_string(&row[1])?,
+                error_type: value_to_string(&row[2])?,
+                size_bytes: value_to_i64(&row[3]).unwrap_or(0).max(0) as u64,
+                detail: value_to_string(&row[4]),
+            })
+        })
+        .collect())
+}
+
+/// `(file_path, error_type)` pairs of a baseline: the
+/// `parse_error.ndjson` that `projects export --artifact-format ndjson`
+/// writes, one JSON object per line. Other fields are ignored.
+pub fn load_baseline(path: &Path) -> Result<HashSet<(String, String)>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read baseline {}", path.display()))?;
+    let mut baseline = HashSet::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| format!("{}:{}: not a JSON object", path.display(), i + 1))?;
+        let field = |name: &str| {
+            row.get(name)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("{}:{}: missing \"{name}\"", path.display(), i + 1))
+        };
+        baseline.insert((field("file_path")?, field("error_type")?));
+    }
+    Ok(baseline)
+}
+
+/// Failures in `errors` whose `(path, error_type)` is not in `baseline`.
+/// A file that moves from one error type to another counts as new.
+pub fn new_failures(
+    errors: &[ParseError],
+    baseline: &HashSet<(String, String)>,
+) -> Vec<ParseError> {
+    errors
+        .iter()
+        .filter(|err| is_failure(&err.error_type))
+        .filter(|err| !baseline.contains(&(err.path.clone(), err.error_type.clone())))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn err(path: &str, language: &str, kind: ParseErrorKind, size_bytes: u64) -> ParseError {
+        ParseError {
+            path: path.to_string(),
+            language: language.to_string(),
+            error_type: kind.as_str().to_string(),
+            size_bytes,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn summary_groups_by_type_and_language() {
+        let errors = vec![
+            err("a.rs", "rust", ParseErrorKind::Syntax, 10),
+            err("b.rs", "rust", ParseErrorKind::Syntax, 400),
+            err("c.py", "python", ParseErrorKind::Read, 50),
+            err("d.ts", "typescript", ParseErrorKind::Syntax, 300),
+        ];
+        let summary = ParseErrorSummary::from_errors(&errors);
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.by_type["syntax_error"], 3);
+        assert_eq!(summary.by_type["read_error"], 1);
+        assert_eq!(summary.by_language["rust"], 2);
+        let largest: Vec<&str> = summary.largest.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(largest, vec!["b.rs", "d.ts", "c.py"]);
+    }
+
+    #[test]
+    fn check_fails_only_when_requested_and_errors_exist() {
+        let clean = ParseErrorSummary::from_errors(&[]);
+        assert!(clean.check(true).is_ok());
+        let dirty =
+            ParseErrorSummary::from_errors(&[err("a.rs", "rust", ParseErrorKind::Syntax, 1)]);
+        assert!(dirty.check(false).is_ok());
+        assert!(dirty.check(true).is_err());
+    }
+
+    #[test]
+    fn skipped_files_do_not_trip_fail_on_error() {
+        let summary = ParseErrorSummary::from_errors(&[err(
+            "bundle.js",
+            "javascript",
+            ParseErrorKind::SkippedTooLarge,
+            5_000_000,
+        )]);
+        assert_eq!(summary.total, 1);
+        assert_eq!(summary.failures(), 0);
+        assert!(summary.check(true).is_ok());
+    }
+
+    #[test]
+    fn baseline_hides_known_errors_but_not_new_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("parse_error.ndjson");
+        std::fs::write(
+            &path,
+            "{\"file_path\":\"old.rs\",\"language\":\"rust\",\"error_type\":\"syntax_error\",\"size_bytes\":10}\n",
+        )
+        .unwrap();
+        let baseline = load_baseline(&path).unwrap();
+
+        let known = [err("old.rs", "rust", ParseErrorKind::Syntax, 12)];
+        assert!(new_failures(&known, &baseline).is_empty());
+
+        let errors = [
+            err("old.rs", "rust", ParseErrorKind::Syntax, 12),
+            err("new.rs", "rust", ParseErrorKind::Syntax, 5),
+            err(
+                "big.js",
+                "javascript",
+                ParseErrorKind::SkippedTooLarge,
+                9_000_000,
+            ),
+        ];
+        let new = new_failures(&errors, &baseline);
+        let paths: Vec<&str> = new.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["new.rs"]);
+    }
+
+    #[test]
+    fn baseline_rows_need_file_path_and_error_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("parse_error.ndjson");
+        std::fs::write(&path, "{\"file_path\":\"a.rs\"}\n").unwrap();
+        let err = load_baseline(&path).unwrap_err().to_string();
+        assert!(err.contains("error_type"), "{err}");
+    }
+}
@@ -14,6 +14,12 @@ pub enum Language {
     Go,
     Java,
     Php,
+    Bash,
+    Yaml,
+    Json,
+    Toml,
+    Vue,
+    Svelte,
 }
 
 impl Language {
@@ -35,15 +41,36 @@ impl Language {
             "go" => Some(Language::Go),
             "java" => Some(Language::Java),
             "php" => Some(Language::Php),
+            "bash" => Some(Language::Bash),
+            "yaml" => Some(Language::Yaml),
+            "json" => Some(Language::Json),
+            "toml" => Some(Language::Toml),
+            "vue" => Some(Language::Vue),
+            "svelte" => Some(Language::Svelte),
             _ => None,
         }
     }
 
+    /// Resolve a user-supplied language name: either the canonical name
+    /// (`rust`) or any of its file extensions (`rs`).
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        Self::from_str(&name)
+            .or_else(|| Self::from_extension(&name))
+            .ok_or_else(|| {
+                let known: Vec<&str> = Self::all().iter().map(|l| l.as_str()).collect();
+                anyhow::anyhow!(
+                    "unknown language '{name}' (expected one of: {})",
+                    known.join(", ")
+                )
+            })
+    }
+
     pub fn from_extension(ext: &str) -> Option<Self> {
         match ext {
-            "ts" => Some(Language::TypeScript),
+            "ts" | "mts" | "cts" => Some(Language::TypeScript),
             "tsx" => Some(Language::Tsx),
-            "js" => Some(Language::JavaScript),
+            "js" | "mjs" | "cjs" => Some(Language::JavaScript),
             "jsx" => Some(Language::Jsx),
             "c" | "h" => Some(Language::C),
             "cpp" | "cc" | "cxx" | "hpp" | "hxx" | "hh" => Some(Language::Cpp),
@@ -53,6 +80,43 @@ impl Language {
             "go" => Some(Language::Go),
             "java" => Some(Language::Java),
             "php" => Some(Language::Php),
+            "sh" | "bash" => Some(Language::Bash),
+            "yaml" | "yml" => Some(Language::Yaml),
+            "json" => Some(Language::Json),
+            "toml" => Some(Language::Toml),
+            "vue" => Some(Language::Vue),
+            "svelte" => Some(Language::Svelte),
+            _ => None,
+        }
+    }
+
+    /// Well-known extensionless filenames whose contents are written in a
+    /// supported language. Consulted only when the path has no extension.
+    pub fn from_filename(name: &str) -> Option<Self> {
+        match name {
+            "SConstruct" | "SConscript" | "wscript" => Some(Language::Python),
+            "Jakefile" => Some(Language::JavaScript),
+            _ => None,
+        }
+    }
+
+    /// Map a `#!` interpreter line to a language. Handles both direct
+    /// (`#!/usr/bin/python3`) and `env`-indirected
+    /// (`#!/usr/bin/env -S node --flag`) forms; trailing version
+    /// suffixes (`python3.11`) are ignored. POSIX `sh` and `dash` scripts
+    /// go to the Bash grammar, which parses them fine.
+    pub fn from_shebang(line: &str) -> Option<Self> {
+        let rest = line.strip_prefix("#!")?.trim();
+        let mut parts = rest.split_whitespace();
+        let mut interpreter = parts.next()?.rsplit('/').next()?;
+        if interpreter == "env" {
+            interpreter = parts.find(|p| !p.starts_with('-'))?;
+        }
+        match interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+            "python" | "pypy" => Some(Language::Python),
+            "node" | "nodejs" => Some(Language::JavaScript),
+            "php" => Some(Language::Php),
+            "sh" | "bash" | "dash" => Some(Language::Bash),
             _ => None,
         }
     }
@@ -70,6 +134,14 @@ impl Language {
             Language::Go => tree_sitter_go::LANGUAGE.into(),
             Language::Java => tree_sitter_java::LANGUAGE.into(),
             Language::Php => tree_sitter_php::LANGUAGE_PHP.into(),
+            Language::Bash => tree_sitter_bash::LANGUAGE.into(),
+            Language::Yaml => tree_sitter_yaml::LANGUAGE.into(),
+            Language::Json => tree_sitter_json::LANGUAGE.into(),
+            Language::Toml => tree_sitter_toml_ng::LANGUAGE.into(),
+            // Only the `<script>` blocks are parsed (see
+            // `parser::parse_source`); the TypeScript grammar also takes
+            // plain JavaScript scripts.
+            Language::Vue | Language::Svelte => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
         }
     }
 
@@ -87,6 +159,12 @@ impl Language {
             Language::Go => "go",
             Language::Java => "java",
             Language::Php => "php",
+            Language::Bash => "bash",
+            Language::Yaml => "yaml",
+            Language::Json => "json",
+            Language::Toml => "toml",
+            Language::Vue => "vue",
+            Language::Svelte => "svelte",
         }
     }
 
@@ -104,14 +182,20 @@ impl Language {
             Language::Go => "go",
             Language::Java => "java",
             Language::Php => "php",
+            Language::Bash => "sh",
+            Language::Yaml => "yaml",
+            Language::Json => "json",
+            Language::Toml => "toml",
+            Language::Vue => "vue",
+            Language::Svelte => "svelte",
         }
     }
 
     pub fn all_extensions(&self) -> &'static [&'static str] {
         match self {
-            Language::TypeScript => &["ts"],
+            Language::TypeScript => &["ts", "mts", "cts"],
             Language::Tsx => &["tsx"],
-            Language::JavaScript => &["js"],
+            Language::JavaScript => &["js", "mjs", "cjs"],
             Language::Jsx => &["jsx"],
             Language::C => &["c", "h"],
             Language::Cpp => &["cpp", "cc", "cxx", "hpp", "hxx", "hh"],
@@ -121,6 +205,12 @@ impl Language {
             Language::Go => &["go"],
             Language::Java => &["java"],
             Language::Php => &["php"],
+            Language::Bash => &["sh", "bash"],
+            Language::Yaml => &["yaml", "yml"],
+            Language::Json => &["json"],
+            Language::Toml => &["toml"],
+            Language::Vue => &["vue"],
+            Language::Svelte => &["svelte"],
         }
     }
 
@@ -138,8 +228,37 @@ impl Language {
             Language::Go,
             Language::Java,
             Language::Php,
+            Language::Bash,
+            Language::Yaml,
+            Language::Json,
+            Language::Toml,
+            Language::Vue,
+            Language::Svelte,
         ]
     }
+
+    /// Config-file formats. Their "symbols" are top-level keys, which
+    /// drown out code symbols in a mixed repo, so they are only indexed
+    /// when a language filter names them.
+    pub fn is_config(&self) -> bool {
+        matches!(self, Language::Yaml | Language::Json | Language::Toml)
+    }
+
+    /// Single-file components: markup with embedded `<script>` blocks,
+    /// of which only the scripts are parsed.
+    pub fn is_single_file_component(&self) -> bool {
+        matches!(self, Language::Vue | Language::Svelte)
+    }
+
+    /// Languages indexed when a project has no language filter: every
+    /// language except the config formats.
+    pub fn defaults() -> Vec<Language> {
+        Self::all()
+            .iter()
+            .copied()
+            .filter(|l| !l.is_config())
+            .collect()
+    }
 }
 
 impl fmt::Display for Language {
@@ -162,8 +281,12 @@ mod tests {
     #[test]
     fn from_extension_valid() {
         assert_eq!(Language::from_extension("ts"), Some(Language::TypeScript));
+        assert_eq!(Language::from_extension("mts"), Some(Language::TypeScript));
+        assert_eq!(Language::from_extension("cts"), Some(Language::TypeScript));
         assert_eq!(Language::from_extension("tsx"), Some(Language::Tsx));
         assert_eq!(Language::from_extension("js"), Some(Language::JavaScript));
+        assert_eq!(Language::from_extension("mjs"), Some(Language::JavaScript));
+        assert_eq!(Language::from_extension("cjs"), Some(Language::JavaScript));
         assert_eq!(Language::from_extension("jsx"), Some(Language::Jsx));
         assert_eq!(Language::from_extension("c"), Some(Language::C));
         assert_eq!(Language::from_extension("h"), Some(Language::C));
@@ -180,6 +303,19 @@ mod tests {
         assert_eq!(Language::from_extension("go"), Some(Language::Go));
         assert_eq!(Language::from_extension("java"), Some(Language::Java));
         assert_eq!(Language::from_extension("php"), Some(Language::Php));
+        assert_eq!(Language::from_extension("sh"), Some(Language::Bash));
+        assert_eq!(Language::from_extension("bash"), Some(Language::Bash));
+        assert_eq!(Language::from_extension("vue"), Some(Language::Vue));
+        assert_eq!(Language::from_extension("svelte"), Some(Language::Svelte));
+    }
+
+    #[test]
+    fn from_name_accepts_names_and_extensions() {
+        assert_eq!(Language::from_name("rust").unwrap(), Language::Rust);
+        assert_eq!(Language::from_name("RS").unwrap(), Language::Rust);
+        assert_eq!(Language::from_name("yml").unwrap(), Language::Yaml);
+        let err = Language::from_name("cobol").unwrap_err().to_string();
+        assert!(err.contains("unknown language 'cobol'"), "{err}");
     }
 
     #[test]
@@ -188,6 +324,47 @@ mod tests {
         assert_eq!(Language::from_extension(""), None);
     }
 
+    #[test]
+    fn from_filename_known_and_unknown() {
+        assert_eq!(
+            Language::from_filename("SConstruct"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            Language::from_filename("Jakefile"),
+            Some(Language::JavaScript)
+        );
+        assert_eq!(Language::from_filename("Dockerfile"), None);
+        assert_eq!(Language::from_filename("Makefile"), None);
+    }
+
+    #[test]
+    fn from_shebang_routes_interpreters() {
+        assert_eq!(
+            Language::from_shebang("#!/usr/bin/env python"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            Language::from_shebang("#!/usr/bin/python3.11 -u"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            Language::from_shebang("#!/usr/bin/env -S node --no-warnings"),
+            Some(Language::JavaScript)
+        );
+        assert_eq!(
+            Language::from_shebang("#!/usr/bin/php"),
+            Some(Language::Php)
+        );
+        assert_eq!(Language::from_shebang("#!/bin/bash"), Some(Language::Bash));
+        assert_eq!(
+            Language::from_shebang("#!/usr/bin/env sh"),
+            Some(Language::Bash)
+        );
+        assert_eq!(Language::from_shebang("#!/usr/bin/env ruby"), None);
+        assert_eq!(Language::from_shebang("print('no shebang')"), None);
+    }
+
     #[test]
     fn extension_round_trip() {
         for lang in Language::all() {
@@ -197,8 +374,20 @@ mod tests {
     }
 
     #[test]
-    fn all_returns_twelve_variants() {
-        assert_eq!(Language::all().len(), 12);
+    fn all_returns_eighteen_variants() {
+        assert_eq!(Language::all().len(), 18);
+    }
+
+    #[test]
+    fn defaults_leave_out_config_formats() {
+        let defaults = Language::defaults();
+        assert_eq!(defaults.len(), 15);
+        assert!(!defaults.contains(&Language::Yaml));
+        assert!(defaults.contains(&Language::Bash));
+        assert_eq!(
+            parse_language_filter("yml,toml,json"),
+            vec![Language::Yaml, Language::Toml, Language::Json]
+        );
     }
 
     #[test]
@@ -207,8 +396,10 @@ mod tests {
         assert_eq!(Language::C.all_extensions(), &["c", "h"]);
         // C++ should have 6 extensions
         assert_eq!(Language::Cpp.all_extensions().len(), 6);
+        // ES module / CommonJS variants
+        assert_eq!(Language::TypeScript.all_extensions(), &["ts", "mts", "cts"]);
+        assert_eq!(Language::JavaScript.all_extensions(), &["js", "mjs", "cjs"]);
         // Single-extension languages
-        assert_eq!(Language::TypeScript.all_extensions(), &["ts"]);
         assert_eq!(Language::CSharp.all_extensions(), &["cs"]);
         // New languages
         assert_eq!(Language::Rust.all_extensions(), &["rs"]);
@@ -0,0 +1,501 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Query, QueryCursor, Tree};
+
+use crate::language::Language;
+use crate::models::{CommentInfo, ImportInfo, SymbolInfo, SymbolKind, SymbolVisibility};
+
+/// The keyword of the `declaration_command` wrapping `def_node`
+/// (`export`, `local`, `declare`, `readonly`, `typeset`), if any.
+fn declaration_keyword<'a>(def_node: tree_sitter::Node<'a>) -> Option<tree_sitter::Node<'a>> {
+    let parent = def_node.parent()?;
+    if parent.kind() != "declaration_command" {
+        return None;
+    }
+    parent.child(0)
+}
+
+/// `readonly X=1` and `declare -r X=1` bind constants; every other
+/// assignment is a mutable variable.
+fn is_readonly_bash(def_node: tree_sitter::Node, source: &[u8]) -> bool {
+    let Some(keyword) = declaration_keyword(def_node) else {
+        return false;
+    };
+    if keyword.kind() == "readonly" {
+        return true;
+    }
+    let Some(decl) = def_node.parent() else {
+        return false;
+    };
+    let mut cursor = decl.walk();
+    decl.children(&mut cursor).any(|c| {
+        c.kind() == "word"
+            && c.utf8_text(source)
+                .is_ok_and(|t| t.starts_with('-') && !t.starts_with("--") && t.contains('r'))
+    })
+}
+
+// ── Symbol queries ──
+
+// Variables are only taken at the top level of the script: assignments
+// inside functions (`local x=…` or bare `x=…`) are scratch state, and
+// `FOO=1 cmd` prefixes are per-command environment, not bindings.
+const BASH_SYMBOL_QUERY: &str = r#"
+(function_definition
+  name: (word) @name) @definition
+
+(program
+  (variable_assignment
+    name: (variable_name) @name) @definition)
+
+(program
+  (variable_assignments
+    (variable_assignment
+      name: (variable_name) @name) @definition))
+
+(program
+  (declaration_command
+    (variable_assignment
+      name: (variable_name) @name) @definition))
+"#;
+
+// ── Import queries ──
+
+// `source` / `.` are ordinary commands in the grammar; the command name
+// is filtered in `extract_imports`.
+const BASH_IMPORT_QUERY: &str = r#"
+(command
+  name: (command_name) @command) @import
+"#;
+
+// ── Comment queries ──
+
+const BASH_COMMENT_QUERY: &str = r#"
+(comment) @comment
+"#;
+
+// ── Query compilation ──
+
+pub fn compile_symbol_query(language: Language) -> Result<Arc<Query>> {
+    let ts_lang = language.tree_sitter_language();
+    let query = Query::new(&ts_lang, BASH_SYMBOL_QUERY)
+        .with_context(|| format!("failed to compile symbol query for {language}"))?;
+    Ok(Arc::new(query))
+}
+
+pub fn compile_import_query(language: Language) -> Result<Arc<Query>> {
+    let ts_lang = language.tree_sitter_language();
+    let query = Query::new(&ts_lang, BASH_IMPORT_QUERY)
+        .with_context(|| format!("failed to compile import query for {language}"))?;
+    Ok(Arc::new(query))
+}
+
+pub fn compile_comment_query(language: Language) -> Result<Arc<Query>> {
+    let ts_lang = language.tree_sitter_language();
+    let query = Query::new(&ts_lang, BASH_COMMENT_QUERY)
+        .with_context(|| format!("failed to compile comment query for {language}"))?;
+    Ok(Arc::new(query))
+}
+
+// ── Symbol extraction ──
+
+pub fn extract_symbols(
+    tree: &Tree,
+    source: &[u8],
+    query: &Query,
+    file_path: &str,
+) -> Vec<SymbolInfo> {
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), source);
+
+    let name_idx = query.capture_index_for_name("name");
+    let definition_idx = query.capture_index_for_name("definition");
+
+    let mut symbols = Vec::new();
+
+    while let Some(m) = matches.next() {
+        let name_cap = name_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+        let def_cap = definition_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+
+        let (Some(name_cap), Some(def_cap)) = (name_cap, def_cap) else {
+            continue;
+        };
+
+        let name_node = name_cap.node;
+        let def_node = def_cap.node;
+
+        let name = name_node.utf8_text(source).unwrap_or("").to_string();
+        if name.is_empty() {
+            continue;
+        }
+
+        // Bash has no visibility: every function is callable by anything
+        // that sources the script, so functions count as exported.
+        // Variables only leave the script through `export`.
+        let (kind, is_exported, is_mutable) = if def_node.kind() == "function_definition" {
+            (SymbolKind::Function, true, false)
+        } else {
+            let exported = declaration_keyword(def_node).is_some_and(|k| k.kind() == "export");
+            if is_readonly_bash(def_node, source) {
+                (SymbolKind::Constant, exported, false)
+            } else {
+                (SymbolKind::Variable, exported, true)
+            }
+        };
+
+        symbols.push(SymbolInfo {
+            name,
+            kind,
+            file_path: file_path.to_string(),
+            start_byte: def_node.start_byte() as u32,
+            end_byte: def_node.end_byte() as u32,
+            start_line: def_node.start_position().row as u32 + 1,
+            start_column: def_node.start_position().column as u32,
+            end_line: def_node.end_position().row as u32 + 1,
+            end_column: def_node.end_position().column as u32,
+            is_exported,
+            visibility: SymbolVisibility::Public,
+            is_async: false,
+            is_static: false,
+            is_abstract: false,
+            is_mutable,
+            is_deprecated: false,
+            loc: None,
+        });
+    }
+
+    symbols
+}
+
+// ── Import extraction ──
+
+pub fn extract_imports(
+    tree: &Tree,
+    source: &[u8],
+    query: &Query,
+    file_path: &str,
+) -> Vec<ImportInfo> {
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), source);
+
+    let command_idx = query.capture_index_for_name("command");
+    let import_idx = query.capture_index_for_name("import");
+
+    let mut imports = Vec::new();
+
+    while let Some(m) = matches.next() {
+        let command_cap = command_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+        let import_cap = import_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+
+        let (Some(command_cap), Some(import_cap)) = (command_cap, import_cap) else {
+            continue;
+        };
+
+        let command = command_cap.node.utf8_text(source).unwrap_or("");
+        if command != "source" && command != "." {
+            continue;
+        }
+
+        let import_node = import_cap.node;
+        let Some(path_node) = import_node.child_by_field_name("argument") else {
+            continue;
+        };
+        let raw_path = path_node.utf8_text(source).unwrap_or("");
+        let module_specifier = raw_path.trim_matches(|c| c == '"' || c == '\'').to_string();
+        if module_specifier.is_empty() {
+            continue;
+        }
+
+        let imported_name = module_specifier
+            .rsplit('/')
+            .next()
+            .unwrap_or(&module_specifier)
+            .to_string();
+
+        // A bare name (`source lib.sh`) is looked up on $PATH; only
+        // explicit paths point at a file we can place.
+        let is_external = !(module_specifier.starts_with('.') || module_specifier.starts_with('/'));
+
+        imports.push(ImportInfo {
+            source_file: file_path.to_string(),
+            module_specifier,
+            imported_name: imported_name.clone(),
+            local_name: imported_name,
+            kind: "source".to_string(),
+            is_type_only: false,
+            line: import_node.start_position().row as u32 + 1,
+            order: 0,
+            is_external,
+            external_kind: None,
+        });
+    }
+
+    imports
+}
+
+// ── Comment extraction ──
+
+pub fn extract_comments(
+    tree: &Tree,
+    source: &[u8],
+    query: &Query,
+    file_path: &str,
+) -> Vec<CommentInfo> {
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), source);
+
+    let comment_idx = query.capture_index_for_name("comment");
+
+    let mut comments = Vec::new();
+
+    while let Some(m) = matches.next() {
+        let comment_cap = comment_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+        let Some(comment_cap) = comment_cap else {
+            continue;
+        };
+
+        let node = comment_cap.node;
+        let text = node.utf8_text(source).unwrap_or("").to_string();
+        if text.is_empty() {
+            continue;
+        }
+        // The grammar parses the `#!` interpreter line as a comment.
+        if node.start_byte() == 0 && text.starts_with("#!") {
+            continue;
+        }
+
+        let (associated_symbol, associated_symbol_kind) = find_associated_symbol(node, source);
+
+        comments.push(CommentInfo {
+            file_path: file_path.to_string(),
+            text,
+            kind: "line".to_string(),
+            start_byte: node.start_byte() as u32,
+            end_byte: node.end_byte() as u32,
+            start_line: node.start_position().row as u32 + 1,
+            start_column: node.start_position().column as u32,
+            end_line: node.end_position().row as u32 + 1,
+            end_column: node.end_position().column as u32,
+            associated_symbol,
+            associated_symbol_kind,
+        });
+    }
+
+    comments
+}
+
+fn find_associated_symbol(
+    comment_node: tree_sitter::Node,
+    source: &[u8],
+) -> (Option<String>, Option<String>) {
+    // Skip the rest of a multi-line `#` block to reach the code below it.
+    let mut sibling = comment_node.next_named_sibling();
+    while let Some(node) = sibling
+        && node.kind() == "comment"
+    {
+        sibling = node.next_named_sibling();
+    }
+    let Some(sibling) = sibling else {
+        return (None, None);
+    };
+
+    extract_symbol_from_node(sibling, source)
+}
+
+fn extract_symbol_from_node(
+    node: tree_sitter::Node,
+    source: &[u8],
+) -> (Option<String>, Option<String>) {
+    let name_of = |n: tree_sitter::Node| {
+        n.child_by_field_name("name")
+            .and_then(|n| n.utf8_text(source).ok())
+            .map(|s| s.to_string())
+    };
+    match node.kind() {
+        "function_definition" => (name_of(node), Some("function".to_string())),
+        "variable_assignment" => (name_of(node), Some("variable".to_string())),
+        "declaration_command" => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if child.kind() == "variable_assignment" {
+                    let kind = if is_readonly_bash(child, source) {
+                        "constant"
+                    } else {
+                        "variable"
+                    };
+                    return (name_of(child), Some(kind.to_string()));
+                }
+            }
+            (None, None)
+        }
+        _ => (None, None),
+    }
+}
+
+// ── Import resolution ──
+
+/// Resolve a sourced script relative to the sourcing file, falling back
+/// to the project root. Specifiers built from expansions
+/// (`"$DIR/lib.sh"`) never match a workspace path and stay unresolved.
+pub fn resolve_import(
+    source_file: &str,
+    specifier: &str,
+    known_files: &HashSet<String>,
+) -> Option<String> {
+    let base_dir = source_file.rsplit_once('/').map(|(d, _)| d).unwrap_or("");
+    let resolved = normalize_relative_path(base_dir, specifier);
+    if known_files.contains(&resolved) {
+        return Some(resolved);
+    }
+    let from_root = specifier.trim_start_matches("./");
+    if known_files.contains(from_root) {
+        return Some(from_root.to_string());
+    }
+    None
+}
+
+fn normalize_relative_path(base_dir: &str, specifier: &str) -> String {
+    let specifier = specifier.strip_prefix("./").unwrap_or(specifier);
+    let mut parts: Vec<&str> = if base_dir.is_empty() {
+        Vec::new()
+    } else {
+        base_dir.split('/').collect()
+    };
+    for segment in specifier.split('/') {
+        match segment {
+            ".." => {
+                parts.pop();
+            }
+            "." | "" => {}
+            other => parts.push(other),
+        }
+    }
+    parts.join("/")
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::create_parser;
+
+    // ── resolve_import regression tests ──
+
+    #[test]
+    fn resolves_sibling_and_parent_scripts() {
+        let files = HashSet::from(["scripts/lib.sh".to_string(), "common/env.sh".to_string()]);
+        assert_eq!(
+            resolve_import("scripts/deploy.sh", "./lib.sh", &files),
+            Some("scripts/lib.sh".to_string())
+        );
+        assert_eq!(
+            resolve_import("scripts/deploy.sh", "../common/env.sh", &files),
+            Some("common/env.sh".to_string())
+        );
+        assert_eq!(
+            resolve_import("scripts/deploy.sh", "$DIR/lib.sh", &files),
+            None
+        );
+    }
+
+    fn parse_and_extract(source: &str) -> Vec<SymbolInfo> {
+        let mut parser = create_parser(Language::Bash).expect("create parser");
+        let tree = parser.parse(source.as_bytes(), None).expect("parse");
+        let query = compile_symbol_query(Language::Bash).expect("compile query");
+        extract_symbols(&tree, source.as_bytes(), &query, "test.sh")
+    }
+
+    fn parse_and_extract_imports(source: &str) -> Vec<ImportInfo> {
+        let mut parser = create_parser(Language::Bash).expect("create parser");
+        let tree = parser.parse(source.as_bytes(), None).expect("parse");
+        let query = compile_import_query(Language::Bash).expect("compile import query");
+        extract_imports(&tree, source.as_bytes(), &query, "test.sh")
+    }
+
+    fn parse_and_extract_comments(source: &str) -> Vec<CommentInfo> {
+        let mut parser = create_parser(Language::Bash).expect("create parser");
+        let tree = parser.parse(source.as_bytes(), None).expect("parse");
+        let query = compile_comment_query(Language::Bash).expect("compile comment query");
+        extract_comments(&tree, source.as_bytes(), &query, "test.sh")
+    }
+
+    #[test]
+    fn extract_function_is_exported() {
+        let syms = parse_and_extract("greet() {\n  echo hi\n}\nfunction deploy {\n  :\n}\n");
+        let greet = syms.iter().find(|s| s.name == "greet").expect("greet");
+        assert_eq!(greet.kind, SymbolKind::Function);
+        assert!(greet.is_exported);
+        assert_eq!(greet.visibility, SymbolVisibility::Public);
+        let deploy = syms.iter().find(|s| s.name == "deploy").expect("deploy");
+        assert_eq!(deploy.kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn extract_top_level_variables() {
+        let syms = parse_and_extract("NAME=app\nexport PORT=8080\nreadonly VERSION=1\n");
+        let name = syms.iter().find(|s| s.name == "NAME").expect("NAME");
+        assert_eq!(name.kind, SymbolKind::Variable);
+        assert!(!name.is_exported);
+        assert!(name.is_mutable);
+        let port = syms.iter().find(|s| s.name == "PORT").expect("PORT");
+        assert!(port.is_exported);
+        let version = syms.iter().find(|s| s.name == "VERSION").expect("VERSION");
+        assert_eq!(version.kind, SymbolKind::Constant);
+        assert!(!version.is_mutable);
+    }
+
+    #[test]
+    fn function_locals_are_not_symbols() {
+        let syms = parse_and_extract("run() {\n  local tmp=1\n  count=2\n}\n");
+        assert_eq!(syms.len(), 1);
+        assert_eq!(syms[0].name, "run");
+    }
+
+    #[test]
+    fn source_relative_script_is_internal() {
+        let imports = parse_and_extract_imports("source ./lib.sh\n. \"../common/env.sh\"\n");
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].module_specifier, "./lib.sh");
+        assert_eq!(imports[0].imported_name, "lib.sh");
+        assert_eq!(imports[0].kind, "source");
+        assert!(!imports[0].is_external);
+        assert_eq!(imports[1].module_specifier, "../common/env.sh");
+        assert_eq!(imports[1].line, 2);
+    }
+
+    #[test]
+    fn source_bare_name_is_external() {
+        let imports = parse_and_extract_imports("source nvm.sh\necho done\n");
+        assert_eq!(imports.len(), 1);
+        assert!(imports[0].is_external);
+    }
+
+    #[test]
+    fn line_comment_skips_shebang() {
+        let comments = parse_and_extract_comments("#!/bin/bash\n# set up env\nNAME=app\n");
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].text, "# set up env");
+        assert_eq!(comments[0].kind, "line");
+        assert_eq!(comments[0].associated_symbol.as_deref(), Some("NAME"));
+    }
+
+    #[test]
+    fn comment_associated_with_function() {
+        let comments = parse_and_extract_comments("# Deploys it.\n# Twice.\ndeploy() {\n  :\n}\n");
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].associated_symbol.as_deref(), Some("deploy"));
+        assert_eq!(
+            comments[0].associated_symbol_kind.as_deref(),
+            Some("function")
+        );
+    }
+
+    #[test]
+    fn empty_source_no_symbols() {
+        assert!(parse_and_extract("").is_empty());
+    }
+}
@@ -138,6 +138,9 @@ const C_SYMBOL_QUERY: &str = r#"
 (field_declaration
   declarator: (array_declarator
     declarator: (field_identifier) @name)) @definition
+
+(enumerator
+  name: (identifier) @name) @definition
 "#;
 
 // ── Import queries ──
@@ -237,6 +240,8 @@ pub fn extract_symbols(
             is_abstract: false,
             // C `const`-ness is tracked in `c_attrs.is_const`, not here.
             is_mutable: false,
+            is_deprecated: false,
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -264,6 +269,7 @@ fn determine_c_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
         "type_definition" => Some(SymbolKind::Typedef),
         "preproc_def" | "preproc_function_def" => Some(SymbolKind::Macro),
         "field_declaration" => Some(SymbolKind::Field),
+        "enumerator" => Some(SymbolKind::EnumMember),
         _ => None,
     }
 }
@@ -276,6 +282,7 @@ fn is_exported_c(def_node: tree_sitter::Node, source: &[u8]) -> bool {
         | "struct_specifier"
         | "union_specifier"
         | "enum_specifier"
+        | "enumerator"
         | "type_definition" => return true,
         _ => {}
     }
@@ -336,7 +343,9 @@ pub fn extract_imports(
             kind: "include".to_string(),
             is_type_only: false,
             line: include_node.start_position().row as u32 + 1,
+            order: 0,
             is_external: is_system,
+            external_kind: None,
         });
     }
 
@@ -644,6 +653,17 @@ mod tests {
         assert_eq!(s.unwrap().kind, SymbolKind::Enum);
     }
 
+    #[test]
+    fn extract_enum_members() {
+        let syms = parse_and_extract("enum Color { RED, GREEN = 2, BLUE };");
+        let members: Vec<&str> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumMember)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(members, vec!["RED", "GREEN", "BLUE"]);
+    }
+
     #[test]
     fn extract_typedef() {
         let syms = parse_and_extract("typedef unsigned int uint;");
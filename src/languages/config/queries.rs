@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Query, QueryCursor, Tree};
+
+use crate::language::Language;
+use crate::models::{CommentInfo, ImportInfo, SymbolInfo, SymbolKind, SymbolVisibility};
+
+// ── Symbol queries ──
+//
+// Only top-level keys are symbols: nested keys repeat across files
+// (`name`, `version`, `image`) and would bury the ones worth finding.
+
+const YAML_SYMBOL_QUERY: &str = r#"
+(stream
+  (document
+    (block_node
+      (block_mapping
+        (block_mapping_pair
+          key: (_) @name) @definition))))
+"#;
+
+const JSON_SYMBOL_QUERY: &str = r#"
+(document
+  (object
+    (pair
+      key: (string) @name) @definition))
+"#;
+
+// A `[table]` header is a top-level key in its own right; its span
+// covers the pairs under it.
+const TOML_SYMBOL_QUERY: &str = r#"
+(document
+  (pair
+    . (_) @name) @definition)
+
+(document
+  (table
+    . (_) @name) @definition)
+
+(document
+  (table_array_element
+    . (_) @name) @definition)
+"#;
+
+// ── Comment queries ──
+
+const CONFIG_COMMENT_QUERY: &str = r#"
+(comment) @comment
+"#;
+
+// ── Query compilation ──
+
+pub fn compile_symbol_query(language: Language) -> Result<Arc<Query>> {
+    let source = match language {
+        Language::Yaml => YAML_SYMBOL_QUERY,
+        Language::Json => JSON_SYMBOL_QUERY,
+        _ => TOML_SYMBOL_QUERY,
+    };
+    let ts_lang = language.tree_sitter_language();
+    let query = Query::new(&ts_lang, source)
+        .with_context(|| format!("failed to compile symbol query for {language}"))?;
+    Ok(Arc::new(query))
+}
+
+/// Config files have no imports; the empty query keeps the builder's
+/// per-language query tables uniform.
+pub fn compile_import_query(language: Language) -> Result<Arc<Query>> {
+    let ts_lang = language.tree_sitter_language();
+    let query = Query::new(&ts_lang, "")
+        .with_context(|| format!("failed to compile import query for {language}"))?;
+    Ok(Arc::new(query))
+}
+
+pub fn compile_comment_query(language: Language) -> Result<Arc<Query>> {
+    let ts_lang = language.tree_sitter_language();
+    let query = Query::new(&ts_lang, CONFIG_COMMENT_QUERY)
+        .with_context(|| format!("failed to compile comment query for {language}"))?;
+    Ok(Arc::new(query))
+}
+
+// ── Symbol extraction ──
+
+pub fn extract_symbols(
+    tree: &Tree,
+    source: &[u8],
+    query: &Query,
+    file_path: &str,
+) -> Vec<SymbolInfo> {
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), source);
+
+    let name_idx = query.capture_index_for_name("name");
+    let definition_idx = query.capture_index_for_name("definition");
+
+    let mut symbols = Vec::new();
+
+    while let Some(m) = matches.next() {
+        let name_cap = name_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+        let def_cap = definition_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+
+        let (Some(name_cap), Some(def_cap)) = (name_cap, def_cap) else {
+            continue;
+        };
+
+        let def_node = def_cap.node;
+        let name = key_text(name_cap.node, source);
+        if name.is_empty() {
+            continue;
+        }
+
+        symbols.push(SymbolInfo {
+            name,
+            kind: SymbolKind::Variable,
+            file_path: file_path.to_string(),
+            start_byte: def_node.start_byte() as u32,
+            end_byte: def_node.end_byte() as u32,
+            start_line: def_node.start_position().row as u32 + 1,
+            start_column: def_node.start_position().column as u32,
+            end_line: def_node.end_position().row as u32 + 1,
+            end_column: def_node.end_position().column as u32,
+            is_exported: false,
+            visibility: SymbolVisibility::Public,
+            is_async: false,
+            is_static: false,
+            is_abstract: false,
+            is_mutable: false,
+            is_deprecated: false,
+            loc: None,
+        });
+    }
+
+    symbols
+}
+
+/// Key text with surrounding quotes removed (`"db-url"` → `db-url`).
+fn key_text(node: tree_sitter::Node, source: &[u8]) -> String {
+    node.utf8_text(source)
+        .unwrap_or("")
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string()
+}
+
+// ── Import extraction ──
+
+pub fn extract_imports(
+    _tree: &Tree,
+    _source: &[u8],
+    _query: &Query,
+    _file_path: &str,
+) -> Vec<ImportInfo> {
+    Vec::new()
+}
+
+// ── Comment extraction ──
+
+pub fn extract_comments(
+    tree: &Tree,
+    source: &[u8],
+    query: &Query,
+    file_path: &str,
+) -> Vec<CommentInfo> {
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), source);
+
+    let comment_idx = query.capture_index_for_name("comment");
+
+    let mut comments = Vec::new();
+
+    while let Some(m) = matches.next() {
+        let comment_cap = comment_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+        let Some(comment_cap) = comment_cap else {
+            continue;
+        };
+
+        let node = comment_cap.node;
+        let text = node.utf8_text(source).unwrap_or("").to_string();
+        if text.is_empty() {
+            continue;
+        }
+
+        comments.push(CommentInfo {
+            file_path: file_path.to_string(),
+            text,
+            kind: "line".to_string(),
+            start_byte: node.start_byte() as u32,
+            end_byte: node.end_byte() as u32,
+            start_line: node.start_position().row as u32 + 1,
+            start_column: node.start_position().column as u32,
+            end_line: node.end_position().row as u32 + 1,
+            end_column: node.end_position().column as u32,
+            associated_symbol: None,
+            associated_symbol_kind: None,
+        });
+    }
+
+    comments
+}
+
+// ── Tests ──
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::create_parser;
+
+    fn parse_and_extract(language: Language, source: &str) -> Vec<SymbolInfo> {
+        let mut parser = create_parser(language).expect("create parser");
+        let tree = parser.parse(source.as_bytes(), None).expect("parse");
+        let query = compile_symbol_query(language).expect("compile query");
+        extract_symbols(&tree, source.as_bytes(), &query, "config")
+    }
+
+    fn names(symbols: &[SymbolInfo]) -> Vec<&str> {
+        symbols.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn yaml_top_level_keys() {
+        let source = "\
+database_url: postgres://localhost/app
+server:
+  port: 8080
+  host: 0.0.0.0
+\"quoted key\": 1
+";
+        let syms = parse_and_extract(Language::Yaml, source);
+        assert_eq!(names(&syms), vec!["database_url", "server", "quoted key"]);
+        assert!(syms.iter().all(|s| s.kind == SymbolKind::Variable));
+        let server = &syms[1];
+        assert_eq!((server.start_line, server.end_line), (2, 4));
+    }
+
+    #[test]
+    fn yaml_keys_from_every_document() {
+        let syms = parse_and_extract(Language::Yaml, "a: 1\n---\nb: 2\n");
+        assert_eq!(names(&syms), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn toml_top_level_keys_and_tables() {
+        let source = "\
+title = \"app\"
+database_url = \"postgres://localhost/app\"
+
+[server]
+port = 8080
+
+[[bin]]
+name = \"cli\"
+";
+        let syms = parse_and_extract(Language::Toml, source);
+        assert_eq!(names(&syms), vec!["title", "database_url", "server", "bin"]);
+        let server = syms.iter().find(|s| s.name == "server").unwrap();
+        assert_eq!(server.start_line, 4);
+    }
+
+    #[test]
+    fn json_top_level_keys() {
+        let syms = parse_and_extract(
+            Language::Json,
+            "{\n  \"name\": \"app\",\n  \"scripts\": { \"build\": \"tsc\" }\n}\n",
+        );
+        assert_eq!(names(&syms), vec!["name", "scripts"]);
+        assert!(!syms[0].is_exported);
+    }
+}
@@ -251,6 +251,9 @@ const CPP_SYMBOL_QUERY: &str = r#"
 (field_declaration
   declarator: (reference_declarator
     (field_identifier) @name)) @definition
+
+(enumerator
+  name: (identifier) @name) @definition
 "#;
 
 // ── Import queries ── (same as C: #include directives)
@@ -348,6 +351,8 @@ pub fn extract_symbols(
             is_abstract,
             // `mutable` on class members is rare; deferred.
             is_mutable: false,
+            is_deprecated: false,
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -379,6 +384,7 @@ fn determine_cpp_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
         "parameter_declaration" | "optional_parameter_declaration" => Some(SymbolKind::Parameter),
         "for_range_loop" => Some(SymbolKind::Variable),
         "field_declaration" => Some(SymbolKind::Field),
+        "enumerator" => Some(SymbolKind::EnumMember),
         _ => None,
     }
 }
@@ -404,6 +410,7 @@ fn is_exported_cpp(def_node: tree_sitter::Node, source: &[u8]) -> bool {
         | "struct_specifier"
         | "union_specifier"
         | "enum_specifier"
+        | "enumerator"
         | "type_definition"
         | "class_specifier"
         | "namespace_definition" => return true,
@@ -466,7 +473,9 @@ pub fn extract_imports(
             kind: "include".to_string(),
             is_type_only: false,
             line: include_node.start_position().row as u32 + 1,
+            order: 0,
             is_external: is_system,
+            external_kind: None,
         });
     }
 
@@ -793,6 +802,17 @@ mod tests {
         assert_eq!(s.unwrap().kind, SymbolKind::Enum);
     }
 
+    #[test]
+    fn extract_enum_class_members() {
+        let syms = parse_and_extract("enum class Mode { Read, Write = 4 };");
+        let members: Vec<&str> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumMember)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(members, vec!["Read", "Write"]);
+    }
+
     #[test]
     fn static_function_not_exported() {
         let syms = parse_and_extract("static void helper() { }");
@@ -28,7 +28,8 @@ fn visibility_csharp(def_node: tree_sitter::Node, source: &[u8]) -> SymbolVisibi
         | "catch_declaration"
         | "variable_declarator"
         | "foreach_statement" => return SymbolVisibility::Private,
-        "namespace_declaration" => return SymbolVisibility::Public,
+        // Enum members take no modifiers and are always public.
+        "namespace_declaration" | "enum_member_declaration" => return SymbolVisibility::Public,
         _ => {}
     }
 
@@ -118,6 +119,42 @@ fn has_modifier(def_node: tree_sitter::Node, source: &[u8], keyword: &str) -> bo
     false
 }
 
+/// True if `def_node` carries `[Obsolete]` (or `[ObsoleteAttribute]`,
+/// `[System.Obsolete(...)]`). Fields are declared through a variable
+/// declarator, so their attributes sit on the enclosing declaration.
+fn is_deprecated_csharp(def_node: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut node = def_node;
+    while matches!(node.kind(), "variable_declarator" | "variable_declaration")
+        && let Some(parent) = node.parent()
+    {
+        node = parent;
+    }
+    let mut cursor = node.walk();
+    for list in node.children(&mut cursor) {
+        if list.kind() != "attribute_list" {
+            continue;
+        }
+        let mut inner = list.walk();
+        for attribute in list.children(&mut inner) {
+            if attribute.kind() != "attribute" {
+                continue;
+            }
+            let name = attribute
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source).ok())
+                .unwrap_or("");
+            let name = name.split('<').next().unwrap_or(name).trim();
+            if matches!(
+                name.rsplit('.').next(),
+                Some("Obsolete" | "ObsoleteAttribute")
+            ) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
 /// True if `def_node` is a method/property/event/indexer declared
 /// directly inside an `interface_declaration` body — interface members
 /// are implicitly abstract.
@@ -179,6 +216,9 @@ const CSHARP_SYMBOL_QUERY: &str = r#"
     (variable_declarator
       (identifier) @name))) @definition
 
+(enum_member_declaration
+  name: (identifier) @name) @definition
+
 (parameter
   name: (identifier) @name) @definition
 
@@ -304,6 +344,8 @@ pub fn extract_symbols(
             // mutable by default. Leaving false matches the cross-language
             // contract that `is_mutable` flags explicit mutability.
             is_mutable: false,
+            is_deprecated: is_deprecated_csharp(def_node, source),
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -322,6 +364,7 @@ fn determine_csharp_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
         "property_declaration" => Some(SymbolKind::Field),
         "delegate_declaration" => Some(SymbolKind::TypeAlias),
         "field_declaration" => Some(SymbolKind::Field),
+        "enum_member_declaration" => Some(SymbolKind::EnumMember),
         // Parameters: regular `(parameter ...)`, `params int[] xs` varargs (the
         // grammar's `_parameter_array` rule is hidden, so its trailing
         // identifier appears directly under `parameter_list`), the
@@ -339,8 +382,11 @@ fn determine_csharp_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
 }
 
 fn is_exported_csharp(def_node: tree_sitter::Node, source: &[u8]) -> bool {
-    // Namespaces are always exported
-    if def_node.kind() == "namespace_declaration" {
+    // Namespaces and enum members are always exported
+    if matches!(
+        def_node.kind(),
+        "namespace_declaration" | "enum_member_declaration"
+    ) {
         return true;
     }
 
@@ -360,6 +406,22 @@ fn is_exported_csharp(def_node: tree_sitter::Node, source: &[u8]) -> bool {
     false // conservative default: not exported
 }
 
+/// `App.Services.` for a file-scoped `namespace App.Services;`. Block
+/// namespaces are symbols and qualify their members themselves.
+pub fn file_namespace_prefix(tree: &Tree, source: &[u8]) -> Option<String> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let namespace = root
+        .named_children(&mut cursor)
+        .find(|n| n.kind() == "file_scoped_namespace_declaration")?;
+    let name = namespace
+        .child_by_field_name("name")?
+        .utf8_text(source)
+        .ok()?;
+    let name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+    (!name.is_empty()).then(|| format!("{name}."))
+}
+
 // ── Import extraction ──
 
 pub fn extract_imports(
@@ -384,39 +446,91 @@ pub fn extract_imports(
         let node = import_cap.node;
         let text = node.utf8_text(source).unwrap_or("").to_string();
 
-        let module_specifier = extract_using_namespace(&text);
-        if module_specifier.is_empty() {
+        let using = parse_using_directive(&text);
+        if using.namespace.is_empty() {
             continue;
         }
 
+        // An alias names one namespace or type; a plain using brings in
+        // everything under the namespace.
+        let (imported_name, local_name) = match using.alias {
+            Some(alias) => (
+                using
+                    .namespace
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or(&using.namespace)
+                    .to_string(),
+                alias,
+            ),
+            None => ("*".to_string(), "*".to_string()),
+        };
+
         imports.push(ImportInfo {
             source_file: file_path.to_string(),
-            module_specifier,
-            imported_name: "*".to_string(),
-            local_name: "*".to_string(),
-            kind: "using".to_string(),
+            module_specifier: using.namespace,
+            imported_name,
+            local_name,
+            kind: using.kind.to_string(),
             is_type_only: false,
             line: (node.start_position().row + 1) as u32,
+            order: 0,
             is_external: true, // no syntactic way to distinguish
+            external_kind: None,
         });
     }
 
     imports
 }
 
-fn extract_using_namespace(text: &str) -> String {
-    // Extract namespace from "using System.Collections.Generic;"
+/// A parsed `using` directive.
+struct UsingDirective {
+    /// Namespace or type the directive refers to.
+    namespace: String,
+    /// `C` in `using C = System.Console;`.
+    alias: Option<String>,
+    /// `"global_using"` for C# 10 `global using`, `"using_static"` for
+    /// `using static`, otherwise `"using"`.
+    kind: &'static str,
+}
+
+fn parse_using_directive(text: &str) -> UsingDirective {
     let text = text.trim();
-    let text = text.strip_prefix("using").unwrap_or(text).trim();
-    let text = text.strip_prefix("static").unwrap_or(text).trim();
+    let (text, global) = match strip_keyword(text, "global") {
+        Some(rest) => (rest, true),
+        None => (text, false),
+    };
+    let text = strip_keyword(text, "using").unwrap_or(text);
+    let (text, is_static) = match strip_keyword(text, "static") {
+        Some(rest) => (rest, true),
+        None => (text, false),
+    };
     let text = text.strip_suffix(';').unwrap_or(text).trim();
 
-    // Handle alias: "using Alias = Namespace.Type" → take just the namespace part
-    if let Some((_alias, ns)) = text.split_once('=') {
-        return ns.trim().to_string();
+    let (alias, namespace) = match text.split_once('=') {
+        Some((alias, ns)) => (Some(alias.trim().to_string()), ns.trim()),
+        None => (None, text),
+    };
+    let kind = if global {
+        "global_using"
+    } else if is_static {
+        "using_static"
+    } else {
+        "using"
+    };
+    UsingDirective {
+        namespace: namespace.to_string(),
+        alias,
+        kind,
     }
+}
 
-    text.to_string()
+/// Strip a leading keyword only when it's a whole word, so a namespace
+/// like `staticdata` isn't mistaken for `static`.
+fn strip_keyword<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = text.strip_prefix(keyword)?;
+    rest.starts_with(char::is_whitespace)
+        .then(|| rest.trim_start())
 }
 
 // ── Comment extraction ──
@@ -643,6 +757,22 @@ mod tests {
         assert_eq!(s.unwrap().kind, SymbolKind::Enum);
     }
 
+    #[test]
+    fn extract_enum_members() {
+        let syms = parse_and_extract("enum Level { Low = 1, High = 2 }");
+        let members: Vec<&SymbolInfo> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumMember)
+            .collect();
+        let names: Vec<&str> = members.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Low", "High"]);
+        assert!(
+            members
+                .iter()
+                .all(|s| s.visibility == SymbolVisibility::Public)
+        );
+    }
+
     #[test]
     fn extract_namespace() {
         let syms = parse_and_extract("namespace MyApp { }");
@@ -670,20 +800,51 @@ mod tests {
     }
 
     #[test]
-    fn extract_using_namespace_helper() {
-        assert_eq!(extract_using_namespace("using System;"), "System");
+    fn parse_using_directive_helper() {
+        assert_eq!(parse_using_directive("using System;").namespace, "System");
         assert_eq!(
-            extract_using_namespace("using System.Collections.Generic;"),
+            parse_using_directive("using System.Collections.Generic;").namespace,
             "System.Collections.Generic"
         );
         assert_eq!(
-            extract_using_namespace("using static System.Math;"),
+            parse_using_directive("using static System.Math;").namespace,
             "System.Math"
         );
         assert_eq!(
-            extract_using_namespace("using Console = System.Console;"),
+            parse_using_directive("using Console = System.Console;").namespace,
             "System.Console"
         );
+        assert_eq!(
+            parse_using_directive("using staticdata;").namespace,
+            "staticdata"
+        );
+    }
+
+    #[test]
+    fn global_using() {
+        let imports = parse_and_extract_imports("global using System;");
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].module_specifier, "System");
+        assert_eq!(imports[0].kind, "global_using");
+        assert_eq!(imports[0].local_name, "*");
+    }
+
+    #[test]
+    fn using_static() {
+        let imports = parse_and_extract_imports("using static System.Math;");
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].module_specifier, "System.Math");
+        assert_eq!(imports[0].kind, "using_static");
+    }
+
+    #[test]
+    fn using_alias() {
+        let imports = parse_and_extract_imports("using C = System.Console;");
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].module_specifier, "System.Console");
+        assert_eq!(imports[0].imported_name, "Console");
+        assert_eq!(imports[0].local_name, "C");
+        assert_eq!(imports[0].kind, "using");
     }
 
     #[test]
@@ -173,6 +173,8 @@ pub fn extract_symbols(
             is_static: false,
             is_abstract,
             is_mutable: false,
+            is_deprecated: false,
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -263,7 +265,9 @@ pub fn extract_imports(
             kind: "import".to_string(),
             is_type_only: false,
             line: import_node.start_position().row as u32 + 1,
+            order: 0,
             is_external: true, // Go has no syntactic internal/external distinction
+            external_kind: None,
         });
     }
 
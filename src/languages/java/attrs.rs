@@ -2,7 +2,8 @@
 //!
 //! Implements the MVP columns from `docs/attrs-java.md`:
 //! - `annotations`     — simple names of `@Foo` / `@Foo(...)` markers on
-//!   the symbol's `modifiers` node, in source order.
+//!   the symbol's `modifiers` node, in source order. Their argument text
+//!   goes to the separate `annotation` table.
 //! - `is_final`        — `final` keyword in `modifiers`.
 //! - `is_synchronized` — `synchronized` keyword in `modifiers` (method
 //!   modifier form only; `synchronized (x) {}` statements do not count).
@@ -42,6 +43,7 @@ pub fn extract_attrs(
             }
             None => (Vec::new(), false, false),
         };
+        let (annotations, annotation_arguments) = annotations.into_iter().unzip();
 
         let throws_clause = def_node
             .map(|n| collect_throws(n, source))
@@ -50,6 +52,7 @@ pub fn extract_attrs(
         out.push(JavaAttrsRow {
             symbol_id,
             annotations,
+            annotation_arguments,
             is_final,
             is_synchronized,
             throws_clause,
@@ -58,9 +61,10 @@ pub fn extract_attrs(
     out
 }
 
-/// Walk the `modifiers` child (if any) and return the simple names of
-/// every `marker_annotation` / `annotation` in source order.
-fn collect_annotations(def_node: Node, source: &[u8]) -> Vec<String> {
+/// Walk the `modifiers` child (if any) and return the simple name and
+/// argument text of every `marker_annotation` / `annotation` in source
+/// order.
+fn collect_annotations(def_node: Node, source: &[u8]) -> Vec<(String, Option<String>)> {
     let mut out = Vec::new();
     let mut cursor = def_node.walk();
     for child in def_node.children(&mut cursor) {
@@ -72,7 +76,7 @@ fn collect_annotations(def_node: Node, source: &[u8]) -> Vec<String> {
             match modifier.kind() {
                 "marker_annotation" | "annotation" => {
                     if let Some(name) = annotation_simple_name(modifier, source) {
-                        out.push(name);
+                        out.push((name, annotation_arguments(modifier, source)));
                     }
                 }
                 _ => {}
@@ -98,6 +102,19 @@ fn annotation_simple_name(ann_node: Node, source: &[u8]) -> Option<String> {
     }
 }
 
+/// The text between an annotation's parentheses, whitespace collapsed
+/// (`@Table(name = "users")` → `name = "users"`). `None` for a marker
+/// annotation; `@Foo()` gives an empty string.
+fn annotation_arguments(ann_node: Node, source: &[u8]) -> Option<String> {
+    let args = ann_node.child_by_field_name("arguments")?;
+    let text = args.utf8_text(source).ok()?;
+    let inner = text
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .unwrap_or(text);
+    Some(inner.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
 /// True if `def_node` carries the given keyword as a direct child of
 /// its `modifiers` node.
 fn has_modifier_keyword(def_node: Node, source: &[u8], keyword: &str) -> bool {
@@ -255,6 +272,26 @@ mod tests {
         assert_eq!(r.annotations, vec!["Cacheable".to_string()]);
     }
 
+    #[test]
+    fn annotation_arguments_are_kept_alongside_names() {
+        let rows = run(
+            "@Entity\n@Table(name=\"users\")\npublic class User { }",
+            "User.java",
+        );
+        let r = rows
+            .iter()
+            .find(|r| r.symbol_id.ends_with("|User|class"))
+            .expect("class row");
+        assert_eq!(
+            r.annotations,
+            vec!["Entity".to_string(), "Table".to_string()]
+        );
+        assert_eq!(
+            r.annotation_arguments,
+            vec![None, Some("name=\"users\"".to_string())]
+        );
+    }
+
     #[test]
     fn final_field_marked() {
         let rows = run(
@@ -27,6 +27,8 @@ fn visibility_java(def_node: tree_sitter::Node, source: &[u8]) -> SymbolVisibili
         | "lambda_expression"
         | "local_variable_declaration"
         | "resource" => return SymbolVisibility::Private,
+        // Enum constants are implicitly `public static final`.
+        "enum_constant" => return SymbolVisibility::Public,
         _ => {}
     }
     let mut cursor = def_node.walk();
@@ -51,7 +53,7 @@ fn visibility_java(def_node: tree_sitter::Node, source: &[u8]) -> SymbolVisibili
 /// child. Only meaningful for nested classes, methods, and fields;
 /// returns `false` for kinds where `static` can't appear.
 fn is_static_java(def_node: tree_sitter::Node, source: &[u8]) -> bool {
-    has_modifier_keyword(def_node, source, "static")
+    def_node.kind() == "enum_constant" || has_modifier_keyword(def_node, source, "static")
 }
 
 /// True if `def_node` is `abstract`. Explicit `abstract` keyword on a
@@ -79,6 +81,31 @@ fn is_abstract_java(def_node: tree_sitter::Node, source: &[u8]) -> bool {
     false
 }
 
+/// True if `def_node`'s modifiers include `@Deprecated`, by simple or
+/// qualified name (`@java.lang.Deprecated`).
+fn is_deprecated_java(def_node: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut cursor = def_node.walk();
+    for child in def_node.children(&mut cursor) {
+        if child.kind() != "modifiers" {
+            continue;
+        }
+        let mut mod_cursor = child.walk();
+        for modifier in child.children(&mut mod_cursor) {
+            if !matches!(modifier.kind(), "marker_annotation" | "annotation") {
+                continue;
+            }
+            let name = modifier
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(source).ok())
+                .unwrap_or("");
+            if name.rsplit('.').next() == Some("Deprecated") {
+                return true;
+            }
+        }
+    }
+    false
+}
+
 fn has_modifier_keyword(def_node: tree_sitter::Node, source: &[u8], keyword: &str) -> bool {
     let mut cursor = def_node.walk();
     for child in def_node.children(&mut cursor) {
@@ -123,6 +150,9 @@ const JAVA_SYMBOL_QUERY: &str = r#"
   declarator: (variable_declarator
     name: (identifier) @name)) @definition
 
+(enum_constant
+  name: (identifier) @name) @definition
+
 (formal_parameter
   name: (identifier) @name) @definition
 
@@ -244,6 +274,8 @@ pub fn extract_symbols(
             // Java has no language-level mutability marker — `final`
             // lives in `java_attrs.is_final`, not on the core symbol.
             is_mutable: false,
+            is_deprecated: is_deprecated_java(def_node, source),
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -258,6 +290,7 @@ fn determine_java_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
         "enum_declaration" => Some(SymbolKind::Enum),
         "method_declaration" | "constructor_declaration" => Some(SymbolKind::Method),
         "field_declaration" => Some(SymbolKind::Field),
+        "enum_constant" => Some(SymbolKind::EnumMember),
         "formal_parameter"
         | "spread_parameter"
         | "catch_formal_parameter"
@@ -268,6 +301,9 @@ fn determine_java_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
 }
 
 fn is_exported_java(def_node: tree_sitter::Node, source: &[u8]) -> bool {
+    if def_node.kind() == "enum_constant" {
+        return true;
+    }
     // Java wraps modifiers in a `modifiers` node
     let mut cursor = def_node.walk();
     for child in def_node.children(&mut cursor) {
@@ -286,6 +322,26 @@ fn is_exported_java(def_node: tree_sitter::Node, source: &[u8]) -> bool {
     false // conservative default: package-private = not exported
 }
 
+/// `com.example.` for a file declaring `package com.example;`.
+pub fn package_prefix(tree: &Tree, source: &[u8]) -> Option<String> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let package = root
+        .named_children(&mut cursor)
+        .find(|n| n.kind() == "package_declaration")?;
+    let mut inner = package.walk();
+    let name = package
+        .named_children(&mut inner)
+        .find(|n| matches!(n.kind(), "scoped_identifier" | "identifier"))?;
+    let name: String = name
+        .utf8_text(source)
+        .ok()?
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    (!name.is_empty()).then(|| format!("{name}."))
+}
+
 // ── Import extraction ──
 
 pub fn extract_imports(
@@ -329,7 +385,9 @@ pub fn extract_imports(
             kind,
             is_type_only: false,
             line: node.start_position().row as u32 + 1,
+            order: 0,
             is_external: true, // Java imports are always external (no relative imports)
+            external_kind: None,
         });
     }
 
@@ -610,6 +668,17 @@ mod tests {
         assert!(s.unwrap().is_exported);
     }
 
+    #[test]
+    fn deprecated_annotation_marks_the_symbol() {
+        let syms = parse_and_extract(
+            "@Deprecated\npublic class Old {\n    @java.lang.Deprecated(since = \"2\")\n    public void gone() {}\n    @Override\n    public String toString() { return \"\"; }\n}",
+        );
+        let deprecated = |name: &str| syms.iter().find(|s| s.name == name).unwrap().is_deprecated;
+        assert!(deprecated("Old"));
+        assert!(deprecated("gone"));
+        assert!(!deprecated("toString"));
+    }
+
     #[test]
     fn extract_private_class() {
         let syms = parse_and_extract("private class Foo { }");
@@ -643,6 +712,22 @@ mod tests {
         assert_eq!(s.unwrap().kind, SymbolKind::Enum);
     }
 
+    #[test]
+    fn extract_enum_constants() {
+        let syms = parse_and_extract("enum Planet { MERCURY(1.0), EARTH(5.9); double mass; }");
+        let constants: Vec<&SymbolInfo> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumMember)
+            .collect();
+        let names: Vec<&str> = constants.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["MERCURY", "EARTH"]);
+        assert!(constants.iter().all(|s| s.is_exported && s.is_static));
+        assert!(
+            syms.iter()
+                .any(|s| s.name == "mass" && s.kind == SymbolKind::Field)
+        );
+    }
+
     #[test]
     fn extract_method() {
         let syms = parse_and_extract("public class Foo { public void bar() { } }");
@@ -1,4 +1,6 @@
+mod bash;
 mod c_lang;
+mod config;
 mod cpp;
 mod csharp;
 mod go;
@@ -9,22 +11,27 @@ mod rust_lang;
 mod typescript;
 
 use std::collections::HashSet;
+use std::path::Path;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use tree_sitter::{Query, Tree};
 
 use crate::graph::GraphNode;
 use crate::language::Language;
 use crate::models::{
-    AttrsBucket, CommentInfo, ExtractedTypes, ImportInfo, ReferencesBucket, SymbolInfo, ThrowsRow,
+    AttrsBucket, CommentInfo, ExtractedTypes, ImportInfo, ReferencesBucket, SymbolInfo, SymbolKind,
+    ThrowsRow,
 };
 
 pub fn compile_symbol_query(language: Language) -> Result<Arc<Query>> {
     match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::compile_symbol_query(language)
-        }
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::compile_symbol_query(language),
         Language::C => c_lang::compile_symbol_query(language),
         Language::Cpp => cpp::compile_symbol_query(language),
         Language::CSharp => csharp::compile_symbol_query(language),
@@ -33,14 +40,40 @@ pub fn compile_symbol_query(language: Language) -> Result<Arc<Query>> {
         Language::Go => go::compile_symbol_query(language),
         Language::Java => java::compile_symbol_query(language),
         Language::Php => php::compile_symbol_query(language),
+        Language::Bash => bash::compile_symbol_query(language),
+        Language::Yaml | Language::Json | Language::Toml => config::compile_symbol_query(language),
     }
 }
 
+/// The symbol query for `language`, read from `<query_dir>/<lang>.symbols.scm`
+/// when that file exists (e.g. `rust.symbols.scm`) and the built-in one
+/// otherwise. An override must keep the built-in capture names
+/// (`@name`, `@definition`, ...) since extraction reads them.
+pub fn compile_symbol_query_from(
+    language: Language,
+    query_dir: Option<&Path>,
+) -> Result<Arc<Query>> {
+    let Some(path) = query_dir
+        .map(|dir| dir.join(format!("{}.symbols.scm", language.as_str())))
+        .filter(|path| path.is_file())
+    else {
+        return compile_symbol_query(language);
+    };
+    let source = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read symbol query {}", path.display()))?;
+    let query = Query::new(&language.tree_sitter_language(), &source)
+        .with_context(|| format!("custom symbol query {} does not compile", path.display()))?;
+    Ok(Arc::new(query))
+}
+
 pub fn compile_import_query(language: Language) -> Result<Arc<Query>> {
     match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::compile_import_query(language)
-        }
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::compile_import_query(language),
         Language::C => c_lang::compile_import_query(language),
         Language::Cpp => cpp::compile_import_query(language),
         Language::CSharp => csharp::compile_import_query(language),
@@ -49,14 +82,19 @@ pub fn compile_import_query(language: Language) -> Result<Arc<Query>> {
         Language::Go => go::compile_import_query(language),
         Language::Java => java::compile_import_query(language),
         Language::Php => php::compile_import_query(language),
+        Language::Bash => bash::compile_import_query(language),
+        Language::Yaml | Language::Json | Language::Toml => config::compile_import_query(language),
     }
 }
 
 pub fn compile_comment_query(language: Language) -> Result<Arc<Query>> {
     match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::compile_comment_query(language)
-        }
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::compile_comment_query(language),
         Language::C => c_lang::compile_comment_query(language),
         Language::Cpp => cpp::compile_comment_query(language),
         Language::CSharp => csharp::compile_comment_query(language),
@@ -65,6 +103,8 @@ pub fn compile_comment_query(language: Language) -> Result<Arc<Query>> {
         Language::Go => go::compile_comment_query(language),
         Language::Java => java::compile_comment_query(language),
         Language::Php => php::compile_comment_query(language),
+        Language::Bash => bash::compile_comment_query(language),
+        Language::Yaml | Language::Json | Language::Toml => config::compile_comment_query(language),
     }
 }
 
@@ -79,10 +119,48 @@ pub fn qname_separator(language: Language) -> &'static str {
         | Language::Tsx
         | Language::JavaScript
         | Language::Jsx
+        | Language::Vue
+        | Language::Svelte
         | Language::CSharp
         | Language::Go
         | Language::Java
-        | Language::Python => ".",
+        | Language::Python
+        | Language::Bash
+        | Language::Yaml
+        | Language::Json
+        | Language::Toml => ".",
+    }
+}
+
+/// Separator override for members of a `parent_kind` symbol. PHP
+/// namespaces join with `\` (`App\Models\User`) while class members
+/// keep `::` (`App\Models\User::save`).
+pub fn qname_member_separator(language: Language, parent_kind: SymbolKind) -> Option<&'static str> {
+    (language == Language::Php && parent_kind == SymbolKind::Namespace).then_some("\\")
+}
+
+/// Byte ranges holding test code inside an otherwise non-test file:
+/// Rust `#[cfg(test)]` modules. Test *files* are recognised by path
+/// instead ([`crate::classify::is_test_file`]).
+pub fn test_ranges(tree: &Tree, source: &[u8], language: Language) -> Vec<(u32, u32)> {
+    match language {
+        Language::Rust => rust_lang::cfg_test_ranges(tree, source),
+        _ => Vec::new(),
+    }
+}
+
+/// Package or file-level namespace that qualifies every top-level
+/// symbol of the file, separator included: `com.example.` for a Java
+/// `package com.example;`, `App.Services.` for a C# file-scoped
+/// `namespace App.Services;`, `App\Models\` for a PHP `namespace
+/// App\Models;`. Block namespaces are symbols already and qualify their
+/// members through the parent chain.
+pub fn qualified_name_prefix(tree: &Tree, source: &[u8], language: Language) -> Option<String> {
+    match language {
+        Language::Java => java::package_prefix(tree, source),
+        Language::CSharp => csharp::file_namespace_prefix(tree, source),
+        Language::Php => php::file_namespace_prefix(tree, source),
+        _ => None,
     }
 }
 
@@ -94,9 +172,12 @@ pub fn extract_symbols(
     language: Language,
 ) -> Vec<SymbolInfo> {
     match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::extract_symbols(tree, source, query, file_path, language)
-        }
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::extract_symbols(tree, source, query, file_path, language),
         Language::C => c_lang::extract_symbols(tree, source, query, file_path),
         Language::Cpp => cpp::extract_symbols(tree, source, query, file_path),
         Language::CSharp => csharp::extract_symbols(tree, source, query, file_path),
@@ -105,6 +186,10 @@ pub fn extract_symbols(
         Language::Go => go::extract_symbols(tree, source, query, file_path),
         Language::Java => java::extract_symbols(tree, source, query, file_path),
         Language::Php => php::extract_symbols(tree, source, query, file_path),
+        Language::Bash => bash::extract_symbols(tree, source, query, file_path),
+        Language::Yaml | Language::Json | Language::Toml => {
+            config::extract_symbols(tree, source, query, file_path)
+        }
     }
 }
 
@@ -115,10 +200,13 @@ pub fn extract_imports(
     file_path: &str,
     language: Language,
 ) -> Vec<ImportInfo> {
-    match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::extract_imports(tree, source, query, file_path)
-        }
+    let imports = match language {
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::extract_imports(tree, source, query, file_path),
         Language::C => c_lang::extract_imports(tree, source, query, file_path),
         Language::Cpp => cpp::extract_imports(tree, source, query, file_path),
         Language::CSharp => csharp::extract_imports(tree, source, query, file_path),
@@ -127,7 +215,44 @@ pub fn extract_imports(
         Language::Go => go::extract_imports(tree, source, query, file_path),
         Language::Java => java::extract_imports(tree, source, query, file_path),
         Language::Php => php::extract_imports(tree, source, query, file_path),
+        Language::Bash => bash::extract_imports(tree, source, query, file_path),
+        Language::Yaml | Language::Json | Language::Toml => {
+            config::extract_imports(tree, source, query, file_path)
+        }
+    };
+    number_in_source_order(imports)
+}
+
+/// Sort `imports` by line (imports sharing a line keep the extractor's
+/// order), drop repeats and set each one's `order`. Extractors walk
+/// query matches or several passes, so their order alone isn't source
+/// order.
+fn number_in_source_order(mut imports: Vec<ImportInfo>) -> Vec<ImportInfo> {
+    imports.sort_by_key(|i| i.line);
+    let mut imports = dedup_imports(imports);
+    for (order, import) in imports.iter_mut().enumerate() {
+        import.order = order as u32;
     }
+    imports
+}
+
+/// Drop imports repeating an earlier one in the same file apart from
+/// its line (`#include "x.h"` twice, or `use a::b;` next to
+/// `use a::{b, c};`), so import counts reflect distinct imports.
+fn dedup_imports(imports: Vec<ImportInfo>) -> Vec<ImportInfo> {
+    let mut seen = HashSet::new();
+    imports
+        .into_iter()
+        .filter(|i| {
+            seen.insert((
+                i.module_specifier.clone(),
+                i.imported_name.clone(),
+                i.local_name.clone(),
+                i.kind.clone(),
+                i.is_type_only,
+            ))
+        })
+        .collect()
 }
 
 pub fn extract_comments(
@@ -138,9 +263,12 @@ pub fn extract_comments(
     language: Language,
 ) -> Vec<CommentInfo> {
     match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::extract_comments(tree, source, query, file_path)
-        }
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::extract_comments(tree, source, query, file_path),
         Language::C => c_lang::extract_comments(tree, source, query, file_path),
         Language::Cpp => cpp::extract_comments(tree, source, query, file_path),
         Language::CSharp => csharp::extract_comments(tree, source, query, file_path),
@@ -149,6 +277,10 @@ pub fn extract_comments(
         Language::Go => go::extract_comments(tree, source, query, file_path),
         Language::Java => java::extract_comments(tree, source, query, file_path),
         Language::Php => php::extract_comments(tree, source, query, file_path),
+        Language::Bash => bash::extract_comments(tree, source, query, file_path),
+        Language::Yaml | Language::Json | Language::Toml => {
+            config::extract_comments(tree, source, query, file_path)
+        }
     }
 }
 
@@ -166,9 +298,12 @@ pub fn extract_types(
 ) -> ExtractedTypes {
     match language {
         Language::Rust => rust_lang::extract_types(tree, source, file_path),
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::extract_types(tree, source, file_path)
-        }
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::extract_types(tree, source, file_path),
         Language::Python => python::extract_types(tree, source, file_path),
         Language::Go => go::extract_types(tree, source, file_path),
         Language::Java => java::extract_types(tree, source, file_path),
@@ -176,6 +311,10 @@ pub fn extract_types(
         Language::C => c_lang::extract_types(tree, source, file_path),
         Language::Cpp => cpp::extract_types(tree, source, file_path),
         Language::CSharp => csharp::extract_types(tree, source, file_path),
+        // Shell scripts and config files have no type annotations.
+        Language::Bash | Language::Yaml | Language::Json | Language::Toml => {
+            ExtractedTypes::default()
+        }
     }
 }
 
@@ -212,7 +351,12 @@ pub fn extract_attrs(
         Language::Rust => {
             bucket.rust = rust_lang::extract_attrs(tree, source, file_path, symbols);
         }
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => {
             bucket.typescript = typescript::extract_attrs(tree, source, file_path, symbols);
         }
         Language::Python => {
@@ -236,6 +380,7 @@ pub fn extract_attrs(
         Language::CSharp => {
             bucket.csharp = csharp::extract_attrs(tree, source, file_path, symbols);
         }
+        Language::Bash | Language::Yaml | Language::Json | Language::Toml => {}
     }
     bucket
 }
@@ -252,9 +397,12 @@ pub fn extract_references(
 ) -> ReferencesBucket {
     match language {
         Language::Rust => rust_lang::extract_references(tree, source, file_path, symbols),
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
-            typescript::extract_references(tree, source, file_path, symbols)
-        }
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => typescript::extract_references(tree, source, file_path, symbols),
         Language::Python => python::extract_references(tree, source, file_path, symbols),
         Language::Go => go::extract_references(tree, source, file_path, symbols),
         Language::Java => java::extract_references(tree, source, file_path, symbols),
@@ -262,6 +410,9 @@ pub fn extract_references(
         Language::C => c_lang::extract_references(tree, source, file_path, symbols),
         Language::Cpp => cpp::extract_references(tree, source, file_path, symbols),
         Language::CSharp => csharp::extract_references(tree, source, file_path, symbols),
+        Language::Bash | Language::Yaml | Language::Json | Language::Toml => {
+            ReferencesBucket::default()
+        }
     }
 }
 
@@ -286,7 +437,12 @@ pub fn resolve_import(
         return None;
     }
     match language {
-        Language::TypeScript | Language::Tsx | Language::JavaScript | Language::Jsx => {
+        Language::TypeScript
+        | Language::Tsx
+        | Language::JavaScript
+        | Language::Jsx
+        | Language::Vue
+        | Language::Svelte => {
             typescript::resolve_import(source_file, &import.module_specifier, known_files)
                 .map(GraphNode::File)
         }
@@ -314,5 +470,37 @@ pub fn resolve_import(
         Language::Cpp => cpp::resolve_import(source_file, &import.module_specifier, known_files)
             .map(GraphNode::File),
         Language::CSharp => None, // No file-level mapping without .csproj
+        Language::Bash => bash::resolve_import(source_file, &import.module_specifier, known_files)
+            .map(GraphNode::File),
+        Language::Yaml | Language::Json | Language::Toml => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_are_numbered_in_source_order() {
+        let source = "import os\nfrom pkg import thing\nimport sys\n";
+        let mut parser = crate::parser::create_parser(Language::Python).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        let query = compile_import_query(Language::Python).unwrap();
+        let imports = extract_imports(&tree, source.as_bytes(), &query, "a.py", Language::Python);
+        let ordered: Vec<(&str, u32)> = imports
+            .iter()
+            .map(|i| (i.module_specifier.as_str(), i.order))
+            .collect();
+        assert_eq!(ordered, vec![("os", 0), ("pkg", 1), ("sys", 2)]);
+
+        // Whatever order an extractor emits them in.
+        let mut shuffled = imports.clone();
+        shuffled.reverse();
+        let renumbered = number_in_source_order(shuffled);
+        let renumbered: Vec<(&str, u32)> = renumbered
+            .iter()
+            .map(|i| (i.module_specifier.as_str(), i.order))
+            .collect();
+        assert_eq!(renumbered, vec![("os", 0), ("pkg", 1), ("sys", 2)]);
     }
 }
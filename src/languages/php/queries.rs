@@ -91,6 +91,9 @@ const PHP_SYMBOL_QUERY: &str = r#"
 (enum_declaration
   name: (name) @name) @definition
 
+(enum_case
+  name: (name) @name) @definition
+
 (method_declaration
   name: (name) @name) @definition
 
@@ -272,6 +275,8 @@ pub fn extract_symbols(
             // no `final` propagation here — `final` lives in
             // `php_attrs.is_final` per docs/attrs-php.md).
             is_mutable: false,
+            is_deprecated: false,
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -304,6 +309,7 @@ fn determine_php_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
         "interface_declaration" => Some(SymbolKind::Interface),
         "trait_declaration" => Some(SymbolKind::Trait),
         "enum_declaration" => Some(SymbolKind::Enum),
+        "enum_case" => Some(SymbolKind::EnumMember),
         "method_declaration" => Some(SymbolKind::Method),
         "property_declaration" => Some(SymbolKind::Field),
         "const_declaration" => Some(SymbolKind::Constant),
@@ -384,6 +390,23 @@ fn is_exported_php(def_node: tree_sitter::Node, source: &[u8]) -> bool {
     }
 }
 
+/// `App\Models\` for a file opening with the statement form `namespace
+/// App\Models;`. The braced form encloses its declarations and already
+/// qualifies them as their parent.
+pub fn file_namespace_prefix(tree: &Tree, source: &[u8]) -> Option<String> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let namespace = root
+        .named_children(&mut cursor)
+        .find(|n| n.kind() == "namespace_definition" && n.child_by_field_name("body").is_none())?;
+    let name = namespace
+        .child_by_field_name("name")?
+        .utf8_text(source)
+        .ok()?;
+    let name = name.trim().trim_start_matches('\\');
+    (!name.is_empty()).then(|| format!("{name}\\"))
+}
+
 // ── Import extraction ──
 
 pub fn extract_imports(
@@ -433,7 +456,9 @@ pub fn extract_imports(
                     kind: "require".to_string(),
                     is_type_only: false,
                     line: (node.start_position().row + 1) as u32,
+                    order: 0,
                     is_external,
+                    external_kind: None,
                 });
             }
             continue;
@@ -455,7 +480,9 @@ pub fn extract_imports(
                     kind: "include".to_string(),
                     is_type_only: false,
                     line: (node.start_position().row + 1) as u32,
+                    order: 0,
                     is_external,
+                    external_kind: None,
                 });
             }
             continue;
@@ -469,6 +496,7 @@ fn parse_use_declaration(text: &str, file_path: &str, line: u32, imports: &mut V
     let text = text.trim();
     let text = text.strip_prefix("use").unwrap_or(text).trim();
     let text = text.strip_suffix(';').unwrap_or(text).trim();
+    let (kind, text) = split_use_kind(text, "use");
 
     if text.is_empty() {
         return;
@@ -485,6 +513,8 @@ fn parse_use_declaration(text: &str, file_path: &str, line: u32, imports: &mut V
             if item.is_empty() {
                 continue;
             }
+            // Mixed groups may tag individual items: `use A\{B, function c}`.
+            let (kind, item) = split_use_kind(item, kind);
 
             let (imported_name, local_name) = if let Some((name, alias)) = item.split_once(" as ") {
                 let name = name.trim();
@@ -506,10 +536,12 @@ fn parse_use_declaration(text: &str, file_path: &str, line: u32, imports: &mut V
                 module_specifier: module,
                 imported_name,
                 local_name,
-                kind: "use".to_string(),
+                kind: kind.to_string(),
                 is_type_only: false,
                 line,
+                order: 0,
                 is_external: true,
+                external_kind: None,
             });
         }
     } else {
@@ -527,14 +559,30 @@ fn parse_use_declaration(text: &str, file_path: &str, line: u32, imports: &mut V
             module_specifier: path.to_string(),
             imported_name,
             local_name,
-            kind: "use".to_string(),
+            kind: kind.to_string(),
             is_type_only: false,
             line,
+            order: 0,
             is_external: true,
+            external_kind: None,
         });
     }
 }
 
+/// Strip a leading `function` / `const` keyword from a `use` clause and
+/// return the matching import kind (`use_function` / `use_const`).
+/// Clauses without a keyword keep `default_kind`.
+fn split_use_kind<'t>(text: &'t str, default_kind: &'static str) -> (&'static str, &'t str) {
+    for (keyword, kind) in [("function", "use_function"), ("const", "use_const")] {
+        if let Some(rest) = text.strip_prefix(keyword)
+            && rest.starts_with(char::is_whitespace)
+        {
+            return (kind, rest.trim_start());
+        }
+    }
+    (default_kind, text)
+}
+
 fn extract_string_arg(text: &str) -> Option<String> {
     // Extract string from require('path') or require "path" or require_once 'path'
     // Find first quote character
@@ -858,6 +906,18 @@ mod tests {
         assert_eq!(s.unwrap().kind, SymbolKind::Enum);
     }
 
+    #[test]
+    fn extract_enum_cases() {
+        let syms =
+            parse_and_extract("<?php\nenum Suit: string { case Hearts = 'H'; case Spades = 'S'; }");
+        let cases: Vec<&str> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumMember)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(cases, vec!["Hearts", "Spades"]);
+    }
+
     #[test]
     fn extract_method() {
         let syms = parse_and_extract("<?php\nclass Foo { public function bar() {} }");
@@ -927,6 +987,43 @@ mod tests {
         assert_eq!(imports[1].imported_name, "Post");
     }
 
+    #[test]
+    fn use_function_kind() {
+        let imports = parse_and_extract_imports("<?php\nuse function App\\helper;");
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].module_specifier, "App\\helper");
+        assert_eq!(imports[0].imported_name, "helper");
+        assert_eq!(imports[0].kind, "use_function");
+    }
+
+    #[test]
+    fn use_const_kind() {
+        let imports = parse_and_extract_imports("<?php\nuse const App\\MAX as LIMIT;");
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].module_specifier, "App\\MAX");
+        assert_eq!(imports[0].local_name, "LIMIT");
+        assert_eq!(imports[0].kind, "use_const");
+    }
+
+    #[test]
+    fn grouped_use_function_kind() {
+        let imports = parse_and_extract_imports("<?php\nuse function App\\{a, b};");
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].module_specifier, "App\\a");
+        assert_eq!(imports[1].module_specifier, "App\\b");
+        assert!(imports.iter().all(|i| i.kind == "use_function"));
+    }
+
+    #[test]
+    fn grouped_use_mixed_kinds() {
+        let imports = parse_and_extract_imports(
+            "<?php\nuse App\\{Models\\User, function helper, const MAX};",
+        );
+        let kinds: Vec<&str> = imports.iter().map(|i| i.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["use", "use_function", "use_const"]);
+        assert_eq!(imports[1].module_specifier, "App\\helper");
+    }
+
     #[test]
     fn require_relative() {
         let imports = parse_and_extract_imports("<?php\nrequire './helpers.php';");
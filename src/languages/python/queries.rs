@@ -63,6 +63,60 @@ fn has_decorator(def_node: tree_sitter::Node, source: &[u8], targets: &[&str]) -
     false
 }
 
+/// True for a function or class decorated `@deprecated` (bare, dotted as
+/// `typing_extensions.deprecated`, or called with a message), or a
+/// function whose own body calls `warnings.warn(..., DeprecationWarning)`.
+fn is_deprecated_python(def_node: tree_sitter::Node, source: &[u8]) -> bool {
+    let def_node = match def_node.kind() {
+        "decorated_definition" => match def_node.child_by_field_name("definition") {
+            Some(definition) => definition,
+            None => return false,
+        },
+        _ => def_node,
+    };
+    match def_node.kind() {
+        "class_definition" => has_decorator(def_node, source, &["deprecated"]),
+        "function_definition" => {
+            has_decorator(def_node, source, &["deprecated"])
+                || def_node
+                    .child_by_field_name("body")
+                    .is_some_and(|body| warns_deprecation(body, source))
+        }
+        _ => false,
+    }
+}
+
+/// Whether `node` contains a `warn(...)` / `warnings.warn(...)` call
+/// passing `DeprecationWarning` (or `PendingDeprecationWarning`),
+/// without descending into nested functions or classes.
+fn warns_deprecation(node: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "function_definition" | "class_definition" | "decorated_definition" => continue,
+            "call" => {
+                let callee = child
+                    .child_by_field_name("function")
+                    .and_then(|f| f.utf8_text(source).ok())
+                    .unwrap_or("");
+                let args = child
+                    .child_by_field_name("arguments")
+                    .and_then(|a| a.utf8_text(source).ok())
+                    .unwrap_or("");
+                if callee.rsplit('.').next() == Some("warn") && args.contains("DeprecationWarning")
+                {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        if warns_deprecation(child, source) {
+            return true;
+        }
+    }
+    false
+}
+
 // ── Symbol queries ──
 
 const PYTHON_SYMBOL_QUERY: &str = r#"
@@ -214,6 +268,10 @@ pub fn extract_symbols(
     // deterministic regardless of tree-sitter match order.
     candidates.sort_by_key(|(n, _)| n.start_byte());
 
+    // First pass: a module-level `__all__` decides which module-level
+    // names are exported; nested symbols keep the underscore heuristic.
+    let dunder_all = module_dunder_all(tree.root_node(), source);
+
     for (def_node, name) in candidates {
         let kind = determine_python_kind(def_node, &name);
         let Some(kind) = kind else { continue };
@@ -232,7 +290,10 @@ pub fn extract_symbols(
             }
         }
 
-        let is_exported = !name.starts_with('_');
+        let is_exported = match &dunder_all {
+            Some(all) if is_module_level(def_node) => all.contains(&name),
+            _ => !name.starts_with('_'),
+        };
 
         let is_async = is_async_python(def_node);
         // `@staticmethod` / `@abstractmethod` are only meaningful on
@@ -265,6 +326,8 @@ pub fn extract_symbols(
             is_abstract,
             // Python has no symbol-level mutability marker.
             is_mutable: false,
+            is_deprecated: is_deprecated_python(def_node, source),
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -272,6 +335,65 @@ pub fn extract_symbols(
     symbols
 }
 
+/// Names listed by module-level `__all__ = [...]` / `__all__ += [...]`
+/// assignments (lists or tuples of string literals), or `None` when the
+/// module never assigns `__all__`.
+fn module_dunder_all(root: tree_sitter::Node, source: &[u8]) -> Option<HashSet<String>> {
+    let mut names: Option<HashSet<String>> = None;
+    let mut cursor = root.walk();
+    for stmt in root.named_children(&mut cursor) {
+        if stmt.kind() != "expression_statement" {
+            continue;
+        }
+        let Some(assign) = stmt.named_child(0) else {
+            continue;
+        };
+        if !matches!(assign.kind(), "assignment" | "augmented_assignment") {
+            continue;
+        }
+        let is_all = assign
+            .child_by_field_name("left")
+            .is_some_and(|l| l.utf8_text(source) == Ok("__all__"));
+        let Some(right) = assign.child_by_field_name("right") else {
+            continue;
+        };
+        if !is_all || !matches!(right.kind(), "list" | "tuple") {
+            continue;
+        }
+        let listed = names.get_or_insert_with(HashSet::new);
+        let mut items = right.walk();
+        for item in right.named_children(&mut items) {
+            if item.kind() == "string" {
+                listed.insert(python_string_text(item, source));
+            }
+        }
+    }
+    names
+}
+
+/// Contents of a string literal without its prefix and quotes.
+fn python_string_text(node: tree_sitter::Node, source: &[u8]) -> String {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|c| c.kind() == "string_content")
+        .filter_map(|c| c.utf8_text(source).ok())
+        .collect()
+}
+
+/// True when `node` is a statement of the module itself (possibly
+/// wrapped in an `expression_statement` or `decorated_definition`).
+fn is_module_level(node: tree_sitter::Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        match parent.kind() {
+            "expression_statement" | "decorated_definition" => current = parent.parent(),
+            "module" => return true,
+            _ => return false,
+        }
+    }
+    false
+}
+
 /// Walk up from `node` to the nearest enclosing `function_definition` or
 /// `lambda` node. Returns `None` if `node` is at module scope. Used to
 /// scope-key the "first assignment" dedupe.
@@ -373,6 +495,28 @@ fn is_inside_class(node: tree_sitter::Node) -> bool {
 
 // ── Import extraction ──
 
+/// The import sits in the body of an `if TYPE_CHECKING:` (or
+/// `if typing.TYPE_CHECKING:`) block, so it only exists for type
+/// checkers, like a TS `import type`. `else`/`elif` branches don't count.
+fn is_type_checking_only(node: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut child = node;
+    while let Some(parent) = child.parent() {
+        if parent.kind() == "if_statement"
+            && parent
+                .child_by_field_name("consequence")
+                .is_some_and(|body| body.id() == child.id())
+            && parent
+                .child_by_field_name("condition")
+                .and_then(|c| c.utf8_text(source).ok())
+                .is_some_and(|c| c == "TYPE_CHECKING" || c.ends_with(".TYPE_CHECKING"))
+        {
+            return true;
+        }
+        child = parent;
+    }
+    false
+}
+
 pub fn extract_imports(
     tree: &Tree,
     source: &[u8],
@@ -395,6 +539,7 @@ pub fn extract_imports(
 
         let import_node = import_cap.node;
         let line = import_node.start_position().row as u32 + 1;
+        let is_type_only = is_type_checking_only(import_node, source);
 
         match import_node.kind() {
             "import_statement" => {
@@ -414,9 +559,11 @@ pub fn extract_imports(
                         imported_name: imported_name.clone(),
                         local_name: imported_name,
                         kind: "import".to_string(),
-                        is_type_only: false,
+                        is_type_only,
                         line,
+                        order: 0,
                         is_external: true,
+                        external_kind: None,
                     });
                 }
             }
@@ -444,9 +591,11 @@ pub fn extract_imports(
                                     imported_name: name.clone(),
                                     local_name: name,
                                     kind: "from".to_string(),
-                                    is_type_only: false,
+                                    is_type_only,
                                     line,
+                                    order: 0,
                                     is_external: !is_internal,
+                                    external_kind: None,
                                 });
                             }
                         }
@@ -468,9 +617,11 @@ pub fn extract_imports(
                                         imported_name: name,
                                         local_name: local,
                                         kind: "from".to_string(),
-                                        is_type_only: false,
+                                        is_type_only,
                                         line,
+                                        order: 0,
                                         is_external: !is_internal,
+                                        external_kind: None,
                                     });
                                 }
                             }
@@ -483,9 +634,11 @@ pub fn extract_imports(
                                 imported_name: "*".to_string(),
                                 local_name: "*".to_string(),
                                 kind: "from".to_string(),
-                                is_type_only: false,
+                                is_type_only,
                                 line,
+                                order: 0,
                                 is_external: !is_internal,
+                                external_kind: None,
                             });
                         }
                         "import" => {
@@ -900,6 +1053,37 @@ mod tests {
         assert!(!syms[0].is_exported);
     }
 
+    #[test]
+    fn dunder_all_limits_module_exports() {
+        let syms = parse_and_extract(
+            "__all__ = [\"a\"]\n\ndef a():\n    pass\n\ndef b():\n    pass\n\nclass C:\n    def method(self):\n        pass\n",
+        );
+        let exported = |name: &str| {
+            syms.iter()
+                .find(|s| s.name == name)
+                .unwrap_or_else(|| panic!("{name} symbol"))
+                .is_exported
+        };
+        assert!(exported("a"));
+        assert!(!exported("b"));
+        assert!(!exported("C"));
+        // Class members keep the underscore heuristic.
+        assert!(exported("method"));
+    }
+
+    #[test]
+    fn dunder_all_accepts_tuples_and_augmented_assignment() {
+        let syms = parse_and_extract(
+            "__all__ = (\"a\",)\n__all__ += ['_b']\n\ndef a(): pass\ndef _b(): pass\ndef c(): pass\n",
+        );
+        let exported: Vec<&str> = syms
+            .iter()
+            .filter(|s| s.is_exported && s.kind == SymbolKind::Function)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(exported, vec!["a", "_b"]);
+    }
+
     #[test]
     fn extract_class() {
         let syms = parse_and_extract("class Foo:\n    pass");
@@ -957,6 +1141,25 @@ mod tests {
         assert!(!imports[0].is_external);
     }
 
+    #[test]
+    fn type_checking_imports_are_type_only() {
+        let imports = parse_and_extract_imports(
+            "from typing import TYPE_CHECKING\nimport typing\n\nif TYPE_CHECKING:\n    from .models import User\nelse:\n    User = None\n\nif typing.TYPE_CHECKING:\n    import collections.abc\n\nif DEBUG:\n    import pdb\n",
+        );
+        let type_only = |name: &str| {
+            imports
+                .iter()
+                .find(|i| i.imported_name == name)
+                .unwrap_or_else(|| panic!("missing {name}"))
+                .is_type_only
+        };
+        assert!(!type_only("TYPE_CHECKING"));
+        assert!(!type_only("typing"));
+        assert!(type_only("User"));
+        assert!(type_only("abc"));
+        assert!(!type_only("pdb"));
+    }
+
     #[test]
     fn line_comment() {
         let comments = parse_and_extract_comments("# This is a comment");
@@ -974,6 +1177,19 @@ mod tests {
         assert_eq!(doc.unwrap().associated_symbol.as_deref(), Some("foo"));
     }
 
+    #[test]
+    fn comment_over_decorated_function() {
+        let comments = parse_and_extract_comments(
+            "# Cached lookup\n@functools.cache\ndef lookup():\n    pass",
+        );
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].associated_symbol.as_deref(), Some("lookup"));
+        assert_eq!(
+            comments[0].associated_symbol_kind.as_deref(),
+            Some("function")
+        );
+    }
+
     #[test]
     fn empty_source_no_symbols() {
         let syms = parse_and_extract("");
@@ -52,6 +52,9 @@ const RUST_SYMBOL_QUERY: &str = r#"
 
 (field_declaration
   name: (field_identifier) @name) @definition
+
+(enum_variant
+  name: (identifier) @name) @definition
 "#;
 
 // ── Import queries ──
@@ -59,6 +62,9 @@ const RUST_SYMBOL_QUERY: &str = r#"
 const RUST_IMPORT_QUERY: &str = r#"
 (use_declaration
   argument: (_) @path) @import
+
+(extern_crate_declaration
+  name: (_) @crate) @import
 "#;
 
 // ── Comment queries ──
@@ -154,6 +160,8 @@ pub fn extract_symbols(
             // downstream query needs the distinction.
             is_abstract: false,
             is_mutable,
+            is_deprecated: is_deprecated_rust(def_node, source),
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -183,6 +191,7 @@ fn determine_rust_kind(def_node: tree_sitter::Node) -> Option<SymbolKind> {
         "parameter" => Some(SymbolKind::Parameter),
         "let_declaration" => Some(SymbolKind::Variable),
         "field_declaration" => Some(SymbolKind::Field),
+        "enum_variant" => Some(SymbolKind::EnumMember),
         _ => None,
     }
 }
@@ -215,6 +224,15 @@ fn is_inside_impl_or_trait(node: tree_sitter::Node) -> bool {
 fn visibility_rust(def_node: tree_sitter::Node, source: &[u8]) -> SymbolVisibility {
     match def_node.kind() {
         "parameter" | "let_declaration" => return SymbolVisibility::Private,
+        // Variants can't carry a modifier; they are as visible as their enum.
+        "enum_variant" => {
+            return def_node
+                .parent()
+                .and_then(|list| list.parent())
+                .filter(|item| item.kind() == "enum_item")
+                .map(|item| visibility_rust(item, source))
+                .unwrap_or(SymbolVisibility::Private);
+        }
         _ => {}
     }
     let mut cursor = def_node.walk();
@@ -275,6 +293,69 @@ fn is_mutable_rust(def_node: tree_sitter::Node) -> bool {
     false
 }
 
+/// Whether the attributes directly above `def_node` include
+/// `#[deprecated]`, bare or with a note (`#[deprecated(since = "1.2")]`,
+/// `#[deprecated = "..."]`).
+fn is_deprecated_rust(def_node: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut prev = def_node.prev_named_sibling();
+    while let Some(node) = prev {
+        match node.kind() {
+            "attribute_item" => {
+                let text = node.utf8_text(source).unwrap_or("");
+                let body = text.trim_start_matches("#[").trim_start();
+                let path = body.split(['(', '=', ']']).next().unwrap_or("").trim();
+                if path == "deprecated" {
+                    return true;
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => return false,
+        }
+        prev = node.prev_named_sibling();
+    }
+    false
+}
+
+/// Byte ranges of `#[cfg(test)] mod … { … }` items, nested ones
+/// included. Symbols inside are test code even in a non-test file.
+pub fn cfg_test_ranges(tree: &Tree, source: &[u8]) -> Vec<(u32, u32)> {
+    let mut ranges = Vec::new();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "mod_item" && has_cfg_test_attribute(node, source) {
+            ranges.push((node.start_byte() as u32, node.end_byte() as u32));
+            continue;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    ranges
+}
+
+/// Whether the attributes directly above `item` include `#[cfg(test)]`.
+fn has_cfg_test_attribute(item: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut prev = item.prev_named_sibling();
+    while let Some(node) = prev {
+        match node.kind() {
+            "attribute_item" => {
+                let text: String = node
+                    .utf8_text(source)
+                    .unwrap_or("")
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect();
+                if text == "#[cfg(test)]" {
+                    return true;
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => return false,
+        }
+        prev = node.prev_named_sibling();
+    }
+    false
+}
+
 // ── Import extraction ──
 
 pub fn extract_imports(
@@ -287,11 +368,18 @@ pub fn extract_imports(
     let mut matches = cursor.matches(query, tree.root_node(), source);
 
     let path_idx = query.capture_index_for_name("path");
+    let crate_idx = query.capture_index_for_name("crate");
     let import_idx = query.capture_index_for_name("import");
 
     let mut imports = Vec::new();
 
     while let Some(m) = matches.next() {
+        let crate_cap = crate_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
+        if let Some(crate_cap) = crate_cap {
+            imports.push(extern_crate_import(crate_cap.node, source, file_path));
+            continue;
+        }
+
         let path_cap = path_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
         let import_cap = import_idx.and_then(|idx| m.captures.iter().find(|c| c.index == idx));
 
@@ -308,81 +396,135 @@ pub fn extract_imports(
             continue;
         }
 
+        // `pub use` (any visibility) re-exports the path from this module.
+        let mut walk = import_node.walk();
+        let kind = if import_node
+            .children(&mut walk)
+            .any(|c| c.kind() == "visibility_modifier")
+        {
+            "re_export"
+        } else {
+            "use"
+        };
+
         // Extract individual imports from the use path
-        extract_use_imports(&path_text, file_path, line, &mut imports);
+        extract_use_imports(&path_text, file_path, line, kind, &mut imports);
     }
 
     imports
 }
 
-fn extract_use_imports(path_text: &str, file_path: &str, line: u32, imports: &mut Vec<ImportInfo>) {
-    let is_internal = path_text.starts_with("crate::")
-        || path_text.starts_with("self::")
-        || path_text.starts_with("super::");
-
-    // Handle grouped imports: use std::collections::{HashMap, HashSet}
-    if let Some(brace_start) = path_text.find('{') {
-        let prefix = &path_text[..brace_start];
-        let brace_end = path_text.rfind('}').unwrap_or(path_text.len());
-        let inner = &path_text[brace_start + 1..brace_end];
-
-        for item in inner.split(',') {
-            let item = item.trim();
-            if item.is_empty() {
-                continue;
-            }
-
-            let (imported_name, local_name) = if let Some((name, alias)) = item.split_once(" as ") {
-                (name.trim().to_string(), alias.trim().to_string())
-            } else {
-                let name = item.split("::").last().unwrap_or(item).trim().to_string();
-                (name.clone(), name)
-            };
-
-            let module = format!("{}{}", prefix, item).trim().to_string();
-
-            imports.push(ImportInfo {
-                source_file: file_path.to_string(),
-                module_specifier: module,
-                imported_name,
-                local_name,
-                kind: "use".to_string(),
-                is_type_only: false,
-                line,
-                is_external: !is_internal,
-            });
-        }
-    } else {
-        // Simple path: use std::collections::HashMap or use std::collections::HashMap as Map
-        let (module, imported_name, local_name) =
-            if let Some((path, alias)) = path_text.split_once(" as ") {
-                let name = path.split("::").last().unwrap_or(path).trim().to_string();
-                (path.trim().to_string(), name, alias.trim().to_string())
-            } else if path_text.ends_with("::*") {
-                (path_text.to_string(), "*".to_string(), "*".to_string())
-            } else {
-                let name = path_text
-                    .split("::")
-                    .last()
-                    .unwrap_or(path_text)
-                    .trim()
-                    .to_string();
-                (path_text.to_string(), name.clone(), name)
-            };
+/// `extern crate name;` or `extern crate name as alias;`. Only
+/// `extern crate self` names the current crate.
+fn extern_crate_import(name_node: tree_sitter::Node, source: &[u8], file_path: &str) -> ImportInfo {
+    let name = name_node.utf8_text(source).unwrap_or("").to_string();
+    let local_name = name_node
+        .parent()
+        .and_then(|decl| decl.child_by_field_name("alias"))
+        .and_then(|alias| alias.utf8_text(source).ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| name.clone());
+    ImportInfo {
+        source_file: file_path.to_string(),
+        module_specifier: name.clone(),
+        is_external: name != "self",
+        imported_name: name,
+        local_name,
+        kind: "extern_crate".to_string(),
+        is_type_only: false,
+        line: name_node.start_position().row as u32 + 1,
+        order: 0,
+        order: 0,
+        external_kind: None,
+    }
+}
 
+fn extract_use_imports(
+    path_text: &str,
+    file_path: &str,
+    line: u32,
+    kind: &str,
+    imports: &mut Vec<ImportInfo>,
+) {
+    let mut paths = Vec::new();
+    expand_use_tree("", path_text, &mut paths);
+
+    for (module, alias) in paths {
+        let is_internal = module == "crate"
+            || module == "self"
+            || module == "super"
+            || module.starts_with("crate::")
+            || module.starts_with("self::")
+            || module.starts_with("super::");
+        let imported_name = module.rsplit("::").next().unwrap_or(&module).to_string();
+        let local_name = alias.unwrap_or_else(|| imported_name.clone());
         imports.push(ImportInfo {
             source_file: file_path.to_string(),
             module_specifier: module,
             imported_name,
             local_name,
-            kind: "use".to_string(),
+            kind: kind.to_string(),
             is_type_only: false,
             line,
+            order: 0,
             is_external: !is_internal,
+            external_kind: None,
         });
     }
 }
 
+/// Flatten a use tree into `(full path, alias)` pairs, so the grouped
+/// `use a::{b, c::{d as e, self}}` yields the same specifiers as the
+/// separate `use a::b; use a::c::d as e; use a::c;`.
+fn expand_use_tree(prefix: &str, tree: &str, out: &mut Vec<(String, Option<String>)>) {
+    let tree = tree.trim();
+    if tree.is_empty() {
+        return;
+    }
+    if let Some(open) = tree.find('{') {
+        let head: String = tree[..open].split_whitespace().collect();
+        let close = tree.rfind('}').unwrap_or(tree.len());
+        let prefix = format!("{prefix}{head}");
+        for item in split_top_level(&tree[open + 1..close.max(open + 1)]) {
+            expand_use_tree(&prefix, item, out);
+        }
+        return;
+    }
+    let (path, alias) = match tree.split_once(" as ") {
+        Some((path, alias)) => (path.trim(), Some(alias.trim().to_string())),
+        None => (tree, None),
+    };
+    let path: String = path.split_whitespace().collect();
+    let full = if path == "self" {
+        prefix.trim_end_matches("::").to_string()
+    } else {
+        format!("{prefix}{path}")
+    };
+    if !full.is_empty() {
+        out.push((full, alias));
+    }
+}
+
+/// Split a use-group body on commas outside nested braces.
+fn split_top_level(inner: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&inner[start..]);
+    items
+}
+
 // ── Comment extraction ──
 
 pub fn extract_comments(
@@ -450,7 +592,14 @@ fn find_associated_symbol(
     comment_node: tree_sitter::Node,
     source: &[u8],
 ) -> (Option<String>, Option<String>) {
-    let sibling = comment_node.next_named_sibling();
+    // Outer attributes (`#[derive(..)]`, `#[cfg(..)]`) sit between a doc
+    // comment and the item it documents; step over them.
+    let mut sibling = comment_node.next_named_sibling();
+    while let Some(node) = sibling
+        && node.kind() == "attribute_item"
+    {
+        sibling = node.next_named_sibling();
+    }
     let Some(sibling) = sibling else {
         return (None, None);
     };
@@ -714,6 +863,18 @@ mod tests {
         extract_symbols(&tree, source.as_bytes(), &query, "test.rs")
     }
 
+    #[test]
+    fn deprecated_attribute_marks_the_symbol() {
+        let syms = parse_and_extract(
+            "#[deprecated]\npub fn old() {}\n\n#[deprecated(since = \"1.2\", note = \"use New\")]\n\
+             // kept for callers\n#[derive(Debug)]\npub struct Legacy;\n\n#[inline]\npub fn current() {}\n",
+        );
+        let deprecated = |name: &str| syms.iter().find(|s| s.name == name).unwrap().is_deprecated;
+        assert!(deprecated("old"));
+        assert!(deprecated("Legacy"));
+        assert!(!deprecated("current"));
+    }
+
     fn parse_and_extract_imports(source: &str) -> Vec<ImportInfo> {
         let mut parser = create_parser(Language::Rust).expect("create parser");
         let tree = parser.parse(source.as_bytes(), None).expect("parse");
@@ -766,6 +927,23 @@ mod tests {
         assert_eq!(s.unwrap().kind, SymbolKind::Enum);
     }
 
+    #[test]
+    fn extract_enum_variants() {
+        let syms = parse_and_extract("pub enum Shape { Dot, Circle(f64), Rect { w: f64 } }");
+        let variants: Vec<&SymbolInfo> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumMember)
+            .collect();
+        let names: Vec<&str> = variants.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Dot", "Circle", "Rect"]);
+        assert!(
+            variants
+                .iter()
+                .all(|s| s.visibility == SymbolVisibility::Public && s.is_exported),
+            "variants inherit the enum's `pub`"
+        );
+    }
+
     #[test]
     fn extract_trait() {
         let syms = parse_and_extract("pub trait Display { fn fmt(&self); }");
@@ -885,6 +1063,70 @@ mod tests {
         assert_eq!(imports[0].local_name, "Map");
     }
 
+    #[test]
+    fn pub_use_is_a_re_export() {
+        let imports = parse_and_extract_imports("pub use crate::x::Y;\nuse crate::x::Z;");
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].module_specifier, "crate::x::Y");
+        assert_eq!(imports[0].kind, "re_export");
+        assert!(!imports[0].is_external);
+        assert_eq!(imports[1].kind, "use");
+    }
+
+    #[test]
+    fn extern_crate_is_an_external_import() {
+        let imports = parse_and_extract_imports("extern crate serde;\nextern crate rand as r;");
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].module_specifier, "serde");
+        assert_eq!(imports[0].imported_name, "serde");
+        assert_eq!(imports[0].local_name, "serde");
+        assert_eq!(imports[0].kind, "extern_crate");
+        assert!(imports[0].is_external);
+        assert_eq!(imports[1].local_name, "r");
+    }
+
+    #[test]
+    fn grouped_and_simple_imports_share_canonical_specifiers() {
+        let grouped = parse_and_extract_imports("use a::{b, c::{d as e, self}, f::*};");
+        let simple =
+            parse_and_extract_imports("use a::b;\nuse a::c::d as e;\nuse a::c;\nuse a::f::*;");
+        let specs = |imports: &[ImportInfo]| -> Vec<(String, String, String)> {
+            imports
+                .iter()
+                .map(|i| {
+                    (
+                        i.module_specifier.clone(),
+                        i.imported_name.clone(),
+                        i.local_name.clone(),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(specs(&grouped), specs(&simple));
+        assert_eq!(
+            grouped
+                .iter()
+                .map(|i| i.module_specifier.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a::b", "a::c::d", "a::c", "a::f::*"]
+        );
+    }
+
+    #[test]
+    fn multiline_group_import() {
+        let imports =
+            parse_and_extract_imports("use crate::models::{\n    SymbolInfo,\n    SymbolKind,\n};");
+        let specs: Vec<&str> = imports
+            .iter()
+            .map(|i| i.module_specifier.as_str())
+            .collect();
+        assert_eq!(
+            specs,
+            vec!["crate::models::SymbolInfo", "crate::models::SymbolKind"]
+        );
+        assert!(imports.iter().all(|i| !i.is_external));
+    }
+
     #[test]
     fn doc_comment() {
         let comments = parse_and_extract_comments("/// This is a doc comment\nfn foo() {}");
@@ -893,6 +1135,19 @@ mod tests {
         assert_eq!(comments[0].associated_symbol.as_deref(), Some("foo"));
     }
 
+    #[test]
+    fn doc_comment_skips_attributes() {
+        let comments = parse_and_extract_comments(
+            "/// A point\n#[derive(Debug)]\n#[allow(dead_code)]\nstruct Point { x: i32 }",
+        );
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].associated_symbol.as_deref(), Some("Point"));
+        assert_eq!(
+            comments[0].associated_symbol_kind.as_deref(),
+            Some("struct")
+        );
+    }
+
     #[test]
     fn inner_doc_comment() {
         let comments = parse_and_extract_comments("//! Module doc");
@@ -8,9 +8,12 @@
 //! - `type_parameters` — declared type-parameter names (source order)
 //!   from a `type_parameters` AST node on
 //!   function/method/class/interface/type_alias declarations.
+//! - `decorators` — `@...` decorators on a class, method or field, as
+//!   expressions without the `@` (`Component({ selector: 'x' })`).
 //!
 //! We emit one row per TS/JS symbol (mirror Rust pilot). JavaScript
-//! files emit defaults only — JS has no `readonly`/`?`/generics syntax.
+//! files emit defaults only — JS has no `readonly`/`?`/generics syntax —
+//! except for decorators, which JS shares.
 //!
 //! Symbol ID convention: ADR-0002 (`path|line|col|name|kind`).
 
@@ -31,16 +34,20 @@ pub fn extract_attrs(
             "{}|{}|{}|{}|{}",
             file_path, sym.start_line, sym.start_column, sym.name, sym.kind
         );
+        let node = find_node_at(tree.root_node(), sym.start_byte, sym.end_byte);
+        let decorators = node
+            .map(|n| collect_decorators(n, source))
+            .unwrap_or_default();
         if is_js {
             out.push(TypescriptAttrsRow {
                 symbol_id,
                 is_readonly: false,
                 is_optional: false,
                 type_parameters: Vec::new(),
+                decorators,
             });
             continue;
         }
-        let node = find_node_at(tree.root_node(), sym.start_byte, sym.end_byte);
         let is_readonly = node.map(|n| node_is_readonly(n, sym)).unwrap_or(false);
         let is_optional = node.map(|n| node_is_optional(n, sym)).unwrap_or(false);
         let type_parameters = node
@@ -51,6 +58,7 @@ pub fn extract_attrs(
             is_readonly,
             is_optional,
             type_parameters,
+            decorators,
         });
     }
     out
@@ -149,6 +157,46 @@ fn parse_type_parameters(tp: Node, source: &[u8]) -> Vec<String> {
     out
 }
 
+/// Decorators attached to `node`, in source order, rendered like Python's
+/// (no leading `@`, whitespace collapsed, call arguments kept). A class
+/// or field holds its decorators as children; a method's are the
+/// `decorator` siblings just before it in the class body; and an
+/// exported class's may sit on the enclosing `export_statement`.
+fn collect_decorators(node: Node, source: &[u8]) -> Vec<String> {
+    let mut nodes = Vec::new();
+    let mut sibling = node.prev_named_sibling();
+    while let Some(prev) = sibling
+        && prev.kind() == "decorator"
+    {
+        nodes.push(prev);
+        sibling = prev.prev_named_sibling();
+    }
+    nodes.reverse();
+    if let Some(parent) = node.parent()
+        && parent.kind() == "export_statement"
+    {
+        let mut cursor = parent.walk();
+        nodes.extend(
+            parent
+                .named_children(&mut cursor)
+                .filter(|c| c.kind() == "decorator"),
+        );
+    }
+    let mut cursor = node.walk();
+    nodes.extend(
+        node.named_children(&mut cursor)
+            .filter(|c| c.kind() == "decorator"),
+    );
+    nodes
+        .into_iter()
+        .filter_map(|d| {
+            let text = d.utf8_text(source).ok()?.trim().trim_start_matches('@');
+            let normalised = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!normalised.is_empty()).then_some(normalised)
+        })
+        .collect()
+}
+
 fn has_readonly_modifier(node: Node) -> bool {
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
@@ -355,4 +403,30 @@ mod tests {
             .expect("plain row");
         assert!(r.type_parameters.is_empty());
     }
+
+    #[test]
+    fn decorators_on_class_and_method() {
+        let rows = run(
+            "@Component({\n  selector: 'app-foo',\n})\nclass Foo {\n  @HostListener('click')\n  onClick() {}\n  plain() {}\n}\n@Injectable()\nexport class Svc {}\n",
+            "src/foo.ts",
+            Language::TypeScript,
+        );
+        let decorators = |suffix: &str| {
+            rows.iter()
+                .find(|r| r.symbol_id.ends_with(suffix))
+                .unwrap_or_else(|| panic!("{suffix} row"))
+                .decorators
+                .clone()
+        };
+        assert_eq!(
+            decorators("|Foo|class"),
+            vec!["Component({ selector: 'app-foo', })".to_string()]
+        );
+        assert_eq!(
+            decorators("|onClick|method"),
+            vec!["HostListener('click')".to_string()]
+        );
+        assert!(decorators("|plain|method").is_empty());
+        assert_eq!(decorators("|Svc|class"), vec!["Injectable()".to_string()]);
+    }
 }
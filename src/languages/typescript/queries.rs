@@ -83,6 +83,29 @@ fn has_keyword_child(node: tree_sitter::Node, keyword: &str) -> bool {
     false
 }
 
+/// True when the doc comment (`/** ... */`) directly above `def_node`
+/// has an `@deprecated` tag. A variable declarator's comment sits above
+/// its `const`/`let` declaration, and an exported declaration's above
+/// the `export` keyword.
+fn has_deprecated_doc(def_node: tree_sitter::Node, source: &[u8]) -> bool {
+    let mut anchor = def_node;
+    while let Some(parent) = anchor.parent()
+        && matches!(
+            parent.kind(),
+            "variable_declarator"
+                | "lexical_declaration"
+                | "variable_declaration"
+                | "export_statement"
+        )
+    {
+        anchor = parent;
+    }
+    anchor.prev_named_sibling().is_some_and(|prev| {
+        let text = prev.utf8_text(source).unwrap_or("");
+        prev.kind() == "comment" && text.starts_with("/**") && text.contains("@deprecated")
+    })
+}
+
 /// True if the symbol carries the `async` keyword. Checks the def node
 /// itself (function/method declarations) and, for variable bindings,
 /// the bound value (e.g. `const f = async () => ...`).
@@ -163,6 +186,13 @@ const TS_SYMBOL_QUERY: &str = r#"
 (enum_declaration
   name: (identifier) @name) @definition
 
+(enum_body
+  (property_identifier) @name @definition)
+
+(enum_body
+  (enum_assignment
+    name: (property_identifier) @name) @definition)
+
 (required_parameter
   pattern: (identifier) @name) @parameter
 
@@ -358,9 +388,24 @@ pub fn extract_symbols(
             let Some(def_cap) = def_cap else { continue };
             let def_node = def_cap.node;
             let kind = determine_kind(def_node.kind(), value_cap.map(|c| c.node.kind()));
-            let Some(kind) = kind else { continue };
-            // Check if parent is an export_statement
-            let is_exported = def_node
+            let Some(mut kind) = kind else { continue };
+            if matches!(language, Language::Tsx | Language::Jsx)
+                && matches!(kind, SymbolKind::Function | SymbolKind::ArrowFunction)
+                && is_component(&name, value_cap.map_or(def_node, |c| c.node))
+            {
+                kind = SymbolKind::Component;
+            }
+            // Check if parent is an export_statement. Enum members are
+            // exported along with their enum.
+            let export_anchor = if kind == SymbolKind::EnumMember {
+                def_node
+                    .parent()
+                    .and_then(|body| body.parent())
+                    .unwrap_or(def_node)
+            } else {
+                def_node
+            };
+            let is_exported = export_anchor
                 .parent()
                 .is_some_and(|p| p.kind() == "export_statement");
             (def_node, kind, is_exported)
@@ -389,6 +434,8 @@ pub fn extract_symbols(
             is_abstract,
             // TS `readonly` lives in `typescript_attrs.is_readonly`, not here.
             is_mutable: false,
+            is_deprecated: has_deprecated_doc(def_node, source),
+            loc: None,
         };
         symbols.push(symbol);
     }
@@ -494,6 +541,8 @@ pub fn extract_symbols(
                         is_static: false,
                         is_abstract: false,
                         is_mutable: false,
+                        is_deprecated: false,
+                        loc: None,
                     });
                 }
             }
@@ -561,6 +610,8 @@ fn determine_kind(def_kind: &str, value_kind: Option<&str>) -> Option<SymbolKind
         "interface_declaration" => Some(SymbolKind::Interface),
         "type_alias_declaration" => Some(SymbolKind::TypeAlias),
         "enum_declaration" => Some(SymbolKind::Enum),
+        // Bare `A` and initialised `B = 2` members of an `enum_body`.
+        "property_identifier" | "enum_assignment" => Some(SymbolKind::EnumMember),
         "public_field_definition" | "property_signature" => Some(SymbolKind::Field),
         "lexical_declaration" | "variable_declaration" => {
             if let Some(vk) = value_kind {
@@ -577,6 +628,23 @@ fn determine_kind(def_kind: &str, value_kind: Option<&str>) -> Option<SymbolKind
     }
 }
 
+/// React component: a PascalCase name bound to a function whose body
+/// renders JSX. Helpers returning plain values stay functions.
+pub(crate) fn is_component(name: &str, function: tree_sitter::Node) -> bool {
+    if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return false;
+    }
+    let mut stack = vec![function];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "jsx_element" | "jsx_self_closing_element") {
+            return true;
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    false
+}
+
 // ── Import extraction ──
 
 pub fn extract_imports(
@@ -643,7 +711,9 @@ pub fn extract_imports(
                     kind: "static".to_string(),
                     is_type_only,
                     line,
+                    order: 0,
                     is_external,
+                    external_kind: external_kind(&module_specifier),
                 });
             } else {
                 for (imported, local, binding_type_only) in extracted {
@@ -655,7 +725,9 @@ pub fn extract_imports(
                         kind: "static".to_string(),
                         is_type_only: is_type_only || binding_type_only,
                         line,
+                        order: 0,
                         is_external,
+                        external_kind: external_kind(&module_specifier),
                     });
                 }
             }
@@ -677,7 +749,9 @@ pub fn extract_imports(
                     kind: "re_export".to_string(),
                     is_type_only: has_type_keyword(reexport_node),
                     line,
+                    order: 0,
                     is_external,
+                    external_kind: external_kind(&module_specifier),
                 });
             } else {
                 for (imported, local) in extracted {
@@ -689,7 +763,9 @@ pub fn extract_imports(
                         kind: "re_export".to_string(),
                         is_type_only: has_type_keyword(reexport_node),
                         line,
+                        order: 0,
                         is_external,
+                        external_kind: external_kind(&module_specifier),
                     });
                 }
             }
@@ -706,7 +782,9 @@ pub fn extract_imports(
                 kind: "dynamic".to_string(),
                 is_type_only: false,
                 line: dynamic_node.start_position().row as u32 + 1,
+                order: 0,
                 is_external: ImportInfo::is_external_specifier(&module_specifier),
+                external_kind: external_kind(&module_specifier),
             });
         } else if has_call {
             let fn_name_cap =
@@ -726,7 +804,9 @@ pub fn extract_imports(
                         kind: "require".to_string(),
                         is_type_only: false,
                         line: call_node.start_position().row as u32 + 1,
+                        order: 0,
                         is_external: ImportInfo::is_external_specifier(&module_specifier),
+                        external_kind: external_kind(&module_specifier),
                     });
                 }
             }
@@ -738,6 +818,68 @@ pub fn extract_imports(
 
 // ── Import helpers ──
 
+/// Node.js core modules importable without the `node:` prefix.
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+/// `"builtin"` for Node core modules (`node:crypto`, `fs`,
+/// `fs/promises`), `"package"` for any other external specifier, `None`
+/// for internal ones.
+fn external_kind(specifier: &str) -> Option<String> {
+    if !ImportInfo::is_external_specifier(specifier) {
+        return None;
+    }
+    let root = specifier.split('/').next().unwrap_or(specifier);
+    let kind = if specifier.starts_with("node:") || NODE_BUILTINS.contains(&root) {
+        "builtin"
+    } else {
+        "package"
+    };
+    Some(kind.to_string())
+}
+
 fn strip_quotes(s: &str) -> String {
     let s = s.trim();
     if (s.starts_with('"') && s.ends_with('"'))
@@ -970,9 +1112,16 @@ fn extract_symbol_from_node(
 ) -> (Option<String>, Option<String>) {
     let kind_str = node.kind();
 
-    // If the sibling is an export_statement, look at its first named child
+    // If the sibling is an export_statement, look at the exported
+    // declaration. Decorators (`@Component export class Foo`) precede it
+    // as named children, so skip them rather than taking the first child.
     if kind_str == "export_statement" {
-        if let Some(child) = node.named_child(0) {
+        let mut cursor = node.walk();
+        let declaration = node.child_by_field_name("declaration").or_else(|| {
+            node.named_children(&mut cursor)
+                .find(|c| c.kind() != "decorator" && c.kind() != "comment")
+        });
+        if let Some(child) = declaration {
             return extract_symbol_from_node(child, source);
         }
         return (None, None);
@@ -1123,6 +1272,15 @@ mod tests {
         extract_imports(&tree, source.as_bytes(), &query, "test.ts")
     }
 
+    // ── Comment test helpers ──
+
+    fn parse_and_extract_comments(source: &str) -> Vec<CommentInfo> {
+        let mut parser = create_parser(Language::TypeScript).expect("create parser");
+        let tree = parser.parse(source.as_bytes(), None).expect("parse");
+        let query = compile_comment_query(Language::TypeScript).expect("compile comment query");
+        extract_comments(&tree, source.as_bytes(), &query, "test.ts")
+    }
+
     // ── Symbol tests ──
 
     #[test]
@@ -1250,6 +1408,33 @@ mod tests {
         assert_eq!(pi.kind, SymbolKind::Variable);
     }
 
+    #[test]
+    fn tsx_components_are_distinguished_from_helpers() {
+        let source = "\
+const Button = ({ label }: Props) => <button>{label}</button>;
+const formatLabel = (s: string) => s.trim();
+const Avatar = () => <img src=\"a.png\" />;
+function Page() {
+  return <div><Button label=\"ok\" /></div>;
+}
+const CONFIG = () => ({ debug: true });
+";
+        let syms = parse_and_extract(source, Language::Tsx);
+        let kind_of = |name: &str| syms.iter().find(|s| s.name == name).unwrap().kind;
+        assert_eq!(kind_of("Button"), SymbolKind::Component);
+        assert_eq!(kind_of("Avatar"), SymbolKind::Component);
+        assert_eq!(kind_of("Page"), SymbolKind::Component);
+        assert_eq!(kind_of("formatLabel"), SymbolKind::ArrowFunction);
+        assert_eq!(
+            kind_of("CONFIG"),
+            SymbolKind::ArrowFunction,
+            "PascalCase without JSX is not a component"
+        );
+
+        let plain = parse_and_extract("const Button = () => 1;\n", Language::TypeScript);
+        assert_eq!(plain[0].kind, SymbolKind::ArrowFunction);
+    }
+
     #[test]
     fn extract_interface_type_enum() {
         let source = r#"
@@ -1264,6 +1449,31 @@ mod tests {
         assert!(names.contains(&"Role"));
     }
 
+    #[test]
+    fn extract_interface_members_as_fields() {
+        let source = "interface User { id: number; name: string; }";
+        let syms = parse_and_extract(source, Language::TypeScript);
+        let fields: Vec<&str> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Field)
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(fields, vec!["id", "name"]);
+    }
+
+    #[test]
+    fn extract_enum_members() {
+        let source = "export enum Status { Active, Retired = 'retired' }";
+        let syms = parse_and_extract(source, Language::TypeScript);
+        let members: Vec<&SymbolInfo> = syms
+            .iter()
+            .filter(|s| s.kind == SymbolKind::EnumMember)
+            .collect();
+        let names: Vec<&str> = members.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Active", "Retired"]);
+        assert!(members.iter().all(|s| s.is_exported));
+    }
+
     #[test]
     fn destructured_variables_skipped() {
         let source = "const { a, b } = { a: 1, b: 2 };";
@@ -1461,6 +1671,27 @@ module.exports.deleteItem = function(req, res) {
         assert_eq!(imports[1].imported_name, "bar");
     }
 
+    #[test]
+    fn node_builtins_are_split_from_packages() {
+        let imports = parse_and_extract_imports(
+            "import fs from \"node:fs\";\nimport { readFile } from \"fs\";\nimport React from \"react\";\nimport { x } from \"./x\";",
+            Language::TypeScript,
+        );
+        let kinds: Vec<(&str, Option<&str>)> = imports
+            .iter()
+            .map(|i| (i.module_specifier.as_str(), i.external_kind.as_deref()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("node:fs", Some("builtin")),
+                ("fs", Some("builtin")),
+                ("react", Some("package")),
+                ("./x", None),
+            ]
+        );
+    }
+
     #[test]
     fn default_import() {
         let imports =
@@ -1637,6 +1868,16 @@ const fs = require("fs");
         assert_eq!(imports[0].line, 2);
     }
 
+    // ── Comment tests ──
+
+    #[test]
+    fn doc_comment_skips_decorators_on_exported_class() {
+        let comments = parse_and_extract_comments("/** doc */\n@Dec\nexport class Foo {}\n");
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].associated_symbol.as_deref(), Some("Foo"));
+        assert_eq!(comments[0].associated_symbol_kind.as_deref(), Some("class"));
+    }
+
     // ── resolve_import tests ──
 
     fn known(paths: &[&str]) -> HashSet<String> {
@@ -18,7 +18,9 @@ use crate::models::{
 
 pub fn extract_types(tree: &Tree, source: &[u8], file_path: &str) -> ExtractedTypes {
     let is_js = is_javascript_path(file_path);
-    let mut ctx = Ctx::new(file_path, source, is_js);
+    let lower = file_path.to_ascii_lowercase();
+    let is_jsx = lower.ends_with(".tsx") || lower.ends_with(".jsx");
+    let mut ctx = Ctx::new(file_path, source, is_js, is_jsx);
     ctx.walk(tree.root_node());
     ctx.finish()
 }
@@ -27,6 +29,8 @@ struct Ctx<'a> {
     file_path: &'a str,
     source: &'a [u8],
     is_js: bool,
+    /// `.tsx`/`.jsx`: functions rendering JSX are `Component` symbols.
+    is_jsx: bool,
     types: Vec<TypeRow>,
     seen_display: HashSet<String>,
     param_types: Vec<ParameterTypeRow>,
@@ -36,11 +40,12 @@ struct Ctx<'a> {
 }
 
 impl<'a> Ctx<'a> {
-    fn new(file_path: &'a str, source: &'a [u8], is_js: bool) -> Self {
+    fn new(file_path: &'a str, source: &'a [u8], is_js: bool, is_jsx: bool) -> Self {
         Self {
             file_path,
             source,
             is_js,
+            is_jsx,
             types: Vec::new(),
             seen_display: HashSet::new(),
             param_types: Vec::new(),
@@ -90,11 +95,18 @@ impl<'a> Ctx<'a> {
         // Function name: for function_declaration, method_definition, etc.
         // arrow_function / function_expression are anonymous unless bound by
         // a variable_declarator — we walk the parent to find the binding.
-        let (fn_name, fn_line, fn_col, fn_kind) = match resolve_function_identity(node, self.source)
+        let (fn_name, fn_line, fn_col, mut fn_kind) =
+            match resolve_function_identity(node, self.source) {
+                Some(v) => v,
+                None => return,
+            };
+        // Mirror the symbol extractor so the function ids line up.
+        if self.is_jsx
+            && matches!(fn_kind, SymbolKind::Function | SymbolKind::ArrowFunction)
+            && super::queries::is_component(&fn_name, node)
         {
-            Some(v) => v,
-            None => return,
-        };
+            fn_kind = SymbolKind::Component;
+        }
 
         // Parameters live on a child labeled "parameters" (formal_parameters)
         // OR — for an arrow function with a single bare identifier — directly
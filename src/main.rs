@@ -1,17 +1,22 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::Instant;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use tracing::{info, info_span, warn};
 
-use virgil_cli::cli::{Cli, Command, LogFormat, ProjectCommand};
-use virgil_cli::db::{self, DbStore};
+use virgil_cli::cli::{ArtifactFormat, Cli, Command, LogFormat, ProjectCommand, SchemaTarget};
+use virgil_cli::db::{self, DbStore, StoreOptions};
+use virgil_cli::graph::batch::{self, BatchOptions, BuildTarget};
+use virgil_cli::graph::builder::{BuildOptions, GraphBuilder};
+use virgil_cli::graph::parse_errors::{self, ParseErrorSummary};
 use virgil_cli::language::{self, Language};
 use virgil_cli::observability::{self, sampler::ResourceSampler};
-use virgil_cli::queries::{self, QueryRequest, QuerySource};
-use virgil_cli::storage::registry;
+use virgil_cli::queries::cache::QueryCache;
+use virgil_cli::queries::{self, Page, QueryRequest, QuerySource};
 use virgil_cli::storage::workspace::Workspace;
+use virgil_cli::storage::{discovery, registry};
 
 enum QueryBody {
     Inline(String),
@@ -19,6 +24,14 @@ enum QueryBody {
     Template(String),
 }
 
+/// A `--with-project` store to attach, with the `built_at` stamp that
+/// keys `--cache` results to that build.
+struct Dataset {
+    name: String,
+    path: PathBuf,
+    built_at: String,
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -26,7 +39,7 @@ fn main() -> Result<()> {
         LogFormat::Compact => observability::LogFormat::Compact,
         LogFormat::Json => observability::LogFormat::Json,
     };
-    observability::init(cli.verbose, cli.quiet, log_format);
+    observability::init(cli.verbose, cli.quiet, log_format, !cli.no_progress);
 
     let result = dispatch(cli.command);
     if let Err(err) = &result {
@@ -43,7 +56,20 @@ fn dispatch(command: Command) -> Result<()> {
                 path,
                 exclude,
                 lang,
+                dry_run,
             } => {
+                if dry_run {
+                    let languages = match &lang {
+                        Some(f) => language::parse_language_filter(f),
+                        None => Language::defaults(),
+                    };
+                    let root = std::fs::canonicalize(&path)
+                        .with_context(|| format!("path does not exist: {}", path.display()))?;
+                    let plan = discovery::plan_files(&root, &languages)?;
+                    let envelope = serde_json::json!({ "result": plan });
+                    println!("{}", queries::render_json(&envelope, true)?);
+                    return Ok(());
+                }
                 let entry = registry::create_project(&name, path, exclude, lang.as_deref())?;
                 info!(
                     project = %entry.name,
@@ -89,6 +115,209 @@ fn dispatch(command: Command) -> Result<()> {
                 Ok(())
             }
 
+            ProjectCommand::Build {
+                mut names,
+                from_file,
+                jobs,
+                rebuild,
+                max_file_size,
+                symbol_kinds,
+                symbol_name_regex,
+                query_dir,
+                internal_prefixes,
+                threads,
+                memory_limit,
+                pretty,
+            } => {
+                configure_thread_pool(threads)?;
+                let build = build_options(
+                    symbol_kinds,
+                    symbol_name_regex,
+                    query_dir,
+                    internal_prefixes,
+                )?;
+                if let Some(path) = from_file {
+                    let list = std::fs::read_to_string(&path)
+                        .with_context(|| format!("failed to read {}", path.display()))?;
+                    names.extend(
+                        list.lines()
+                            .map(str::trim)
+                            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                            .map(str::to_string),
+                    );
+                }
+                if names.is_empty() {
+                    anyhow::bail!("projects build needs at least one project name");
+                }
+                let targets = names
+                    .iter()
+                    .map(|name| {
+                        let project = registry::get_project(name)?;
+                        let languages = match &project.languages {
+                            Some(f) => language::parse_language_filter(f),
+                            None => Language::defaults(),
+                        };
+                        Ok(BuildTarget {
+                            project: name.clone(),
+                            root: project.path,
+                            languages,
+                            store_path: db::cache_dir_for_db(name)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let options = BatchOptions {
+                    jobs,
+                    rebuild,
+                    max_file_size: (max_file_size > 0).then_some(max_file_size),
+                    store_options: StoreOptions {
+                        memory_limit_mb: memory_limit,
+                        threads: (threads > 0).then_some(threads),
+                    },
+                    build,
+                };
+                let summaries = batch::build_all(&targets, &options);
+                let failed = summaries.iter().filter(|s| s.state == "failed").count();
+                let envelope = serde_json::json!({ "result": summaries });
+                println!("{}", queries::render_json(&envelope, pretty)?);
+                if failed > 0 {
+                    anyhow::bail!("{failed} of {} projects failed to build", summaries.len());
+                }
+                Ok(())
+            }
+
+            ProjectCommand::Diff {
+                before,
+                after,
+                pretty,
+            } => {
+                let before_store = open_built_store(&before)?;
+                let after_store = open_built_store(&after)?;
+                let diff = queries::diff::diff_stores(&before_store, &after_store)?;
+                info!(
+                    files_added = diff.summary.files_added,
+                    files_removed = diff.summary.files_removed,
+                    symbols_added = diff.summary.symbols_added,
+                    symbols_removed = diff.summary.symbols_removed,
+                    symbols_modified = diff.summary.symbols_modified,
+                    "diff complete"
+                );
+                let envelope = serde_json::json!({
+                    "before": before,
+                    "after": after,
+                    "result": diff,
+                });
+                println!("{}", queries::render_json(&envelope, pretty)?);
+                Ok(())
+            }
+
+            ProjectCommand::Read {
+                name,
+                path,
+                start_line,
+                end_line,
+                numbers,
+                highlight,
+                root,
+                pretty,
+            } => {
+                let root = match root {
+                    Some(root) => root,
+                    None => default_read_root(&name)?,
+                };
+                let mut excerpt = queries::read::read_excerpt(&root, &path, start_line, end_line)?;
+                if numbers {
+                    excerpt.number_lines(highlight);
+                }
+                let envelope = serde_json::json!({
+                    "project": name,
+                    "result": excerpt,
+                });
+                println!("{}", queries::render_json(&envelope, pretty)?);
+                Ok(())
+            }
+
+            ProjectCommand::Schema { name, pretty } => {
+                let store = open_built_store(&name)?;
+                let tables = queries::schema::describe_store(&store)?;
+                let envelope = serde_json::json!({
+                    "project": name,
+                    "tables": tables,
+                });
+                println!("{}", queries::render_json(&envelope, pretty)?);
+                Ok(())
+            }
+
+            ProjectCommand::CheckErrors {
+                name,
+                baseline,
+                pretty,
+            } => {
+                let store = open_built_store(&name)?;
+                let errors = parse_errors::load(&store)?;
+                let known = match &baseline {
+                    Some(path) => parse_errors::load_baseline(path)?,
+                    None => HashSet::new(),
+                };
+                let new = parse_errors::new_failures(&errors, &known);
+                let envelope = serde_json::json!({
+                    "project": name,
+                    "failures": errors.iter().filter(|e| parse_errors::is_failure(&e.error_type)).count(),
+                    "new": new,
+                });
+                println!("{}", queries::render_json(&envelope, pretty)?);
+                match (new.len(), baseline) {
+                    (0, _) => {}
+                    (n, Some(_)) => anyhow::bail!("{n} new parse error(s) not in the baseline"),
+                    (n, None) => anyhow::bail!("{n} file(s) had parse errors"),
+                }
+                Ok(())
+            }
+
+            ProjectCommand::Export {
+                name,
+                output,
+                artifact_format,
+                absolute_paths,
+                pretty,
+            } => {
+                let store = open_built_store(&name)?;
+                let format = match artifact_format {
+                    ArtifactFormat::ArrowIpc => db::export::ExportFormat::ArrowIpc,
+                    ArtifactFormat::Ndjson => db::export::ExportFormat::Ndjson,
+                };
+                let tables = db::export::export(&store, &output, format, absolute_paths)?;
+                let envelope = serde_json::json!({
+                    "project": name,
+                    "tables": tables,
+                });
+                println!("{}", queries::render_json(&envelope, pretty)?);
+                Ok(())
+            }
+
+            ProjectCommand::ApiDiff {
+                before,
+                after,
+                allow_breaking,
+                pretty,
+            } => {
+                let before_store = open_built_store(&before)?;
+                let after_store = open_built_store(&after)?;
+                let diff = queries::diff::api_diff(&before_store, &after_store)?;
+                let breaking = diff.breaking;
+                let envelope = serde_json::json!({
+                    "before": before,
+                    "after": after,
+                    "result": diff,
+                });
+                println!("{}", queries::render_json(&envelope, pretty)?);
+                if breaking > 0 && !allow_breaking {
+                    anyhow::bail!(
+                        "{breaking} breaking API change(s); pass --allow-breaking to accept them"
+                    );
+                }
+                Ok(())
+            }
+
             ProjectCommand::Query {
                 name,
                 lang,
@@ -97,9 +326,54 @@ fn dispatch(command: Command) -> Result<()> {
                 file,
                 template,
                 params,
+                args,
                 rebuild,
+                limit,
+                offset,
+                count,
+                cache,
+                cache_dir,
+                threads,
+                memory_limit,
+                symbol_kinds,
+                symbol_name_regex,
+                query_dir,
+                internal_prefixes,
+                max_file_size,
+                fail_on_error,
+                with_projects,
+                load_extensions,
                 pretty,
             } => {
+                configure_thread_pool(threads)?;
+                for extension in &load_extensions {
+                    db::check_extension(extension)?;
+                }
+                let datasets = with_projects
+                    .into_iter()
+                    .map(|other| {
+                        let built_at = open_built_store(&other)?
+                            .build_meta()?
+                            .remove("built_at")
+                            .unwrap_or_default();
+                        let path = built_store_path(&other)?;
+                        Ok(Dataset {
+                            name: other,
+                            path,
+                            built_at,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let store_options = StoreOptions {
+                    memory_limit_mb: memory_limit,
+                    threads: (threads > 0).then_some(threads),
+                };
+                let build = build_options(
+                    symbol_kinds,
+                    symbol_name_regex,
+                    query_dir,
+                    internal_prefixes,
+                )?;
                 let body = match (sql, file, template) {
                     (Some(s), _, _) => QueryBody::Inline(s),
                     (_, Some(p), _) => QueryBody::FilePath(p),
@@ -109,10 +383,104 @@ fn dispatch(command: Command) -> Result<()> {
                          --file <path>, or --template <name>"
                     ),
                 };
-                run_query(body, params, name, lang, rebuild, pretty)
+                run_query(
+                    body,
+                    params,
+                    args,
+                    name,
+                    lang,
+                    rebuild,
+                    limit.map(|limit| Page { limit, offset }),
+                    count,
+                    cache.then_some(cache_dir),
+                    build,
+                    max_file_size,
+                    store_options,
+                    datasets,
+                    load_extensions,
+                    fail_on_error,
+                    pretty,
+                )
             }
         },
 
+        Command::ParseOne {
+            file,
+            language,
+            path,
+            pretty,
+        } => {
+            let language = language.as_deref().map(Language::from_name).transpose()?;
+            let outline = match (file, language) {
+                (Some(file), language) => virgil_cli::parser::outline_file(&file, language)?,
+                (None, Some(language)) => {
+                    virgil_cli::parser::outline_source(std::io::stdin().lock(), language, &path)?
+                }
+                (None, None) => anyhow::bail!("parse-one needs --language when reading stdin"),
+            };
+            println!("{}", queries::render_json(&outline, pretty)?);
+            Ok(())
+        }
+
+        Command::ParseArchive {
+            archive,
+            output,
+            lang,
+            max_file_size,
+            pretty,
+        } => {
+            let languages = match &lang {
+                Some(f) => language::parse_language_filter(f),
+                None => Language::defaults(),
+            };
+            let project = archive
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let target = BuildTarget {
+                project,
+                root: archive,
+                languages,
+                store_path: output,
+            };
+            let options = BatchOptions {
+                jobs: 1,
+                rebuild: true,
+                max_file_size: (max_file_size > 0).then_some(max_file_size),
+                ..BatchOptions::default()
+            };
+            let summaries = batch::build_all(std::slice::from_ref(&target), &options);
+            let envelope = serde_json::json!({ "result": summaries });
+            println!("{}", queries::render_json(&envelope, pretty)?);
+            if let Some(error) = summaries.iter().find_map(|s| s.error.as_ref()) {
+                anyhow::bail!("failed to build {}: {error}", target.root.display());
+            }
+            Ok(())
+        }
+
+        Command::Info { store, pretty } => {
+            if !store.is_file() {
+                anyhow::bail!("no store file at {}", store.display());
+            }
+            let meta = DbStore::open_read_only(&store)?.build_meta()?;
+            let envelope = serde_json::json!({ "result": meta });
+            println!("{}", queries::render_json(&envelope, pretty)?);
+            Ok(())
+        }
+
+        Command::JsonSchema { command, pretty } => {
+            use queries::json_schema::{OutputKind, output_schema};
+            let kind = match command {
+                SchemaTarget::Query => OutputKind::Query,
+                SchemaTarget::Diff => OutputKind::Diff,
+                SchemaTarget::ApiDiff => OutputKind::ApiDiff,
+                SchemaTarget::Schema => OutputKind::Schema,
+                SchemaTarget::Read => OutputKind::Read,
+            };
+            println!("{}", queries::render_json(&output_schema(kind)?, pretty)?);
+            Ok(())
+        }
+
         Command::Serve {
             name,
             port,
@@ -122,12 +490,108 @@ fn dispatch(command: Command) -> Result<()> {
     }
 }
 
+/// Open a project's warm store without building it. Errors if the
+/// project was never parsed or its store was stale and got reset.
+fn open_built_store(name: &str) -> Result<DbStore> {
+    let cache_path = built_store_path(name)?;
+    let store = DbStore::open_persistent(&cache_path)?;
+    if store.fresh() {
+        anyhow::bail!(
+            "project '{name}' store was stale or incompatible and is now empty. \
+             Rebuild it: virgil-cli projects query {name} --sql 'SELECT 1' --rebuild"
+        );
+    }
+    Ok(store)
+}
+
+/// Path of a registered project's store. Errors if it hasn't been built.
+fn built_store_path(name: &str) -> Result<PathBuf> {
+    registry::get_project(name)?;
+    let cache_path = db::cache_dir_for_db(name)?;
+    if !cache_path.exists() {
+        anyhow::bail!(
+            "project '{name}' is not parsed yet. Build it first, e.g.: \
+             virgil-cli projects query {name} --sql 'SELECT 1'"
+        );
+    }
+    Ok(cache_path)
+}
+
+/// Root that `projects read` resolves paths against: the one recorded
+/// in the project's built store, else the registered project root.
+fn default_read_root(name: &str) -> Result<PathBuf> {
+    let project = registry::get_project(name)?;
+    let cache_path = db::cache_dir_for_db(name)?;
+    if cache_path.exists() {
+        let store = DbStore::open_persistent(&cache_path)?;
+        if !store.fresh()
+            && let Some(root) = store.build_root()?
+        {
+            return Ok(root);
+        }
+    }
+    Ok(project.path)
+}
+
+/// The build-time extraction flags shared by `projects query` and
+/// `projects build`, validated before any build starts.
+fn build_options(
+    symbol_kinds: Option<String>,
+    symbol_name_regex: Option<String>,
+    query_dir: Option<PathBuf>,
+    internal_prefixes: Vec<String>,
+) -> Result<BuildOptions> {
+    let symbol_kinds = symbol_kinds
+        .as_deref()
+        .map(virgil_cli::models::parse_symbol_kinds)
+        .transpose()?;
+    let symbol_name_regex = symbol_name_regex
+        .as_deref()
+        .map(regex::Regex::new)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("invalid --symbol-name-regex: {e}"))?;
+    Ok(BuildOptions {
+        symbol_kinds,
+        symbol_name_regex,
+        query_dir,
+        internal_prefixes,
+    })
+}
+
+/// Size rayon's global pool before any parallel phase runs. Workspace
+/// loading uses the global pool directly; `GraphBuilder` sizes its own
+/// pool from it, so one call bounds the whole cold build. `0` keeps
+/// rayon's default of one thread per core.
+fn configure_thread_pool(threads: usize) -> Result<()> {
+    if threads == 0 {
+        return Ok(());
+    }
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|e| anyhow::anyhow!("failed to configure {threads} worker threads: {e}"))?;
+    info!(threads, "thread pool configured");
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_query(
     source: QueryBody,
     params: Vec<(String, String)>,
+    args: Vec<String>,
     name: String,
     lang: Option<String>,
     rebuild: bool,
+    page: Option<Page>,
+    count_only: bool,
+    // `Some` when `--cache` is on, holding the `--cache-dir` override.
+    result_cache: Option<Option<PathBuf>>,
+    build_options: BuildOptions,
+    max_file_size: u64,
+    store_options: StoreOptions,
+    datasets: Vec<Dataset>,
+    load_extensions: Vec<String>,
+    fail_on_error: bool,
     pretty: bool,
 ) -> Result<()> {
     let sampler = ResourceSampler::start(std::time::Duration::from_millis(250));
@@ -137,9 +601,10 @@ fn run_query(
         let project = registry::get_project(&name)?;
         let languages = match &project.languages {
             Some(f) => language::parse_language_filter(f),
-            None => Language::all().to_vec(),
+            None => Language::defaults(),
         };
-        let ws = Workspace::load(&project.path, &languages, None)?;
+        let max_file_size = (max_file_size > 0).then_some(max_file_size);
+        let ws = Workspace::load(&project.path, &languages, max_file_size)?;
         info!(files = ws.file_count(), project = %name, "workspace loaded");
         (ws, name)
     };
@@ -155,38 +620,98 @@ fn run_query(
         info!(path = %cache_path.display(), "rebuild requested, wiping cache");
         std::fs::remove_file(&cache_path)?;
     }
-    let store = DbStore::open_persistent(&cache_path)?;
-    let cache_state = if store.fresh() {
+    let store = DbStore::open_persistent(&cache_path)?.with_options(store_options)?;
+    let (cache_state, parse_errors) = if store.fresh() {
         let _span = info_span!("db.cold_build").entered();
         let graph = {
             let _gs = info_span!("graph.build").entered();
-            virgil_cli::graph::builder::GraphBuilder::new(&workspace, &languages).build(&store)?
+            GraphBuilder::new(&workspace, &languages)
+                .with_options(&build_options)
+                .build(&store)?
         };
         {
             let _ps = info_span!("db.populate").entered();
             db::populate(&store, &graph, Some(&workspace))?;
         }
-        "cold"
+        ("cold", graph.parse_errors)
     } else {
+        for flag in build_options.unapplied_flags() {
+            warn!("{flag} only applies to a fresh build; pass --rebuild to apply it");
+        }
         // Incremental refresh skipped on this branch (Q6 decision).
         // Warm reopen means "schema version matches"; we trust the
         // cached store is current. To force a rebuild, pass --rebuild.
-        "warm"
+        ("warm", parse_errors::load(&store)?)
     };
+    let parse_error_summary = ParseErrorSummary::from_errors(&parse_errors);
+    parse_error_summary.log();
+
+    // The query itself runs on a read-only connection, so SQL that
+    // writes (INSERT, DROP, ...) fails instead of corrupting the store.
+    drop(store);
+    let attached: Vec<(String, PathBuf)> = datasets
+        .iter()
+        .map(|d| (d.name.clone(), d.path.clone()))
+        .collect();
+    let store = DbStore::open_read_only(&cache_path)?
+        .with_options(store_options)?
+        .with_datasets(&project_name, &attached)?
+        .with_extensions(&load_extensions)?;
 
     let source_ref = match &source {
         QueryBody::Inline(s) => QuerySource::Inline(s.as_str()),
         QueryBody::FilePath(p) => QuerySource::File(p.as_path()),
         QueryBody::Template(t) => QuerySource::Template(t.as_str()),
     };
-    let output = {
+    // Keyed before `run_body` takes ownership of the params and args.
+    let result_cache = match result_cache {
+        Some(dir) => {
+            let dir = match dir {
+                Some(dir) => dir,
+                None => QueryCache::default_dir(&project_name)?,
+            };
+            let key = result_cache_key(
+                &source,
+                &params,
+                &args,
+                &load_extensions,
+                &datasets,
+                page,
+                count_only,
+            )?;
+            Some((QueryCache::new(dir), key))
+        }
+        None => None,
+    };
+    let run_body = || -> Result<serde_json::Value> {
         let _qs = info_span!("query.run", cache_state = cache_state).entered();
-        queries::run(QueryRequest {
+        let request = QueryRequest {
             source: source_ref,
             params,
+            args,
             store: &store,
             workspace: &workspace,
-        })?
+        };
+        let mut body = serde_json::json!({});
+        match page {
+            _ if count_only => body["count"] = queries::run_count(request)?.into(),
+            Some(page) => {
+                let paged = queries::run_page(request, page)?;
+                body["result"] = serde_json::to_value(paged.output)?;
+                body["total_count"] = paged.total_count.into();
+                body["limit"] = page.limit.into();
+                body["offset"] = page.offset.into();
+            }
+            None => body["result"] = serde_json::to_value(queries::run(request)?)?,
+        }
+        Ok(body)
+    };
+    let (body, cache_hit) = match result_cache {
+        Some((cache, key)) => {
+            let fingerprint = queries::cache::fingerprint(&workspace, &store)?;
+            cache.get_or_run(key, fingerprint, run_body)?
+        }
+        None => (run_body()?, false),
     };
     let elapsed = start.elapsed();
     let res = sampler.stop();
@@ -196,20 +721,62 @@ fn run_query(
         peak_rss_mb = res.peak_rss_mb,
         avg_cpu_pct = res.avg_cpu_pct,
         cache = cache_state,
+        cache_hit,
         "query pipeline complete",
     );
 
-    let envelope = serde_json::json!({
+    let mut envelope = serde_json::json!({
         "project": project_name,
         "query_ms": elapsed.as_millis(),
-        "cache": cache_state,
-        "result": output,
+        "cache": if cache_hit { "hit" } else { cache_state },
     });
-    let s = if pretty {
-        serde_json::to_string_pretty(&envelope)?
-    } else {
-        serde_json::to_string(&envelope)?
+    if let serde_json::Value::Object(fields) = body {
+        for (field, value) in fields {
+            envelope[field] = value;
+        }
+    }
+    println!("{}", queries::render_json(&envelope, pretty)?);
+    parse_error_summary.check(fail_on_error)
+}
+
+/// `--cache` key: the query as written (a SQL file by its contents, so
+/// editing it misses), its params and args, the loaded extensions, each
+/// attached project and when it was built, and the paging mode.
+fn result_cache_key(
+    source: &QueryBody,
+    params: &[(String, String)],
+    args: &[String],
+    extensions: &[String],
+    datasets: &[Dataset],
+    page: Option<Page>,
+    count_only: bool,
+) -> Result<u64> {
+    let (kind, text) = match source {
+        QueryBody::Inline(sql) => ("sql", sql.clone()),
+        QueryBody::FilePath(path) => (
+            "file",
+            std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?,
+        ),
+        QueryBody::Template(name) => ("template", name.clone()),
     };
-    println!("{s}");
-    Ok(())
+    let params: Vec<String> = params.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    let datasets: Vec<String> = datasets
+        .iter()
+        .map(|d| format!("{}@{}", d.name, d.built_at))
+        .collect();
+    let paging = match page {
+        _ if count_only => "count".to_string(),
+        Some(page) => format!("{}+{}", page.offset, page.limit),
+        None => "all".to_string(),
+    };
+    Ok(queries::cache::key(&[
+        kind,
+        &text,
+        &params.join("\n"),
+        &args.join("\n"),
+        &extensions.join("\n"),
+        &datasets.join("\n"),
+        &paging,
+    ]))
 }
@@ -1,5 +1,8 @@
+use std::collections::HashSet;
 use std::fmt;
 
+use anyhow::{Result, bail};
+
 #[derive(Debug, Clone)]
 pub struct FileMetadata {
     pub path: String,
@@ -35,6 +38,13 @@ pub enum SymbolKind {
     /// here. Used as the `kind` segment of the synthesized symbol_id in
     /// `field_type` rows (issue #14).
     Field,
+    /// React component in a `.tsx`/`.jsx` file: a PascalCase function or
+    /// arrow function whose body renders JSX.
+    Component,
+    /// Enum variant / member (Rust variant, C/C++ enumerator, Java enum
+    /// constant, C#/TS enum member, PHP enum case). Its enclosing enum is
+    /// recorded as the symbol's parent.
+    EnumMember,
 }
 
 impl SymbolKind {
@@ -60,9 +70,60 @@ impl SymbolKind {
             "module" => Some(SymbolKind::Module),
             "parameter" => Some(SymbolKind::Parameter),
             "field" => Some(SymbolKind::Field),
+            "component" => Some(SymbolKind::Component),
+            "enum_member" => Some(SymbolKind::EnumMember),
             _ => None,
         }
     }
+
+    /// The closest LSP `SymbolKind` number. LSP has no type-alias, trait,
+    /// macro or component kinds: aliases map to TypeParameter (26),
+    /// traits to Interface (11), macros and components to Function (12).
+    pub fn lsp_kind(self) -> u8 {
+        match self {
+            SymbolKind::Module => 2,
+            SymbolKind::Namespace => 3,
+            SymbolKind::Class => 5,
+            SymbolKind::Method => 6,
+            SymbolKind::Property => 7,
+            SymbolKind::Field => 8,
+            SymbolKind::Enum => 10,
+            SymbolKind::Interface | SymbolKind::Trait => 11,
+            SymbolKind::Function
+            | SymbolKind::ArrowFunction
+            | SymbolKind::Macro
+            | SymbolKind::Component => 12,
+            SymbolKind::Variable | SymbolKind::Parameter => 13,
+            SymbolKind::Constant => 14,
+            SymbolKind::EnumMember => 22,
+            SymbolKind::Struct | SymbolKind::Union => 23,
+            SymbolKind::TypeAlias | SymbolKind::Typedef => 26,
+        }
+    }
+}
+
+/// Parse a comma-separated kind list (`function,class,method`), as
+/// given to `--symbol-kinds`. Unknown names are an error rather than
+/// silently matching nothing.
+pub fn parse_symbol_kinds(list: &str) -> Result<HashSet<SymbolKind>> {
+    let mut kinds = HashSet::new();
+    for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match SymbolKind::from_str(name) {
+            Some(kind) => {
+                kinds.insert(kind);
+            }
+            None => bail!(
+                "unknown symbol kind '{name}' (expected one of: function, class, method, \
+                 variable, interface, type_alias, enum, arrow_function, struct, union, \
+                 namespace, macro, property, typedef, trait, constant, module, parameter, field, component, \
+                 enum_member)"
+            ),
+        }
+    }
+    if kinds.is_empty() {
+        bail!("--symbol-kinds needs at least one kind");
+    }
+    Ok(kinds)
 }
 
 impl fmt::Display for SymbolKind {
@@ -87,6 +148,8 @@ impl fmt::Display for SymbolKind {
             SymbolKind::Module => "module",
             SymbolKind::Parameter => "parameter",
             SymbolKind::Field => "field",
+            SymbolKind::Component => "component",
+            SymbolKind::EnumMember => "enum_member",
         };
         f.write_str(s)
     }
@@ -149,6 +212,15 @@ pub struct SymbolInfo {
     pub is_static: bool,
     pub is_abstract: bool,
     pub is_mutable: bool,
+    /// Carries a deprecation marker: Rust `#[deprecated]`, Java
+    /// `@Deprecated`, C# `[Obsolete]`, Python `@deprecated` or a
+    /// `warnings.warn(..., DeprecationWarning)` in the body, or
+    /// `@deprecated` in a TS/JS doc comment.
+    pub is_deprecated: bool,
+    /// Non-blank, non-comment lines in the symbol's span. Language
+    /// extractors leave it `None`; the graph builder fills it in once the
+    /// file's comments are known.
+    pub loc: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -160,7 +232,15 @@ pub struct ImportInfo {
     pub kind: String,
     pub is_type_only: bool,
     pub line: u32,
+    /// Zero-based position among the file's imports in source order, for
+    /// reconstructing side-effect order. Extractors leave it 0;
+    /// [`crate::languages::extract_imports`] numbers the final list.
+    pub order: u32,
     pub is_external: bool,
+    /// Finer split of external imports where the language has one: JS/TS
+    /// set `"builtin"` (Node core modules, `node:*`) or `"package"`.
+    /// `None` for internal imports and for other languages.
+    pub external_kind: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -376,6 +456,7 @@ pub struct TypescriptAttrsRow {
     pub is_readonly: bool,
     pub is_optional: bool,
     pub type_parameters: Vec<String>,
+    pub decorators: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -430,6 +511,9 @@ pub struct CAttrsRow {
 pub struct JavaAttrsRow {
     pub symbol_id: String,
     pub annotations: Vec<String>,
+    /// Argument text of each entry in `annotations`, without the
+    /// parentheses; `None` for a marker annotation (`@Override`).
+    pub annotation_arguments: Vec<Option<String>>,
     pub is_final: bool,
     pub is_synchronized: bool,
     pub throws_clause: Vec<String>,
@@ -474,12 +558,135 @@ impl ImportInfo {
     pub fn is_external_specifier(module_specifier: &str) -> bool {
         !(module_specifier.starts_with('.') || module_specifier.starts_with('#'))
     }
+
+    /// Reclassify as internal when the specifier starts with one of
+    /// `prefixes` (`--internal-prefix`), e.g. a monorepo path alias
+    /// like `@app/*`. A trailing `*` is ignored, so `@app/*` and `@app/`
+    /// are the same rule.
+    pub fn apply_internal_prefixes(&mut self, prefixes: &[String]) {
+        let internal = prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('*');
+            !prefix.is_empty() && self.module_specifier.starts_with(prefix)
+        });
+        if internal {
+            self.is_external = false;
+            self.external_kind = None;
+        }
+    }
+}
+
+impl CommentInfo {
+    /// The comment as prose: delimiters (`//`, `///`, `/* */`, `#`,
+    /// docstring quotes) and ` * ` gutters stripped, the lines of each
+    /// paragraph joined with a space and paragraphs with a newline.
+    /// The raw `text` keeps the original block.
+    pub fn clean_text(&self) -> String {
+        let mut paragraphs: Vec<String> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        for line in self.text.lines() {
+            let line = clean_comment_line(line);
+            if line.is_empty() {
+                if !current.is_empty() {
+                    paragraphs.push(current.join(" "));
+                    current.clear();
+                }
+            } else {
+                current.push(line);
+            }
+        }
+        if !current.is_empty() {
+            paragraphs.push(current.join(" "));
+        }
+        paragraphs.join("\n")
+    }
+}
+
+fn clean_comment_line(line: &str) -> &str {
+    let mut line = line.trim();
+    for opener in ["/**", "/*!", "/*", "///", "//!", "//", "\"\"\"", "'''", "#"] {
+        if let Some(rest) = line.strip_prefix(opener) {
+            line = rest;
+            break;
+        }
+    }
+    for closer in ["*/", "\"\"\"", "'''"] {
+        if let Some(rest) = line.strip_suffix(closer) {
+            line = rest;
+            break;
+        }
+    }
+    let line = line.trim();
+    let line = match line.strip_prefix('*') {
+        Some(rest) if !rest.starts_with('*') => rest,
+        _ => line,
+    };
+    line.trim()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn internal_prefixes_reclassify_path_aliases() {
+        let import = |specifier: &str| ImportInfo {
+            source_file: "src/app.ts".to_string(),
+            module_specifier: specifier.to_string(),
+            imported_name: "x".to_string(),
+            local_name: "x".to_string(),
+            kind: "named".to_string(),
+            is_type_only: false,
+            line: 1,
+            order: 0,
+            is_external: true,
+            external_kind: Some("package".to_string()),
+        };
+        let prefixes = vec!["@app/*".to_string()];
+
+        let mut aliased = import("@app/utils");
+        aliased.apply_internal_prefixes(&prefixes);
+        assert!(!aliased.is_external);
+        assert_eq!(aliased.external_kind, None);
+
+        let mut package = import("react");
+        package.apply_internal_prefixes(&prefixes);
+        assert!(package.is_external);
+        assert_eq!(package.external_kind.as_deref(), Some("package"));
+    }
+
+    fn comment(text: &str) -> CommentInfo {
+        CommentInfo {
+            file_path: "a".to_string(),
+            text: text.to_string(),
+            kind: "doc".to_string(),
+            start_byte: 0,
+            end_byte: text.len() as u32,
+            start_line: 1,
+            start_column: 0,
+            end_line: 1,
+            end_column: 0,
+            associated_symbol: None,
+            associated_symbol_kind: None,
+        }
+    }
+
+    #[test]
+    fn clean_text_strips_rust_line_doc_markers() {
+        let c = comment("/// Adds one to `x`.\n/// Never overflows.\n///\n/// # Panics\n");
+        assert_eq!(
+            c.clean_text(),
+            "Adds one to `x`. Never overflows.\n# Panics"
+        );
+    }
+
+    #[test]
+    fn clean_text_strips_javadoc_gutters() {
+        let c = comment("/**\n * Adds one.\n *\n * @param x the value\n */");
+        assert_eq!(c.clean_text(), "Adds one.\n@param x the value");
+        assert_eq!(comment("/* inline */").clean_text(), "inline");
+        assert_eq!(comment("# shell note").clean_text(), "shell note");
+    }
+
     #[test]
     fn is_external_specifier_classifies_correctly() {
         // External: bare specifiers, scoped packages, builtins
@@ -495,9 +702,22 @@ mod tests {
         assert!(!ImportInfo::is_external_specifier("#internal/utils"));
     }
 
+    #[test]
+    fn parse_symbol_kinds_accepts_known_and_rejects_unknown() {
+        let kinds = parse_symbol_kinds("function, class").unwrap();
+        assert_eq!(
+            kinds,
+            HashSet::from([SymbolKind::Function, SymbolKind::Class])
+        );
+        let err = parse_symbol_kinds("function,klass").unwrap_err();
+        assert!(err.to_string().contains("klass"));
+        assert!(parse_symbol_kinds("").is_err());
+    }
+
     #[test]
     fn symbol_kind_display() {
         assert_eq!(SymbolKind::Function.to_string(), "function");
+        assert_eq!(SymbolKind::Component.to_string(), "component");
         assert_eq!(SymbolKind::Class.to_string(), "class");
         assert_eq!(SymbolKind::Method.to_string(), "method");
         assert_eq!(SymbolKind::Variable.to_string(), "variable");
@@ -516,5 +736,6 @@ mod tests {
         assert_eq!(SymbolKind::Module.to_string(), "module");
         assert_eq!(SymbolKind::Parameter.to_string(), "parameter");
         assert_eq!(SymbolKind::Field.to_string(), "field");
+        assert_eq!(SymbolKind::EnumMember.to_string(), "enum_member");
     }
 }
@@ -13,7 +13,9 @@ pub enum LogFormat {
     Json,
 }
 
-pub fn init(verbosity: u8, quiet: bool, format: LogFormat) {
+/// `progress = false` (`--no-progress`) suppresses progress bars even on
+/// a TTY; piped or JSON output never shows them.
+pub fn init(verbosity: u8, quiet: bool, format: LogFormat, progress: bool) {
     if INITIALIZED.swap(true, Ordering::SeqCst) {
         return;
     }
@@ -36,6 +38,7 @@ pub fn init(verbosity: u8, quiet: bool, format: LogFormat) {
     // progress bars would corrupt that. Only enable indicatif for compact output
     // attached to a TTY.
     let want_bars = matches!(format, LogFormat::Compact)
+        && progress
         && !quiet
         && std::io::IsTerminal::is_terminal(&std::io::stderr());
 
@@ -1,8 +1,11 @@
+use std::io::Read;
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use serde_json::json;
 
 use crate::language::Language;
+use crate::languages;
 use crate::models::FileMetadata;
 
 pub fn create_parser(language: Language) -> Result<tree_sitter::Parser> {
@@ -13,17 +16,121 @@ pub fn create_parser(language: Language) -> Result<tree_sitter::Parser> {
     Ok(parser)
 }
 
+/// Parse `source` as `language`. Single-file components (`.vue`,
+/// `.svelte`) are parsed only within their `<script>` blocks, so node
+/// positions (and the symbol and import lines read from them) stay
+/// relative to the whole file. A component without a script yields an
+/// empty tree.
+pub fn parse_source(
+    parser: &mut tree_sitter::Parser,
+    source: &str,
+    language: Language,
+) -> Option<tree_sitter::Tree> {
+    if language.is_single_file_component() {
+        let mut ranges = script_ranges(source);
+        if ranges.is_empty() {
+            ranges.push(tree_sitter::Range {
+                start_byte: 0,
+                end_byte: 0,
+                start_point: tree_sitter::Point::new(0, 0),
+                end_point: tree_sitter::Point::new(0, 0),
+            });
+        }
+        parser.set_included_ranges(&ranges).ok()?;
+    }
+    parser.parse(source, None)
+}
+
+/// Byte ranges of the contents of every `<script ...>...</script>`
+/// element in `source`, in order. Svelte components may carry both a
+/// module and an instance script. Tags inside `<!-- ... -->` are
+/// skipped.
+fn script_ranges(source: &str) -> Vec<tree_sitter::Range> {
+    let bytes = source.as_bytes();
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while let Some(found) = source[pos..].find("<script") {
+        if let Some(comment) = source[pos..pos + found].find("<!--") {
+            let body = pos + comment + "<!--".len();
+            let Some(len) = source[body..].find("-->") else {
+                break;
+            };
+            pos = body + len + "-->".len();
+            continue;
+        }
+        let tag_start = pos + found;
+        let after_name = tag_start + "<script".len();
+        pos = after_name;
+        if !bytes
+            .get(after_name)
+            .is_some_and(|b| *b == b'>' || b.is_ascii_whitespace())
+        {
+            continue;
+        }
+        let Some(close) = tag_end(bytes, after_name) else {
+            break;
+        };
+        let content_start = close + 1;
+        if bytes[content_start - 2] == b'/' {
+            // `<script src="..." />` has no content.
+            pos = content_start;
+            continue;
+        }
+        let Some(len) = source[content_start..].find("</script") else {
+            break;
+        };
+        let content_end = content_start + len;
+        ranges.push(tree_sitter::Range {
+            start_byte: content_start,
+            end_byte: content_end,
+            start_point: point_at(bytes, content_start),
+            end_point: point_at(bytes, content_end),
+        });
+        pos = content_end;
+    }
+    ranges
+}
+
+/// Index of the `>` closing the tag whose attributes start at `from`,
+/// skipping over quoted attribute values such as
+/// `generics="T extends Record<string, unknown>"`.
+fn tag_end(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut quote = None;
+    for (i, &b) in bytes.iter().enumerate().skip(from) {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if b == b'>' => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+fn point_at(bytes: &[u8], offset: usize) -> tree_sitter::Point {
+    let before = &bytes[..offset];
+    let row = before.iter().filter(|&&b| b == b'\n').count();
+    let column = offset
+        - before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+    tree_sitter::Point::new(row, column)
+}
+
 pub fn parse_file(
     parser: &mut tree_sitter::Parser,
     path: &Path,
     root: &Path,
     language: Language,
 ) -> Result<(FileMetadata, tree_sitter::Tree)> {
-    let source = std::fs::read_to_string(path)
-        .with_context(|| format!("failed to read {}", path.display()))?;
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let (source, _) = crate::storage::encoding::decode(bytes)
+        .with_context(|| format!("failed to decode {}", path.display()))?;
 
-    let tree = parser
-        .parse(&source, None)
+    let tree = parse_source(parser, &source, language)
         .with_context(|| format!("tree-sitter failed to parse {}", path.display()))?;
 
     let relative_path = path
@@ -63,8 +170,7 @@ pub fn parse_content(
     relative_path: &str,
     language: Language,
 ) -> Result<(FileMetadata, tree_sitter::Tree)> {
-    let tree = parser
-        .parse(source, None)
+    let tree = parse_source(parser, source, language)
         .with_context(|| format!("tree-sitter failed to parse {relative_path}"))?;
 
     // Extract name and extension from the key/path string
@@ -91,6 +197,107 @@ pub fn parse_content(
     Ok((metadata, tree))
 }
 
+/// [`outline_source`] for a file on disk. Without an explicit
+/// `language` it is detected the way workspace discovery does it
+/// (extension, well-known filename, `#!` line). The output's `path` is
+/// `file` as given.
+pub fn outline_file(file: &Path, language: Option<Language>) -> Result<serde_json::Value> {
+    let language = match language {
+        Some(language) => language,
+        None => crate::storage::discovery::detect_language(file).with_context(|| {
+            format!(
+                "cannot detect the language of {}; pass --language",
+                file.display()
+            )
+        })?,
+    };
+    let reader =
+        std::fs::File::open(file).with_context(|| format!("failed to open {}", file.display()))?;
+    outline_source(reader, language, &file.to_string_lossy())
+}
+
+/// Parse one buffer and return its symbols, imports and comments as
+/// JSON. Backs `parse-one`, which reads an editor buffer from stdin and
+/// never touches a project store. `path` only labels the output and
+/// lets path-sensitive extractors (Python `__init__`, Rust `mod.rs`)
+/// behave as they would on disk.
+pub fn outline_source(
+    mut reader: impl Read,
+    language: Language,
+    path: &str,
+) -> Result<serde_json::Value> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .context("failed to read source")?;
+    let (source, _) = crate::storage::encoding::decode(bytes).context("failed to decode source")?;
+
+    let mut parser = create_parser(language)?;
+    let (_, tree) = parse_content(&mut parser, &source, path, language)?;
+    let bytes = source.as_bytes();
+
+    let symbol_query = languages::compile_symbol_query(language)?;
+    let import_query = languages::compile_import_query(language)?;
+    let symbols = languages::extract_symbols(&tree, bytes, &symbol_query, path, language);
+    let imports = languages::extract_imports(&tree, bytes, &import_query, path, language);
+    let comments = match languages::compile_comment_query(language) {
+        Ok(q) => languages::extract_comments(&tree, bytes, &q, path, language),
+        Err(_) => Vec::new(),
+    };
+
+    let symbols: Vec<_> = symbols
+        .iter()
+        .map(|s| {
+            json!({
+                "name": s.name,
+                "kind": s.kind.to_string(),
+                "start_line": s.start_line,
+                "start_column": s.start_column,
+                "end_line": s.end_line,
+                "end_column": s.end_column,
+                "exported": s.is_exported,
+                "visibility": s.visibility.as_str(),
+            })
+        })
+        .collect();
+    let imports: Vec<_> = imports
+        .iter()
+        .map(|i| {
+            json!({
+                "module": i.module_specifier,
+                "imported_name": i.imported_name,
+                "local_name": i.local_name,
+                "kind": i.kind,
+                "line": i.line,
+                "external": i.is_external,
+                "external_kind": i.external_kind,
+            })
+        })
+        .collect();
+    let comments: Vec<_> = comments
+        .iter()
+        .map(|c| {
+            json!({
+                "text": c.text,
+                "clean_text": c.clean_text(),
+                "kind": c.kind,
+                "start_line": c.start_line,
+                "end_line": c.end_line,
+                "associated_symbol": c.associated_symbol,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "path": path,
+        "language": language.as_str(),
+        "has_errors": tree.root_node().has_error(),
+        "symbols": symbols,
+        "imports": imports,
+        "comments": comments,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,6 +310,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn script_ranges_cover_every_svelte_script() {
+        let source = "<script context=\"module\">\n  export const a = 1;\n</script>\n\
+                      <script src=\"x.js\" />\n\
+                      <script>\n  let b = 2;\n</script>\n<h1>{b}</h1>\n";
+        let ranges = script_ranges(source);
+        let contents: Vec<&str> = ranges
+            .iter()
+            .map(|r| &source[r.start_byte..r.end_byte])
+            .collect();
+        assert_eq!(
+            contents,
+            vec!["\n  export const a = 1;\n", "\n  let b = 2;\n"]
+        );
+        assert_eq!(ranges[1].start_point, tree_sitter::Point::new(4, 8));
+        assert!(script_ranges("<scripts>no</scripts>").is_empty());
+
+        let source = "<!-- <script>old()</script> -->\n\
+                      <script lang=\"ts\" generics=\"T extends Record<string, unknown>\">\n\
+                      let c: T;\n</script>\n";
+        let contents: Vec<&str> = script_ranges(source)
+            .iter()
+            .map(|r| &source[r.start_byte..r.end_byte])
+            .collect();
+        assert_eq!(contents, vec!["\nlet c: T;\n"]);
+    }
+
     #[test]
     fn parse_file_metadata() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -148,4 +382,62 @@ mod tests {
         assert_eq!(meta.line_count, 0);
         assert_eq!(meta.size_bytes, 0);
     }
+
+    #[test]
+    fn outline_source_reads_rust_snippet() {
+        let snippet: &[u8] = b"use std::fmt;\n\n/// Says hi.\npub fn greet(name: &str) {}\n";
+        let out = outline_source(snippet, Language::Rust, "<stdin>").unwrap();
+        assert_eq!(out["language"], "rust");
+        assert_eq!(out["has_errors"], false);
+        let greet = out["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["name"] == "greet")
+            .expect("greet symbol");
+        assert_eq!(greet["kind"], "function");
+        assert_eq!(greet["start_line"], 4);
+        assert_eq!(greet["exported"], true);
+        assert_eq!(out["imports"][0]["module"], "std::fmt");
+        assert_eq!(out["comments"][0]["associated_symbol"], "greet");
+    }
+
+    #[test]
+    fn outline_file_detects_the_language_from_the_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("tasks.py");
+        std::fs::write(
+            &file,
+            "import os\n\ndef run():\n    pass\n\nclass Job:\n    def start(self):\n        pass\n",
+        )
+        .unwrap();
+        let out = outline_file(&file, None).unwrap();
+        assert_eq!(out["language"], "python");
+        let names: Vec<&str> = out["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|s| s["kind"] != "parameter")
+            .map(|s| s["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"run"), "{names:?}");
+        assert!(names.contains(&"start"), "{names:?}");
+
+        let unknown = dir.path().join("notes.txt");
+        std::fs::write(&unknown, "hello\n").unwrap();
+        assert!(outline_file(&unknown, None).is_err());
+    }
+
+    #[test]
+    fn repeated_imports_are_deduplicated_per_file() {
+        let snippet: &[u8] = b"use a::b;\nuse a::{b, c};\n";
+        let out = outline_source(snippet, Language::Rust, "<stdin>").unwrap();
+        let modules: Vec<&str> = out["imports"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["module"].as_str().unwrap())
+            .collect();
+        assert_eq!(modules, vec!["a::b", "a::c"]);
+    }
 }
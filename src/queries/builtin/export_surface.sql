@@ -13,4 +13,4 @@ JOIN imports i ON i.imported_id = s.file_path
 JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path
 WHERE s.visibility = 'public'
   AND s.exported = true
-ORDER BY s.file_path, sp.start_line;
+ORDER BY s.file_path, sp.start_line, s.name, s.kind;
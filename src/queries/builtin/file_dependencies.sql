@@ -0,0 +1,30 @@
+-- file_dependencies — files reachable from $file by following resolved
+-- imports, up to $depth hops, each with its shortest distance.
+--
+-- Only resolved (internal) imports are followed; external packages
+-- never appear in `imports`. The walk keeps one row per (file,
+-- distance) pair rather than enumerating paths, so each hop costs at
+-- most one pass over `imports` however dense the graph is, and import
+-- cycles terminate at the hop limit.
+--
+-- Params:
+--   $file  — workspace-relative path of the root file.
+--   $depth — maximum number of hops (1 = direct imports only), capped
+--            at 64.
+
+WITH RECURSIVE deps(file, distance) AS (
+    SELECT i.imported_id, 1
+    FROM imports i
+    WHERE i.importer_file_id = $file
+  UNION
+    SELECT i.imported_id, deps.distance + 1
+    FROM deps
+    JOIN imports i ON i.importer_file_id = deps.file
+    WHERE deps.distance < LEAST($depth, 64)
+)
+SELECT file,
+       MIN(distance)::BIGINT AS distance
+FROM deps
+WHERE file <> $file
+GROUP BY file
+ORDER BY distance, file;
@@ -23,4 +23,4 @@ FROM GRAPH_TABLE (codegraph
 ) gt
 JOIN span sp
   ON sp.entity_id = gt.callee_id AND sp.file_path = gt.callee_file
-ORDER BY callee_file, callee_line;
+ORDER BY callee_file, callee_line, callee, caller;
@@ -28,4 +28,4 @@ FROM GRAPH_TABLE (codegraph
 ) gt
 JOIN span sp
   ON sp.entity_id = gt.caller_id AND sp.file_path = gt.caller_file
-ORDER BY caller_file, caller_line;
+ORDER BY caller_file, caller_line, caller, call_site_file;
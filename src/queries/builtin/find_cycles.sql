@@ -26,4 +26,4 @@ JOIN reach r2 ON r1.a_id = r2.b_id AND r1.b_id = r2.a_id
 JOIN symbol sa ON sa.id = r1.a_id
 JOIN symbol sb ON sb.id = r1.b_id
 WHERE r1.a_id < r1.b_id
-ORDER BY a_name, b_name;
+ORDER BY a_name, b_name, r1.a_id, r1.b_id;
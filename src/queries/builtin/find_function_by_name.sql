@@ -17,4 +17,4 @@ JOIN span sp
   ON sp.entity_id = s.id AND sp.file_path = s.file_path
 WHERE s.kind IN ('function', 'method')
   AND (s.name = $name OR s.qualified_name = $name)
-ORDER BY s.file_path, sp.start_line;
+ORDER BY s.file_path, sp.start_line, s.id;
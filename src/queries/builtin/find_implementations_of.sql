@@ -26,4 +26,4 @@ SELECT s.name,
 FROM derived d
 JOIN symbol s ON s.id = d.d_id
 JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path
-ORDER BY s.file_path, sp.start_line;
+ORDER BY s.file_path, sp.start_line, s.name, relation;
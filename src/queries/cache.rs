@@ -0,0 +1,218 @@
+//! Opt-in on-disk cache of formatted query results (`projects query
+//! --cache`).
+//!
+//! An entry is keyed by a hash of everything that shapes the output —
+//! the query text, template name or SQL file contents, params, args and
+//! paging — and stores a fingerprint of the workspace's source files
+//! (path, size, mtime) and of the store's `build_meta.built_at`. A
+//! lookup only hits when the fingerprint still matches, so editing,
+//! adding or removing any source file, or rebuilding the store (say with
+//! other `--symbol-kinds`), invalidates every entry of the project.
+//! Entries are plain JSON files named `<key>.json` under the cache
+//! directory.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::db::DbStore;
+use crate::db::store::stable_hash;
+use crate::storage::workspace::Workspace;
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    fingerprint: u64,
+    output: serde_json::Value,
+}
+
+pub struct QueryCache {
+    dir: PathBuf,
+}
+
+impl QueryCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `~/.cache/virgil/query-cache/<project-hash>/`, next to the
+    /// project's DuckDB store.
+    pub fn default_dir(project: &str) -> Result<PathBuf> {
+        let base = dirs::cache_dir()
+            .context("could not determine OS cache directory")?
+            .join("virgil")
+            .join("query-cache");
+        Ok(base.join(format!("{:016x}", stable_hash(project))))
+    }
+
+    /// The cached output for `key` if its fingerprint still matches,
+    /// else the result of `run`, which is stored for next time. The
+    /// flag is `true` on a hit. A corrupt or unreadable entry counts as
+    /// a miss.
+    pub fn get_or_run<F>(
+        &self,
+        key: u64,
+        fingerprint: u64,
+        run: F,
+    ) -> Result<(serde_json::Value, bool)>
+    where
+        F: FnOnce() -> Result<serde_json::Value>,
+    {
+        let path = self.entry_path(key);
+        if let Some(entry) = read_entry(&path)
+            && entry.fingerprint == fingerprint
+        {
+            return Ok((entry.output, true));
+        }
+        let output = run()?;
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("creating query cache dir {}", self.dir.display()))?;
+        let entry = Entry {
+            fingerprint,
+            output,
+        };
+        std::fs::write(&path, serde_json::to_vec(&entry)?)
+            .with_context(|| format!("writing query cache entry {}", path.display()))?;
+        Ok((entry.output, false))
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{key:016x}.json"))
+    }
+}
+
+fn read_entry(path: &Path) -> Option<Entry> {
+    let bytes = std::fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Cache key over the parts that determine a query's output. Parts are
+/// length-prefixed so `["ab", "c"]` and `["a", "bc"]` differ; the crate
+/// version is mixed in because built-in templates ship with the binary.
+pub fn key(parts: &[&str]) -> u64 {
+    let mut joined = format!("{}\n", env!("CARGO_PKG_VERSION"));
+    for part in parts {
+        joined.push_str(&format!("{}:{part}\n", part.len()));
+    }
+    stable_hash(&joined)
+}
+
+/// Hash of every workspace file's path, size and mtime, plus the time
+/// `store` was built. A workspace read from an archive has no files on
+/// disk, so the archive's own size and mtime stand in for them.
+pub fn fingerprint(workspace: &Workspace, store: &DbStore) -> Result<u64> {
+    let root = workspace.root();
+    let mut files: Vec<&String> = workspace.files().iter().collect();
+    files.sort();
+    let built_at = store.build_meta()?.remove("built_at").unwrap_or_default();
+    let mut joined = format!("{built_at}\n");
+    if root.is_file() {
+        joined.push_str(&format!("{}\n", size_and_mtime(root)));
+    }
+    for path in files {
+        joined.push_str(&format!("{path}\t{}\n", size_and_mtime(&root.join(path))));
+    }
+    Ok(stable_hash(&joined))
+}
+
+/// `size\tmtime` of `path`, zeros when it can't be read.
+fn size_and_mtime(path: &Path) -> String {
+    let meta = std::fs::metadata(path).ok();
+    let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+    let mtime = meta
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{size}\t{mtime}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    use crate::graph::builder::GraphBuilder;
+    use crate::language::Language;
+
+    #[test]
+    fn second_identical_query_is_served_from_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = QueryCache::new(dir.path());
+        let runs = Cell::new(0);
+        let run = || {
+            runs.set(runs.get() + 1);
+            Ok(serde_json::json!({"result": [[1]]}))
+        };
+        let k = key(&["sql", "SELECT 1"]);
+
+        let (first, hit) = cache.get_or_run(k, 7, run).unwrap();
+        assert!(!hit);
+        let (second, hit) = cache.get_or_run(k, 7, run).unwrap();
+        assert!(hit);
+        assert_eq!(first, second);
+        assert_eq!(runs.get(), 1);
+
+        let (_, hit) = cache.get_or_run(k, 8, run).unwrap();
+        assert!(!hit, "a changed fingerprint must invalidate the entry");
+        assert_eq!(runs.get(), 2);
+    }
+
+    fn built_store(ws: &Workspace, kinds: Option<&str>) -> DbStore {
+        let langs = [Language::Rust];
+        let store = DbStore::open_in_memory().unwrap();
+        let mut builder = GraphBuilder::new(ws, &langs);
+        if let Some(kinds) = kinds {
+            builder = builder.with_symbol_kinds(crate::models::parse_symbol_kinds(kinds).unwrap());
+        }
+        let graph = builder.build(&store).unwrap();
+        crate::db::populate(&store, &graph, Some(ws)).unwrap();
+        store
+    }
+
+    #[test]
+    fn fingerprint_changes_when_a_source_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+        let langs = [Language::Rust];
+        let store = built_store(&Workspace::load(dir.path(), &langs, None).unwrap(), None);
+        let before = fingerprint(&Workspace::load(dir.path(), &langs, None).unwrap(), &store);
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        let after = fingerprint(&Workspace::load(dir.path(), &langs, None).unwrap(), &store);
+        assert_ne!(before.unwrap(), after.unwrap());
+    }
+
+    #[test]
+    fn rebuilding_with_other_options_misses_the_cache() {
+        let src = tempfile::tempdir().unwrap();
+        std::fs::write(src.path().join("a.rs"), "pub struct A;\npub fn a() {}\n").unwrap();
+        let ws = Workspace::load(src.path(), &[Language::Rust], None).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let cache = QueryCache::new(dir.path());
+        let sql = "SELECT name FROM symbol ORDER BY name";
+        let k = key(&["sql", sql]);
+        let query = |store: &DbStore| {
+            let (output, hit) = cache
+                .get_or_run(k, fingerprint(&ws, store).unwrap(), || {
+                    let rows = store.run_query(sql, Default::default())?;
+                    Ok(serde_json::json!(rows.rows.len()))
+                })
+                .unwrap();
+            (output, hit)
+        };
+
+        let all = built_store(&ws, None);
+        assert_eq!(query(&all), (serde_json::json!(2), false));
+        assert_eq!(query(&all), (serde_json::json!(2), true));
+
+        // Same sources, rebuilt with `--symbol-kinds function`.
+        let functions = built_store(&ws, Some("function"));
+        assert_eq!(query(&functions), (serde_json::json!(1), false));
+    }
+
+    #[test]
+    fn key_separates_parts() {
+        assert_ne!(key(&["ab", "c"]), key(&["a", "bc"]));
+        assert_eq!(key(&["sql", "SELECT 1"]), key(&["sql", "SELECT 1"]));
+    }
+}
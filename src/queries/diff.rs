@@ -0,0 +1,393 @@
+//! Snapshot diff between two built fact stores.
+//!
+//! Backs `projects diff <before> <after>`: compares files, symbols and
+//! resolved file imports of two warm stores. Symbols are matched on
+//! `(file_path, qualified_name, kind)`; a matched symbol counts as
+//! modified when its line span length or `exported` flag changed, so a
+//! symbol that merely moved down the file is not reported.
+//!
+//! [`api_diff`] backs `projects api-diff`: the same matching restricted
+//! to exported symbols, comparing a signature rebuilt from the
+//! `parameter`/`returns_type` facts, with removals and signature changes
+//! classified as breaking.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Result, anyhow};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::db::DbStore;
+
+use super::runner::{value_to_i64, value_to_string};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct SymbolRecord {
+    pub file: String,
+    pub name: String,
+    pub kind: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    pub exported: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SymbolChange {
+    pub before: SymbolRecord,
+    pub after: SymbolRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, JsonSchema)]
+pub struct ImportEdge {
+    pub importer: String,
+    pub imported: String,
+}
+
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct DiffSummary {
+    pub files_added: usize,
+    pub files_removed: usize,
+    pub symbols_added: usize,
+    pub symbols_removed: usize,
+    pub symbols_modified: usize,
+    pub imports_added: usize,
+    pub imports_removed: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct StoreDiff {
+    pub summary: DiffSummary,
+    pub files_added: Vec<String>,
+    pub files_removed: Vec<String>,
+    pub symbols_added: Vec<SymbolRecord>,
+    pub symbols_removed: Vec<SymbolRecord>,
+    pub symbols_modified: Vec<SymbolChange>,
+    pub imports_added: Vec<ImportEdge>,
+    pub imports_removed: Vec<ImportEdge>,
+}
+
+type SymbolKey = (String, String, String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiChangeKind {
+    Added,
+    Removed,
+    SignatureChanged,
+}
+
+impl ApiChangeKind {
+    /// Removing or re-signing an export breaks its callers; adding one
+    /// doesn't.
+    pub fn is_breaking(self) -> bool {
+        !matches!(self, ApiChangeKind::Added)
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiChange {
+    pub change: ApiChangeKind,
+    pub breaking: bool,
+    pub file: String,
+    pub name: String,
+    pub kind: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiDiff {
+    pub breaking: usize,
+    pub changes: Vec<ApiChange>,
+}
+
+pub fn diff_stores(before: &DbStore, after: &DbStore) -> Result<StoreDiff> {
+    let (files_before, files_after) = (load_files(before)?, load_files(after)?);
+    let (symbols_before, symbols_after) = (load_symbols(before)?, load_symbols(after)?);
+    let (imports_before, imports_after) = (load_imports(before)?, load_imports(after)?);
+
+    let files_added: Vec<String> = files_after.difference(&files_before).cloned().collect();
+    let files_removed: Vec<String> = files_before.difference(&files_after).cloned().collect();
+
+    let mut symbols_added = Vec::new();
+    let mut symbols_modified = Vec::new();
+    for (key, after_sym) in &symbols_after {
+        match symbols_before.get(key) {
+            None => symbols_added.push(after_sym.clone()),
+            Some(before_sym) => {
+                let span = |s: &SymbolRecord| s.end_line - s.start_line;
+                if span(before_sym) != span(after_sym) || before_sym.exported != after_sym.exported
+                {
+                    symbols_modified.push(SymbolChange {
+                        before: before_sym.clone(),
+                        after: after_sym.clone(),
+                    });
+                }
+            }
+        }
+    }
+    let symbols_removed: Vec<SymbolRecord> = symbols_before
+        .iter()
+        .filter(|(key, _)| !symbols_after.contains_key(*key))
+        .map(|(_, sym)| sym.clone())
+        .collect();
+
+    let imports_added: Vec<ImportEdge> =
+        imports_after.difference(&imports_before).cloned().collect();
+    let imports_removed: Vec<ImportEdge> =
+        imports_before.difference(&imports_after).cloned().collect();
+
+    Ok(StoreDiff {
+        summary: DiffSummary {
+            files_added: files_added.len(),
+            files_removed: files_removed.len(),
+            symbols_added: symbols_added.len(),
+            symbols_removed: symbols_removed.len(),
+            symbols_modified: symbols_modified.len(),
+            imports_added: imports_added.len(),
+            imports_removed: imports_removed.len(),
+        },
+        files_added,
+        files_removed,
+        symbols_added,
+        symbols_removed,
+        symbols_modified,
+        imports_added,
+        imports_removed,
+    })
+}
+
+pub fn api_diff(before: &DbStore, after: &DbStore) -> Result<ApiDiff> {
+    let before = load_api(before)?;
+    let after = load_api(after)?;
+    let change =
+        |kind: ApiChangeKind, key: &SymbolKey, b: Option<&String>, a: Option<&String>| ApiChange {
+            change: kind,
+            breaking: kind.is_breaking(),
+            file: key.0.clone(),
+            name: key.1.clone(),
+            kind: key.2.clone(),
+            before: b.cloned(),
+            after: a.cloned(),
+        };
+
+    let mut changes = Vec::new();
+    for (key, sig) in &before {
+        match after.get(key) {
+            None => changes.push(change(ApiChangeKind::Removed, key, Some(sig), None)),
+            Some(new_sig) if new_sig != sig => changes.push(change(
+                ApiChangeKind::SignatureChanged,
+                key,
+                Some(sig),
+                Some(new_sig),
+            )),
+            Some(_) => {}
+        }
+    }
+    for (key, sig) in &after {
+        if !before.contains_key(key) {
+            changes.push(change(ApiChangeKind::Added, key, None, Some(sig)));
+        }
+    }
+    changes.sort_by(|a, b| (&a.file, &a.name, &a.kind).cmp(&(&b.file, &b.name, &b.kind)));
+
+    Ok(ApiDiff {
+        breaking: changes.iter().filter(|c| c.breaking).count(),
+        changes,
+    })
+}
+
+/// Exported symbols with a normalised signature: `(name: Type, opt?) ->
+/// Ret`. Parameters come from the `parameter` facts when the language's
+/// type extractor emits them, else from `parameter`-kind symbols. Types
+/// are omitted where none were recorded.
+fn load_api(store: &DbStore) -> Result<BTreeMap<SymbolKey, String>> {
+    let rows = store
+        .run_query(
+            "SELECT s.file_path, s.qualified_name, s.kind, \
+                    COALESCE( \
+                        (SELECT string_agg(p.name \
+                                || CASE WHEN p.is_optional OR p.has_default THEN '?' ELSE '' END \
+                                || COALESCE(': ' || t.display_name, ''), \
+                                ', ' ORDER BY p.position) \
+                         FROM parameter p LEFT JOIN type t ON t.id = p.type_id \
+                         WHERE p.function_id = s.id), \
+                        (SELECT string_agg(ps.name, ', ' ORDER BY psp.start_byte) \
+                         FROM symbol ps \
+                         JOIN span psp ON psp.entity_id = ps.id AND psp.file_path = ps.file_path \
+                         WHERE ps.parent_id = s.id AND ps.kind = 'parameter'), \
+                        '') AS params, \
+                    (SELECT t.display_name FROM returns_type r \
+                     JOIN type t ON t.id = r.type_id WHERE r.function_id = s.id) AS ret \
+             FROM symbol s \
+             WHERE s.exported = true AND s.kind <> 'parameter' \
+             ORDER BY s.file_path, s.qualified_name, s.kind, s.id",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query exported symbols: {e}"))?;
+
+    let mut api = BTreeMap::new();
+    for row in rows.rows {
+        let (Some(file), Some(name), Some(kind)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_string(&row[2]),
+        ) else {
+            continue;
+        };
+        let signature = if matches!(
+            kind.as_str(),
+            "function" | "method" | "arrow_function" | "component"
+        ) {
+            let params = value_to_string(&row[3]).unwrap_or_default();
+            match value_to_string(&row[4]) {
+                Some(ret) => format!("({params}) -> {ret}"),
+                None => format!("({params})"),
+            }
+        } else {
+            kind.clone()
+        };
+        api.entry((file, name, kind)).or_insert(signature);
+    }
+    Ok(api)
+}
+
+fn load_files(store: &DbStore) -> Result<BTreeSet<String>> {
+    let rows = store
+        .run_query("SELECT path FROM file", BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query files: {e}"))?;
+    Ok(rows
+        .rows
+        .iter()
+        .filter_map(|r| value_to_string(&r[0]))
+        .collect())
+}
+
+/// Parameters are left out: they change with every signature edit and
+/// the owning function already reports as modified when its span moves.
+fn load_symbols(store: &DbStore) -> Result<BTreeMap<SymbolKey, SymbolRecord>> {
+    let rows = store
+        .run_query(
+            "SELECT s.file_path, s.qualified_name, s.kind, sp.start_line, sp.end_line, s.exported \
+             FROM symbol s \
+             JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+             WHERE s.kind <> 'parameter' \
+             ORDER BY s.file_path, sp.start_byte, s.id",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
+    let mut symbols = BTreeMap::new();
+    for row in rows.rows {
+        let (Some(file), Some(name), Some(kind)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_string(&row[2]),
+        ) else {
+            continue;
+        };
+        // Same-named symbols of one kind in one file (overloads,
+        // redefinitions) collapse onto the first declaration.
+        symbols
+            .entry((file.clone(), name.clone(), kind.clone()))
+            .or_insert(SymbolRecord {
+                file,
+                name,
+                kind,
+                start_line: value_to_i64(&row[3]).unwrap_or(0),
+                end_line: value_to_i64(&row[4]).unwrap_or(0),
+                exported: matches!(row[5], duckdb::types::Value::Boolean(true)),
+            });
+    }
+    Ok(symbols)
+}
+
+fn load_imports(store: &DbStore) -> Result<BTreeSet<ImportEdge>> {
+    let rows = store
+        .run_query(
+            "SELECT importer_file_id, imported_id FROM imports",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query imports: {e}"))?;
+    Ok(rows
+        .rows
+        .iter()
+        .filter_map(|r| {
+            Some(ImportEdge {
+                importer: value_to_string(&r[0])?,
+                imported: value_to_string(&r[1])?,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use crate::graph::builder::GraphBuilder;
+    use crate::language::Language;
+    use crate::storage::workspace::Workspace;
+
+    fn build(files: &[(&str, &str)]) -> DbStore {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, body) in files {
+            std::fs::write(dir.path().join(name), body).unwrap();
+        }
+        let langs = [Language::Rust];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        db::populate(&store, &graph, Some(&ws)).unwrap();
+        store
+    }
+
+    #[test]
+    fn reports_added_function_and_removed_export() {
+        let before = build(&[("lib.rs", "pub fn keep() {}\npub fn api() {}\n")]);
+        let after = build(&[(
+            "lib.rs",
+            "pub fn keep() {}\nfn api() {}\npub fn added() {}\n",
+        )]);
+        let diff = diff_stores(&before, &after).unwrap();
+
+        assert_eq!(diff.summary.symbols_added, 1);
+        assert_eq!(diff.symbols_added[0].name, "added");
+        assert!(diff.symbols_removed.is_empty());
+        assert_eq!(diff.symbols_modified.len(), 1);
+        let change = &diff.symbols_modified[0];
+        assert_eq!(change.after.name, "api");
+        assert!(change.before.exported);
+        assert!(!change.after.exported);
+        assert!(diff.files_added.is_empty() && diff.files_removed.is_empty());
+    }
+
+    #[test]
+    fn reports_added_and_removed_files() {
+        let before = build(&[("a.rs", "fn a() {}\n")]);
+        let after = build(&[("b.rs", "fn b() {}\n")]);
+        let diff = diff_stores(&before, &after).unwrap();
+        assert_eq!(diff.files_added, vec!["b.rs".to_string()]);
+        assert_eq!(diff.files_removed, vec!["a.rs".to_string()]);
+        assert_eq!(diff.summary.symbols_added, 1);
+        assert_eq!(diff.summary.symbols_removed, 1);
+    }
+
+    #[test]
+    fn api_diff_flags_parameter_change_as_breaking() {
+        let before = build(&[("lib.rs", "pub fn api(a: i32) {}\nfn private(a: i32) {}\n")]);
+        let after = build(&[(
+            "lib.rs",
+            "pub fn api(a: i32, b: i32) {}\nfn private() {}\npub fn added() {}\n",
+        )]);
+        let diff = api_diff(&before, &after).unwrap();
+
+        assert_eq!(diff.breaking, 1, "{diff:?}");
+        let api = diff.changes.iter().find(|c| c.name == "api").unwrap();
+        assert_eq!(api.change, ApiChangeKind::SignatureChanged);
+        assert!(api.breaking);
+        let added = diff.changes.iter().find(|c| c.name == "added").unwrap();
+        assert_eq!(added.change, ApiChangeKind::Added);
+        assert!(!added.breaking);
+        assert!(diff.changes.iter().all(|c| c.name != "private"));
+    }
+}
@@ -0,0 +1,86 @@
+//! JSON Schema documents for command output (`json-schema <command>`).
+//!
+//! Generated with `schemars` from the same `Serialize` structs the
+//! commands print, so they can't drift from the real output. Each
+//! schema describes the command's payload: the `result` field of the
+//! `projects query` / `diff` / `api-diff` / `read` envelope, or the
+//! `tables` field of `projects schema`.
+
+use anyhow::Result;
+use schemars::schema_for;
+
+use super::diff::{ApiDiff, StoreDiff};
+use super::read::FileExcerpt;
+use super::runner::QueryOutput;
+use super::schema::TableStats;
+
+/// Commands whose output has a schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputKind {
+    Query,
+    Diff,
+    ApiDiff,
+    Schema,
+    Read,
+}
+
+/// The JSON Schema of `kind`'s payload.
+pub fn output_schema(kind: OutputKind) -> Result<serde_json::Value> {
+    let schema = match kind {
+        OutputKind::Query => schema_for!(QueryOutput),
+        OutputKind::Diff => schema_for!(StoreDiff),
+        OutputKind::ApiDiff => schema_for!(ApiDiff),
+        OutputKind::Schema => schema_for!(Vec<TableStats>),
+        OutputKind::Read => schema_for!(FileExcerpt),
+    };
+    Ok(serde_json::to_value(schema)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every property name declared anywhere in `schema`, definitions
+    /// included.
+    fn property_names(schema: &serde_json::Value, out: &mut Vec<String>) {
+        match schema {
+            serde_json::Value::Object(map) => {
+                if let Some(serde_json::Value::Object(props)) = map.get("properties") {
+                    out.extend(props.keys().cloned());
+                }
+                for value in map.values() {
+                    property_names(value, out);
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    property_names(item, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn query_schema_lists_finding_row_and_outline_fields() {
+        let schema = output_schema(OutputKind::Query).unwrap();
+        let mut names = Vec::new();
+        property_names(&schema, &mut names);
+        for field in [
+            "file", "line", "severity", "pattern", "message", "headers", "rows", "children",
+        ] {
+            assert!(
+                names.iter().any(|n| n == field),
+                "{field} missing: {names:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn api_diff_schema_names_change_kinds() {
+        let schema = output_schema(OutputKind::ApiDiff).unwrap();
+        let text = schema.to_string();
+        assert!(text.contains("signature_changed"), "{text}");
+        assert!(text.contains("\"breaking\""), "{text}");
+    }
+}
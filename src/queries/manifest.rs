@@ -0,0 +1,237 @@
+//! Declared dependency versions from a `package.json` or `Cargo.toml`.
+//!
+//! Backs the `manifest` param of the `imports` template: each import's
+//! package is looked up here so external modules carry the version the
+//! project declares for them. Cargo manifests are read with the
+//! tree-sitter TOML grammar the indexer already ships, so no TOML crate
+//! is needed.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::language::Language;
+
+/// `package.json` sections holding dependency versions, in lookup order.
+const NPM_SECTIONS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Cargo tables holding dependency versions. Target-specific tables
+/// (`[target.'cfg(unix)'.dependencies]`) are matched by suffix.
+const CARGO_TABLES: &[&str] = &[
+    "dependencies",
+    "dev-dependencies",
+    "build-dependencies",
+    "workspace.dependencies",
+];
+
+#[derive(Debug, Default)]
+pub struct Manifest {
+    versions: HashMap<String, String>,
+}
+
+impl Manifest {
+    /// Load `path`, dispatching on its file name.
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read manifest {}", path.display()))?;
+        match path.file_name().and_then(|n| n.to_str()) {
+            Some("package.json") => Self::from_package_json(&source),
+            Some("Cargo.toml") => Self::from_cargo_toml(&source),
+            _ => Err(anyhow!(
+                "unsupported manifest {}: expected package.json or Cargo.toml",
+                path.display()
+            )),
+        }
+    }
+
+    pub fn from_package_json(source: &str) -> Result<Self> {
+        let json: serde_json::Value =
+            serde_json::from_str(source).context("failed to parse package.json")?;
+        let mut versions = HashMap::new();
+        for section in NPM_SECTIONS {
+            let Some(deps) = json.get(section).and_then(|d| d.as_object()) else {
+                continue;
+            };
+            for (name, version) in deps {
+                if let Some(version) = version.as_str() {
+                    versions
+                        .entry(name.clone())
+                        .or_insert_with(|| version.to_string());
+                }
+            }
+        }
+        Ok(Manifest { versions })
+    }
+
+    pub fn from_cargo_toml(source: &str) -> Result<Self> {
+        let mut parser = crate::parser::create_parser(Language::Toml)?;
+        let tree = parser
+            .parse(source.as_bytes(), None)
+            .ok_or_else(|| anyhow!("failed to parse Cargo.toml"))?;
+        let source = source.as_bytes();
+
+        let mut versions = HashMap::new();
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        for table in root.named_children(&mut cursor) {
+            if table.kind() != "table" {
+                continue;
+            }
+            let Some(header) = table.named_child(0) else {
+                continue;
+            };
+            let header = key_text(header, source);
+            if is_dependency_table(&header) {
+                // `[dependencies]`: one pair per crate.
+                let mut pairs = table.walk();
+                for pair in table.named_children(&mut pairs) {
+                    if pair.kind() != "pair" {
+                        continue;
+                    }
+                    let (Some(key), Some(value)) = (pair.named_child(0), pair.named_child(1))
+                    else {
+                        continue;
+                    };
+                    if let Some(version) = dependency_version(value, source) {
+                        versions.entry(key_text(key, source)).or_insert(version);
+                    }
+                }
+            } else if let Some((prefix, name)) = header.rsplit_once('.')
+                && is_dependency_table(prefix)
+            {
+                // `[dependencies.serde]`: the crate's fields as pairs.
+                if let Some(version) = dependency_version(table, source) {
+                    versions.entry(name.to_string()).or_insert(version);
+                }
+            }
+        }
+        Ok(Manifest { versions })
+    }
+
+    /// Declared version of `package`. Names are also tried with `_`
+    /// spelled `-`: the `tree-sitter` crate is `tree_sitter` in a path.
+    pub fn version_of(&self, package: &str) -> Option<&str> {
+        self.versions
+            .get(package)
+            .or_else(|| self.versions.get(&package.replace('_', "-")))
+            .map(String::as_str)
+    }
+}
+
+/// The package an import specifier belongs to, or `None` for relative
+/// and crate-local paths. `@scope/pkg/sub` → `@scope/pkg`, `lodash/fp`
+/// → `lodash`, `serde::Serialize` → `serde`, `os.path` → `os`.
+pub fn package_name(specifier: &str, language: Language) -> Option<&str> {
+    if language == Language::Rust {
+        let root = specifier.split("::").next()?.trim();
+        return match root {
+            "" | "crate" | "self" | "super" => None,
+            _ => Some(root),
+        };
+    }
+    if specifier.is_empty() || specifier.starts_with(['.', '/', '#']) {
+        return None;
+    }
+    if language == Language::Python {
+        return specifier.split('.').next();
+    }
+    let end = if specifier.starts_with('@') {
+        specifier
+            .match_indices('/')
+            .nth(1)
+            .map_or(specifier.len(), |(i, _)| i)
+    } else {
+        specifier.find('/').unwrap_or(specifier.len())
+    };
+    Some(&specifier[..end])
+}
+
+fn is_dependency_table(header: &str) -> bool {
+    CARGO_TABLES.contains(&header)
+        || CARGO_TABLES
+            .iter()
+            .any(|t| header.starts_with("target.") && header.ends_with(&format!(".{t}")))
+}
+
+/// `"1.0"` directly, or the `version` field of an inline table or of a
+/// `[dependencies.name]` table. Path and git dependencies have none.
+fn dependency_version(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    if node.kind() == "string" {
+        return Some(string_text(node, source));
+    }
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|pair| pair.kind() == "pair")
+        .find_map(|pair| {
+            let key = pair.named_child(0)?;
+            let value = pair.named_child(1)?;
+            (key_text(key, source) == "version" && value.kind() == "string")
+                .then(|| string_text(value, source))
+        })
+}
+
+/// Key or string text with quotes and whitespace around dotted parts
+/// removed (`"serde" . 'x'` → `serde.x`).
+fn key_text(node: tree_sitter::Node, source: &[u8]) -> String {
+    node.utf8_text(source)
+        .unwrap_or("")
+        .split('.')
+        .map(|part| part.trim().trim_matches(|c| c == '"' || c == '\''))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn string_text(node: tree_sitter::Node, source: &[u8]) -> String {
+    node.utf8_text(source)
+        .unwrap_or("")
+        .trim_matches(|c| c == '"' || c == '\'')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_name_strips_subpaths() {
+        assert_eq!(
+            package_name("@scope/pkg/sub", Language::TypeScript),
+            Some("@scope/pkg")
+        );
+        assert_eq!(
+            package_name("lodash/fp", Language::JavaScript),
+            Some("lodash")
+        );
+        assert_eq!(package_name("./util", Language::TypeScript), None);
+        assert_eq!(
+            package_name("serde::Serialize", Language::Rust),
+            Some("serde")
+        );
+        assert_eq!(package_name("crate::db", Language::Rust), None);
+        assert_eq!(package_name("os.path", Language::Python), Some("os"));
+    }
+
+    #[test]
+    fn cargo_toml_versions_from_every_table_shape() {
+        let manifest = Manifest::from_cargo_toml(
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n\
+             [dependencies]\nanyhow = \"1\"\nserde = { version = \"1.0\", features = [\"derive\"] }\n\
+             local = { path = \"../local\" }\n\n\
+             [dependencies.tree-sitter]\nversion = \"0.25\"\n\n\
+             [dev-dependencies]\ntempfile = \"3\"\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.version_of("anyhow"), Some("1"));
+        assert_eq!(manifest.version_of("serde"), Some("1.0"));
+        assert_eq!(manifest.version_of("tree_sitter"), Some("0.25"));
+        assert_eq!(manifest.version_of("tempfile"), Some("3"));
+        assert_eq!(manifest.version_of("local"), None);
+        assert_eq!(manifest.version_of("app"), None);
+    }
+}
@@ -10,8 +10,14 @@
 //! cannot be expressed as pure Cozoscript (complexity_hotspots,
 //! taint_paths, unreleased_resources).
 
+pub mod cache;
+pub mod diff;
+pub mod json_schema;
+pub mod manifest;
+pub mod read;
 pub mod runner;
 pub mod rust_templates;
+pub mod schema;
 pub mod templates;
 
-pub use runner::{QueryRequest, QuerySource, run};
+pub use runner::{Page, QueryRequest, QuerySource, render_json, run, run_count, run_page};
@@ -0,0 +1,160 @@
+//! Read a workspace file, or a line range of it, by its stored path.
+//!
+//! Backs `projects read <name> <path>`. Paths are workspace-relative,
+//! exactly as they appear in `file.path` / `symbol.file_path`, and are
+//! joined against the root the store was built from (see
+//! [`DbStore::build_root`](crate::db::DbStore::build_root)) unless the
+//! caller passes another one. Absolute paths, as `projects export
+//! --absolute-paths` writes them, are accepted when they lie under that
+//! root.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FileExcerpt {
+    pub path: String,
+    pub root: PathBuf,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub total_lines: usize,
+    pub content: String,
+}
+
+/// Lines `start_line..=end_line` (1-based, both optional) of `root/path`,
+/// or of `path` itself when it is absolute and under `root`. A range
+/// past the end of the file is clamped to it.
+pub fn read_excerpt(
+    root: &Path,
+    path: &str,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+) -> Result<FileExcerpt> {
+    let mut relative = Path::new(path);
+    if relative.is_absolute() {
+        relative = relative
+            .strip_prefix(root)
+            .map_err(|_| anyhow!("'{path}' is not under the project root {}", root.display()))?;
+    }
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        bail!("'{path}' must be a workspace-relative path without '..'");
+    }
+    let full = root.join(relative);
+    let bytes =
+        std::fs::read(&full).with_context(|| format!("failed to read {}", full.display()))?;
+    let source = String::from_utf8_lossy(&bytes);
+
+    let lines: Vec<&str> = source.lines().collect();
+    let total_lines = lines.len();
+    let start_line = start_line.unwrap_or(1).max(1);
+    let end_line = end_line.unwrap_or(total_lines).min(total_lines);
+    if start_line > end_line && total_lines > 0 {
+        bail!("empty line range {start_line}-{end_line} ({path} has {total_lines} lines)");
+    }
+    let content = lines
+        .get(start_line - 1..end_line)
+        .unwrap_or_default()
+        .join("\n");
+
+    Ok(FileExcerpt {
+        path: path.to_string(),
+        root: root.to_path_buf(),
+        start_line,
+        end_line,
+        total_lines,
+        content,
+    })
+}
+
+impl FileExcerpt {
+    /// Prefix every line of `content` with its 1-based line number,
+    /// right-aligned to the widest number in range (`projects read
+    /// --numbers`). With `highlight`, that line's gutter starts with `>`
+    /// and the others with a space so the columns stay aligned.
+    pub fn number_lines(&mut self, highlight: Option<usize>) {
+        if self.start_line > self.end_line {
+            return;
+        }
+        let width = self.end_line.to_string().len();
+        let numbered: Vec<String> = self
+            .content
+            .split('\n')
+            .zip(self.start_line..)
+            .map(|(line, n)| {
+                let marker = match highlight {
+                    Some(h) if h == n => ">",
+                    Some(_) => " ",
+                    None => "",
+                };
+                format!("{marker}{n:>width$} | {line}")
+            })
+            .collect();
+        self.content = numbered.join("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{self, DbStore};
+    use crate::graph::builder::GraphBuilder;
+    use crate::language::Language;
+    use crate::storage::workspace::Workspace;
+
+    #[test]
+    fn stored_root_round_trips_and_resolves_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "fn a() {}\nfn b() {}\nfn c() {}\n",
+        )
+        .unwrap();
+        let langs = [Language::Rust];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        db::populate(&store, &graph, Some(&ws)).unwrap();
+
+        let root = store.build_root().unwrap().expect("root recorded");
+        assert_eq!(root, dir.path().canonicalize().unwrap());
+
+        let excerpt = read_excerpt(&root, "src/lib.rs", Some(2), Some(5)).unwrap();
+        assert_eq!(excerpt.content, "fn b() {}\nfn c() {}");
+        assert_eq!((excerpt.start_line, excerpt.end_line), (2, 3));
+        assert_eq!(excerpt.total_lines, 3);
+
+        assert!(read_excerpt(&root, "../outside.rs", None, None).is_err());
+
+        let absolute = root.join("src/lib.rs");
+        let excerpt = read_excerpt(&root, &absolute.to_string_lossy(), Some(1), Some(1)).unwrap();
+        assert_eq!(excerpt.content, "fn a() {}");
+        let elsewhere = tempfile::tempdir().unwrap();
+        let outside = elsewhere.path().join("lib.rs");
+        assert!(read_excerpt(&root, &outside.to_string_lossy(), None, None).is_err());
+    }
+
+    #[test]
+    fn gutter_numbers_follow_the_requested_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let source: String = (1..=12).map(|i| format!("line {i}\n")).collect();
+        std::fs::write(dir.path().join("a.rs"), source).unwrap();
+
+        let mut excerpt = read_excerpt(dir.path(), "a.rs", Some(8), Some(11)).unwrap();
+        excerpt.number_lines(None);
+        assert_eq!(
+            excerpt.content,
+            " 8 | line 8\n 9 | line 9\n10 | line 10\n11 | line 11"
+        );
+
+        let mut excerpt = read_excerpt(dir.path(), "a.rs", Some(2), Some(3)).unwrap();
+        excerpt.number_lines(Some(3));
+        assert_eq!(excerpt.content, " 2 | line 2\n>3 | line 3");
+    }
+}
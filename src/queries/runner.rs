@@ -9,6 +9,7 @@ use std::collections::BTreeMap;
 
 use anyhow::{Context, Result, anyhow};
 use duckdb::types::Value;
+use schemars::JsonSchema;
 use serde::Serialize;
 use tracing::{debug, info};
 
@@ -28,11 +29,28 @@ pub enum QuerySource<'a> {
 pub struct QueryRequest<'a> {
     pub source: QuerySource<'a>,
     pub params: Vec<(String, String)>,
+    /// Values bound to positional `?` placeholders, in order. SQL only;
+    /// Rust-side templates reject them.
+    pub args: Vec<String>,
     pub store: &'a DbStore,
     pub workspace: &'a Workspace,
 }
 
-#[derive(Debug, Serialize)]
+/// `--limit` / `--offset` window over a query's result.
+#[derive(Debug, Clone, Copy)]
+pub struct Page {
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// One [`Page`] of a query's output plus the size of the full result.
+#[derive(Debug)]
+pub struct PagedOutput {
+    pub output: QueryOutput,
+    pub total_count: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum QueryOutput {
     Findings(Vec<AuditFinding>),
@@ -40,9 +58,14 @@ pub enum QueryOutput {
         headers: Vec<String>,
         rows: Vec<Vec<serde_json::Value>>,
     },
+    /// Symbol tree produced by the `outline` template with `nested=true`.
+    Outline(Vec<OutlineNode>),
+    /// The same tree as LSP `DocumentSymbol`s, from `outline` with
+    /// `lsp=true`.
+    DocumentSymbols(Vec<DocumentSymbol>),
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AuditFinding {
     pub file: String,
     pub line: i64,
@@ -53,6 +76,50 @@ pub struct AuditFinding {
     pub extras: Vec<(String, serde_json::Value)>,
 }
 
+/// One symbol in a nested outline. `children` are the symbols whose
+/// `parent_id` points at this one, in declaration order.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct OutlineNode {
+    pub name: String,
+    pub kind: String,
+    pub start_line: i64,
+    pub end_line: i64,
+    /// First line of the symbol's doc comment, with `with_comments=true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    pub children: Vec<OutlineNode>,
+    /// Byte columns of the span's ends, kept for the LSP shape.
+    #[serde(skip)]
+    pub start_col: i64,
+    #[serde(skip)]
+    pub end_col: i64,
+}
+
+/// An LSP `DocumentSymbol`. `detail` holds virgil's own kind name;
+/// positions are 0-based, with columns in UTF-16 code units.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentSymbol {
+    pub name: String,
+    pub detail: String,
+    pub kind: u8,
+    pub range: LspRange,
+    pub selection_range: LspRange,
+    pub children: Vec<DocumentSymbol>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct LspRange {
+    pub start: LspPosition,
+    pub end: LspPosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct LspPosition {
+    pub line: i64,
+    pub character: i64,
+}
+
 pub fn run(req: QueryRequest<'_>) -> Result<QueryOutput> {
     let source_kind = match &req.source {
         QuerySource::Inline(_) => "inline",
@@ -68,6 +135,11 @@ pub fn run(req: QueryRequest<'_>) -> Result<QueryOutput> {
     if let QuerySource::Template(name) = &req.source
         && let Some(handler) = rust_templates::lookup(name)
     {
+        if !req.args.is_empty() {
+            return Err(anyhow!(
+                "template '{name}' takes --param key=value, not positional --arg values"
+            ));
+        }
         let param_map = params_to_btree(&req.params);
         let out = handler(&rust_templates::Context {
             store: req.store,
@@ -78,7 +150,106 @@ pub fn run(req: QueryRequest<'_>) -> Result<QueryOutput> {
         return Ok(out);
     }
 
-    let script = match req.source {
+    let script = load_script(&req.source)?;
+    let params = params_to_values(&req.params);
+    let args: Vec<Value> = req.args.iter().map(|v| coerce_value(v)).collect();
+    let rows = req
+        .store
+        .run_query_with_args(&script, params, &args)
+        .with_context(|| "running sql")?;
+
+    let out = rows_to_output(rows.headers, rows.rows);
+    log_output_summary(&out);
+    Ok(out)
+}
+
+/// Like [`run`], but return only `page` of the result with the total
+/// row count. SQL is wrapped so DuckDB applies `LIMIT`/`OFFSET` and a
+/// separate `COUNT(*)` over the same query (same params and args)
+/// supplies the total; Rust-side templates are run in full and sliced.
+pub fn run_page(req: QueryRequest<'_>, page: Page) -> Result<PagedOutput> {
+    if let QuerySource::Template(name) = &req.source
+        && rust_templates::lookup(name).is_some()
+    {
+        let (output, total_count) = slice_output(run(req)?, page);
+        return Ok(PagedOutput {
+            output,
+            total_count,
+        });
+    }
+
+    let script = load_script(&req.source)?;
+    let inner = script.trim().trim_end_matches(';');
+    let params = params_to_values(&req.params);
+    let args: Vec<Value> = req.args.iter().map(|v| coerce_value(v)).collect();
+
+    let total_count = count_sql(req.store, inner, params.clone(), &args)?;
+
+    let rows = req
+        .store
+        .run_query_with_args(
+            &format!(
+                "SELECT * FROM (\n{inner}\n) AS paged LIMIT {} OFFSET {}",
+                page.limit, page.offset
+            ),
+            params,
+            &args,
+        )
+        .with_context(|| "running sql")?;
+    let output = rows_to_output(rows.headers, rows.rows);
+    log_output_summary(&output);
+    Ok(PagedOutput {
+        output,
+        total_count,
+    })
+}
+
+/// Number of rows (findings, top-level outline nodes) [`run`] would
+/// return, without materialising them: SQL is wrapped in a `COUNT(*)`
+/// with the same params and args. Rust-side templates are run in full
+/// and their output counted.
+pub fn run_count(req: QueryRequest<'_>) -> Result<usize> {
+    if let QuerySource::Template(name) = &req.source
+        && rust_templates::lookup(name).is_some()
+    {
+        return Ok(output_len(&run(req)?));
+    }
+    let script = load_script(&req.source)?;
+    let args: Vec<Value> = req.args.iter().map(|v| coerce_value(v)).collect();
+    let count = count_sql(
+        req.store,
+        script.trim().trim_end_matches(';'),
+        params_to_values(&req.params),
+        &args,
+    )?;
+    info!(count, "query complete");
+    Ok(count)
+}
+
+fn count_sql(
+    store: &DbStore,
+    inner: &str,
+    params: BTreeMap<String, Value>,
+    args: &[Value],
+) -> Result<usize> {
+    let count = store
+        .run_query_with_args(
+            &format!("SELECT COUNT(*) FROM (\n{inner}\n) AS counted"),
+            params,
+            args,
+        )
+        .with_context(|| "counting sql result")?;
+    Ok(count
+        .rows
+        .first()
+        .and_then(|row| row.first())
+        .and_then(value_to_i64)
+        .unwrap_or(0)
+        .max(0) as usize)
+}
+
+fn load_script(source: &QuerySource<'_>) -> Result<String> {
+    Ok(match source {
         QuerySource::Inline(s) => s.to_string(),
         QuerySource::File(path) => {
             std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?
@@ -86,49 +257,86 @@ pub fn run(req: QueryRequest<'_>) -> Result<QueryOutput> {
         QuerySource::Template(name) => templates::load_sql_template(name)
             .ok_or_else(|| anyhow!("unknown template '{name}'"))?
             .to_string(),
-    };
+    })
+}
 
-    let params = params_to_values(&req.params);
-    let rows = req
-        .store
-        .run_query(&script, params)
-        .with_context(|| "running sql")?;
+fn output_len(output: &QueryOutput) -> usize {
+    match output {
+        QueryOutput::Findings(findings) => findings.len(),
+        QueryOutput::Rows { rows, .. } => rows.len(),
+        QueryOutput::Outline(nodes) => nodes.len(),
+        QueryOutput::DocumentSymbols(symbols) => symbols.len(),
+    }
+}
 
-    let out = rows_to_output(rows.headers, rows.rows);
-    log_output_summary(&out);
-    Ok(out)
+/// Keep `page` of an already-computed output; the total is its length
+/// (top-level nodes for a nested outline).
+fn slice_output(output: QueryOutput, page: Page) -> (QueryOutput, usize) {
+    fn window<T>(items: Vec<T>, page: Page) -> Vec<T> {
+        items
+            .into_iter()
+            .skip(page.offset)
+            .take(page.limit)
+            .collect()
+    }
+    match output {
+        QueryOutput::Findings(findings) => {
+            let total = findings.len();
+            (QueryOutput::Findings(window(findings, page)), total)
+        }
+        QueryOutput::Rows { headers, rows } => {
+            let total = rows.len();
+            let rows = window(rows, page);
+            (QueryOutput::Rows { headers, rows }, total)
+        }
+        QueryOutput::Outline(nodes) => {
+            let total = nodes.len();
+            (QueryOutput::Outline(window(nodes, page)), total)
+        }
+        QueryOutput::DocumentSymbols(symbols) => {
+            let total = symbols.len();
+            (QueryOutput::DocumentSymbols(window(symbols, page)), total)
+        }
+    }
 }
 
 fn log_output_summary(out: &QueryOutput) {
     match out {
         QueryOutput::Findings(f) => info!(findings = f.len(), "query complete"),
         QueryOutput::Rows { rows, .. } => info!(rows = rows.len(), "query complete"),
+        QueryOutput::Outline(nodes) => info!(roots = nodes.len(), "query complete"),
+        QueryOutput::DocumentSymbols(symbols) => {
+            info!(roots = symbols.len(), "query complete")
+        }
     }
 }
 
-/// Convert raw `--param k=v` pairs into typed DuckDB values. Auto-coerce
-/// integers and booleans; everything else binds as text.
+/// Convert raw `--param k=v` pairs into typed DuckDB values.
 fn params_to_values(params: &[(String, String)]) -> BTreeMap<String, Value> {
-    let mut out = BTreeMap::new();
-    for (k, v) in params {
-        if let Ok(i) = v.parse::<i64>() {
-            out.insert(k.clone(), Value::BigInt(i));
-        } else if v == "true" {
-            out.insert(k.clone(), Value::Boolean(true));
-        } else if v == "false" {
-            out.insert(k.clone(), Value::Boolean(false));
-        } else {
-            out.insert(k.clone(), Value::Text(v.clone()));
-        }
+    params
+        .iter()
+        .map(|(k, v)| (k.clone(), coerce_value(v)))
+        .collect()
+}
+
+/// Auto-coerce integers and booleans; everything else binds as text.
+fn coerce_value(v: &str) -> Value {
+    if let Ok(i) = v.parse::<i64>() {
+        Value::BigInt(i)
+    } else if v == "true" {
+        Value::Boolean(true)
+    } else if v == "false" {
+        Value::Boolean(false)
+    } else {
+        Value::Text(v.to_string())
     }
-    out
 }
 
 fn params_to_btree(params: &[(String, String)]) -> BTreeMap<String, String> {
     params.iter().cloned().collect()
 }
 
-fn rows_to_output(headers: Vec<String>, rows: Vec<Vec<Value>>) -> QueryOutput {
+pub(crate) fn rows_to_output(headers: Vec<String>, rows: Vec<Vec<Value>>) -> QueryOutput {
     const AUDIT_COLS: &[&str] = &["file", "line", "severity", "pattern", "message"];
     let audit_indices: Option<Vec<usize>> = AUDIT_COLS
         .iter()
@@ -197,6 +405,16 @@ pub fn value_to_i64(v: &Value) -> Option<i64> {
     }
 }
 
+/// Serialise query output for stdout. Compact (single-line) unless
+/// `pretty` — machine consumers pipe this, so indentation is opt-in.
+pub fn render_json(value: &serde_json::Value, pretty: bool) -> Result<String> {
+    Ok(if pretty {
+        serde_json::to_string_pretty(value)?
+    } else {
+        serde_json::to_string(value)?
+    })
+}
+
 pub fn value_to_json(v: &Value) -> serde_json::Value {
     use serde_json::Value as J;
     match v {
@@ -281,4 +499,77 @@ mod tests {
         assert_eq!(findings[0].extras.len(), 1);
         assert_eq!(findings[0].extras[0].0, "extra1");
     }
+
+    #[test]
+    fn compact_and_pretty_render_the_same_value() {
+        let value = serde_json::json!({
+            "project": "demo",
+            "result": [{"file": "src/a.rs", "line": 1}],
+        });
+        let compact = render_json(&value, false).unwrap();
+        let pretty = render_json(&value, true).unwrap();
+        assert!(!compact.contains('\n'));
+        assert!(compact.len() < pretty.len());
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&pretty).unwrap()
+        );
+    }
+
+    #[test]
+    fn run_page_returns_window_and_filtered_total() {
+        let store = DbStore::open_in_memory().unwrap();
+        store
+            .run_script(
+                "CREATE TABLE t (n BIGINT); INSERT INTO t SELECT * FROM range(10);",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let ws = Workspace::load(dir.path(), &[], None).unwrap();
+        let paged = run_page(
+            QueryRequest {
+                source: QuerySource::Inline("SELECT n FROM t WHERE n >= $min ORDER BY n;"),
+                params: vec![("min".to_string(), "4".to_string())],
+                args: Vec::new(),
+                store: &store,
+                workspace: &ws,
+            },
+            Page {
+                limit: 2,
+                offset: 1,
+            },
+        )
+        .unwrap();
+        assert_eq!(paged.total_count, 6);
+        let QueryOutput::Rows { rows, .. } = paged.output else {
+            panic!("expected rows");
+        };
+        assert_eq!(
+            rows,
+            vec![vec![serde_json::json!(5)], vec![serde_json::json!(6)]]
+        );
+    }
+
+    #[test]
+    fn run_count_matches_the_filtered_row_count() {
+        let store = DbStore::open_in_memory().unwrap();
+        store
+            .run_script(
+                "CREATE TABLE t (n BIGINT); INSERT INTO t SELECT * FROM range(10);",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let ws = Workspace::load(dir.path(), &[], None).unwrap();
+        let count = run_count(QueryRequest {
+            source: QuerySource::Inline("SELECT n FROM t WHERE n < ? ORDER BY n;"),
+            params: Vec::new(),
+            args: vec!["3".to_string()],
+            store: &store,
+            workspace: &ws,
+        })
+        .unwrap();
+        assert_eq!(count, 3);
+    }
 }
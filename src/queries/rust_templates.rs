@@ -2,24 +2,73 @@
 //!
 //! These templates can't be expressed in pure SQL because their inputs
 //! require source-of-truth access beyond what's materialised in the
-//! fact store. Each handler returns a [`QueryOutput::Findings`] using
-//! the audit-shape columns so the CLI formatter treats it uniformly
-//! with pure-SQL templates.
+//! fact store. Most handlers return [`QueryOutput::Findings`] using the
+//! audit-shape columns so the CLI formatter treats them uniformly with
+//! pure-SQL templates; a few return [`QueryOutput::Rows`] tables,
+//! `outline` returns a [`QueryOutput::Outline`] tree, and its `lsp=true`
+//! form returns [`QueryOutput::DocumentSymbols`].
 //!
 //! Currently registered:
 //!
+//! - **barrel_cycles** — barrel files that re-export each other in a
+//!   loop, traced over resolved `re_export` specifiers only.
+//! - **callers** — direct callers of a function, optionally following
+//!   barrel re-exports to calls made under another imported name.
+//! - **collisions** — names defined in more than one file.
+//! - **comment_ratio** — comment lines per code line for each file and
+//!   overall, flagging files under a threshold.
+//! - **comments** — comments with their line range and documented
+//!   symbol, or paired with the next symbol starting within `near` lines.
 //! - **complexity_hotspots** — cyclomatic complexity + function length,
 //!   computed on-demand from each function's tree-sitter subtree.
+//! - **composition** — per-language file, line and symbol totals with a
+//!   language-by-language matrix of resolved file imports.
+//! - **density** — symbols per 100 lines for each file, flagging dense
+//!   files as candidates for splitting.
+//! - **doc_coverage** — share of exported symbols carrying a doc
+//!   comment, per directory and overall, or the undocumented ones as
+//!   findings.
+//! - **files** / **search** — file and symbol listings with size bounds
+//!   (`min_lines`/`max_lines`, `min_span`/`max_span`) that are optional,
+//!   which `$name` substitution in a `.sql` template can't express.
+//! - **hotspots** — files ranked by symbol count, line count and
+//!   longest symbol, plus types owning more than `method_threshold`
+//!   methods: a quick god-object detector.
+//! - **imports** — raw import specifiers with their package and, given
+//!   a `manifest`, the version the project declares for it.
+//! - **module_graph** — resolved file imports collapsed to directory
+//!   (or top-level directory) edges, weighted by how many file imports
+//!   each one stands for.
+//! - **outline** — one file's symbols in declaration order, flat or
+//!   nested under their enclosing class/impl/module. Returns rows or a
+//!   [`QueryOutput::Outline`] tree rather than findings.
+//! - **similar_symbols** — near-duplicate function bodies, by Jaccard
+//!   similarity over token shingles read from each symbol's source span.
+//! - **tests** — test functions and methods recognised by their
+//!   framework marker: `#[test]`, `@Test`, `test_*`, `it(...)`/`test(...)`.
+//! - **todos** — TODO/FIXME/HACK/XXX/NOTE markers in comments, with the
+//!   line number recovered from each comment's byte offset.
 
-use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail};
 use duckdb::types::Value;
 
+use crate::classify::is_barrel_file;
 use crate::db::DbStore;
+use crate::graph::GraphNode;
+use crate::language::Language;
+use crate::languages;
+use crate::models::{ImportInfo, SymbolKind};
 use crate::storage::workspace::Workspace;
 
-use super::runner::{AuditFinding, QueryOutput, value_to_i64, value_to_string};
+use super::manifest::{Manifest, package_name};
+use super::runner::{
+    AuditFinding, DocumentSymbol, LspPosition, LspRange, OutlineNode, QueryOutput, rows_to_output,
+    value_to_i64, value_to_string,
+};
 
 pub struct Context<'a> {
     pub store: &'a DbStore,
@@ -31,13 +80,49 @@ pub type Handler = fn(&Context<'_>) -> Result<QueryOutput>;
 
 pub fn lookup(name: &str) -> Option<Handler> {
     match name {
+        "barrel_cycles" => Some(barrel_cycles),
+        "callers" => Some(callers),
+        "collisions" => Some(collisions),
+        "comment_ratio" => Some(comment_ratio),
+        "comments" => Some(comments),
         "complexity_hotspots" => Some(complexity_hotspots),
+        "composition" => Some(composition),
+        "density" => Some(density),
+        "doc_coverage" => Some(doc_coverage),
+        "files" => Some(files),
+        "hotspots" => Some(hotspots),
+        "imports" => Some(imports),
+        "module_graph" => Some(module_graph),
+        "outline" => Some(outline),
+        "search" => Some(search),
+        "similar_symbols" => Some(similar_symbols),
+        "tests" => Some(tests),
+        "todos" => Some(todos),
         _ => None,
     }
 }
 
 pub fn names() -> &'static [&'static str] {
-    &["complexity_hotspots"]
+    &[
+        "barrel_cycles",
+        "callers",
+        "collisions",
+        "comment_ratio",
+        "comments",
+        "complexity_hotspots",
+        "composition",
+        "density",
+        "doc_coverage",
+        "files",
+        "hotspots",
+        "imports",
+        "module_graph",
+        "outline",
+        "search",
+        "similar_symbols",
+        "tests",
+        "todos",
+    ]
 }
 
 fn parse_int(params: &BTreeMap<String, String>, key: &str, default: i64) -> i64 {
@@ -47,6 +132,471 @@ fn parse_int(params: &BTreeMap<String, String>, key: &str, default: i64) -> i64
         .unwrap_or(default)
 }
 
+/// Optional integer bound: `None` when absent, an error when present but
+/// not an integer (a silently ignored typo would return unfiltered rows).
+fn parse_bound(params: &BTreeMap<String, String>, key: &str) -> Result<Option<i64>> {
+    params
+        .get(key)
+        .map(|v| {
+            v.parse()
+                .map_err(|_| anyhow!("--param {key} expects an integer, got '{v}'"))
+        })
+        .transpose()
+}
+
+/// [`parse_bound`] for a count or depth, which can't be negative.
+fn parse_count(params: &BTreeMap<String, String>, key: &str) -> Result<Option<usize>> {
+    params
+        .get(key)
+        .map(|v| {
+            v.parse()
+                .map_err(|_| anyhow!("--param {key} expects a non-negative integer, got '{v}'"))
+        })
+        .transpose()
+}
+
+/// ` AND <column> IN (...)` for the `lang` param (comma-separated
+/// extensions, as for `--lang`), or an empty string when it's absent.
+fn language_clause(params: &BTreeMap<String, String>, column: &str) -> Result<String> {
+    let Some(filter) = params.get("lang") else {
+        return Ok(String::new());
+    };
+    let langs = crate::language::parse_language_filter(filter);
+    if langs.is_empty() {
+        return Err(anyhow!("no known language in lang={filter}"));
+    }
+    let list = langs
+        .iter()
+        .map(|l| format!("'{}'", l.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(format!(" AND {column} IN ({list})"))
+}
+
+/// Optional boolean flag: `None` when absent, an error unless the value
+/// is `true` or `false` (a typo must not quietly read as false).
+fn parse_flag(params: &BTreeMap<String, String>, key: &str) -> Result<Option<bool>> {
+    params
+        .get(key)
+        .map(|v| match v.as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(anyhow!("--param {key} expects true or false, got '{v}'")),
+        })
+        .transpose()
+}
+
+/// ` AND <column> = false` when `exclude_tests=true`, so listings show
+/// production code only; an empty string otherwise.
+fn exclude_tests_clause(params: &BTreeMap<String, String>, column: &str) -> Result<String> {
+    Ok(match parse_flag(params, "exclude_tests")? {
+        Some(true) => format!(" AND {column} = false"),
+        _ => String::new(),
+    })
+}
+
+/// `'a', 'b'` from a comma-separated param value, for an SQL `IN (...)`.
+fn quoted_list(csv: &str) -> String {
+    csv.split(',')
+        .map(|v| format!("'{}'", v.trim().replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// callers — direct callers of the function or method `$name`, read
+/// from the resolved `call_edge` table exactly as `find_callers` does.
+///
+/// `follow_reexports=true` also counts calls that reach the target
+/// through barrels. Starting from the target's own file and name, it
+/// follows resolved `re_export` rows (`export { Foo as Bar } from`,
+/// `export *`, `pub use`) to every `(file, name)` the symbol is
+/// re-exported as, then takes each file importing one of those under a
+/// local name and keeps its receiver-less call sites of that name. This
+/// is a name-level heuristic: it doesn't see shadowing, and a call made
+/// through a namespace object (`ns.Foo()`) is not followed. `called_as`
+/// is the name written at the call site.
+fn callers(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let name = ctx
+        .params
+        .get("name")
+        .ok_or_else(|| anyhow!("callers requires --param name=<symbol>"))?;
+    let follow_reexports = parse_flag(ctx.params, "follow_reexports")?.unwrap_or(false);
+    let mut params = BTreeMap::new();
+    params.insert("name".to_string(), Value::Text(name.clone()));
+    let direct = ctx
+        .store
+        .run_query(
+            "SELECT ce.caller_id, a.name, a.file_path, sp.start_line, ce.file_path \
+             FROM call_edge ce \
+             JOIN symbol c ON c.id = ce.callee_id \
+             JOIN symbol a ON a.id = ce.caller_id \
+             JOIN span sp ON sp.entity_id = a.id AND sp.file_path = a.file_path \
+             WHERE c.name = $name AND c.kind IN ('function', 'method')",
+            params.clone(),
+        )
+        .map_err(|e| anyhow!("failed to query call edges: {e}"))?;
+
+    // (caller id, call-site file) -> caller
+    let mut found: BTreeMap<(String, String), Caller> = BTreeMap::new();
+    for row in &direct.rows {
+        let (Some(id), Some(caller), Some(file), Some(line), Some(site)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_string(&row[2]),
+            value_to_i64(&row[3]),
+            value_to_string(&row[4]),
+        ) else {
+            continue;
+        };
+        let caller = Caller {
+            file,
+            line,
+            name: caller,
+            called_as: name.clone(),
+        };
+        found.insert((id, site), caller);
+    }
+
+    if follow_reexports {
+        for (key, caller) in reexported_callers(ctx, name, params)? {
+            found.entry(key).or_insert(caller);
+        }
+    }
+
+    let mut rows: Vec<_> = found
+        .into_iter()
+        .map(|((_, site), caller)| (caller, site))
+        .collect();
+    rows.sort();
+    let headers = [
+        "caller",
+        "caller_file",
+        "caller_line",
+        "callee",
+        "called_as",
+        "call_site_file",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect();
+    let rows = rows
+        .into_iter()
+        .map(|(caller, site)| {
+            vec![
+                serde_json::Value::from(caller.name),
+                serde_json::Value::from(caller.file),
+                serde_json::Value::from(caller.line),
+                serde_json::Value::from(name.as_str()),
+                serde_json::Value::from(caller.called_as),
+                serde_json::Value::from(site),
+            ]
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// One calling function, ordered by file, line and name.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Caller {
+    file: String,
+    line: i64,
+    name: String,
+    called_as: String,
+}
+
+/// Callers that reach `$name` under a name it is re-exported or
+/// imported as, keyed by caller id and call-site file; see [`callers`].
+fn reexported_callers(
+    ctx: &Context<'_>,
+    name: &str,
+    params: BTreeMap<String, Value>,
+) -> Result<Vec<((String, String), Caller)>> {
+    let targets = ctx
+        .store
+        .run_query(
+            "SELECT DISTINCT file_path FROM symbol \
+             WHERE name = $name AND kind IN ('function', 'method')",
+            params,
+        )
+        .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
+    // Every (file, name) the target can be imported from.
+    let mut exposed: HashSet<(String, String)> = targets
+        .rows
+        .iter()
+        .filter_map(|row| Some((value_to_string(&row[0])?, name.to_string())))
+        .collect();
+    if exposed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let imports = ctx
+        .store
+        .run_query(
+            "SELECT file_path, raw_path, imported_name, local_name, language, kind \
+             FROM raw_import WHERE NOT is_external ORDER BY file_path, position",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query imports: {e}"))?;
+    let known: HashSet<String> = ctx.workspace.files().iter().cloned().collect();
+    // (importing file, resolved file, imported name, local name, re-export?)
+    let mut resolved = Vec::new();
+    for row in &imports.rows {
+        let (Some(file), Some(spec), Some(imported), Some(local), Some(lang), Some(kind)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_string(&row[2]),
+            value_to_string(&row[3]),
+            value_to_string(&row[4]),
+            value_to_string(&row[5]),
+        ) else {
+            continue;
+        };
+        let Ok(lang) = Language::from_name(&lang) else {
+            continue;
+        };
+        if let Some(GraphNode::File(target)) =
+            resolve_raw_import(&file, &spec, &kind, false, lang, &known)
+        {
+            resolved.push((file, target, imported, local, kind == "re_export"));
+        }
+    }
+
+    // Follow re-exports to a fixed point: each pass can only add names,
+    // and chains through several barrels take one pass per hop.
+    loop {
+        let mut added = Vec::new();
+        for (file, target, imported, local, re_export) in &resolved {
+            if !re_export {
+                continue;
+            }
+            if imported == "*" {
+                for (from, exported) in &exposed {
+                    if from == target {
+                        added.push((file.clone(), exported.clone()));
+                    }
+                }
+            } else if exposed.contains(&(target.clone(), imported.clone())) {
+                added.push((file.clone(), local.clone()));
+            }
+        }
+        let before = exposed.len();
+        exposed.extend(added);
+        if exposed.len() == before {
+            break;
+        }
+    }
+
+    // (file, local name) bindings that import the target, directly or
+    // through a re-exporting file.
+    let bindings: HashSet<(String, String)> = resolved
+        .iter()
+        .filter(|(_, target, imported, _, re_export)| {
+            !re_export && exposed.contains(&(target.clone(), imported.clone()))
+        })
+        .map(|(file, _, _, local, _)| (file.clone(), local.clone()))
+        .collect();
+    if bindings.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sites = ctx
+        .store
+        .run_query(
+            "SELECT cs.caller_id, cs.file_path, cs.callee_name, a.name, a.file_path, sp.start_line \
+             FROM call_site cs \
+             JOIN symbol a ON a.id = cs.caller_id \
+             JOIN span sp ON sp.entity_id = a.id AND sp.file_path = a.file_path \
+             WHERE cs.receiver IS NULL",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query call sites: {e}"))?;
+    Ok(sites
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let site = value_to_string(&row[1])?;
+            let called_as = value_to_string(&row[2])?;
+            if !bindings.contains(&(site.clone(), called_as.clone())) {
+                return None;
+            }
+            let caller = Caller {
+                file: value_to_string(&row[4])?,
+                line: value_to_i64(&row[5])?,
+                name: value_to_string(&row[3])?,
+                called_as,
+            };
+            Some(((value_to_string(&row[0])?, site), caller))
+        })
+        .collect())
+}
+
+/// collisions — symbol names defined in more than one file.
+///
+/// Params: `kind` (comma-separated symbol kinds). Parameters and
+/// function-local symbols are never counted: every function having its
+/// own `i` is not a collision. Rows list the distinct files and kinds
+/// per name, most widespread first.
+fn collisions(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let kind_clause = ctx
+        .params
+        .get("kind")
+        .map(|k| format!(" AND s.kind IN ({})", quoted_list(k)))
+        .unwrap_or_default();
+    let sql = format!(
+        "SELECT s.name, \
+                COUNT(DISTINCT s.file_path) AS file_count, \
+                list_sort(list_distinct(list(s.file_path))) AS files, \
+                list_sort(list_distinct(list(s.kind))) AS kinds \
+         FROM symbol s \
+         LEFT JOIN symbol p ON p.id = s.parent_id \
+         WHERE s.kind <> 'parameter' \
+           AND (p.kind IS NULL OR p.kind NOT IN ('function', 'method', 'arrow_function', 'component')){kind_clause} \
+         GROUP BY s.name \
+         HAVING COUNT(DISTINCT s.file_path) > 1 \
+         ORDER BY file_count DESC, s.name"
+    );
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
+    Ok(rows_to_output(rows.headers, rows.rows))
+}
+
+/// Default `comment_ratio` threshold: comment lines per code line.
+const COMMENT_RATIO_THRESHOLD: f64 = 0.1;
+
+/// comment_ratio — comment lines against code lines, per file and
+/// overall, worst-documented first.
+///
+/// Comment lines are the distinct lines any `comment` row spans, so a
+/// line holding code and a trailing comment counts as a comment line;
+/// code lines are the rest of `file.line_count`. `ratio` is comment
+/// lines per code line, null for a file with no code lines. Params:
+/// `lang`; `threshold` (default 0.1) sets `below_threshold` on files
+/// under it. Empty files are left out; a final `(total)` row sums the
+/// rest.
+fn comment_ratio(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let threshold = match ctx.params.get("threshold") {
+        Some(v) => v
+            .parse::<f64>()
+            .map_err(|_| anyhow!("--param threshold expects a number, got '{v}'"))?,
+        None => COMMENT_RATIO_THRESHOLD,
+    };
+    let sql = format!(
+        "WITH commented AS ( \
+             SELECT file_path, COUNT(DISTINCT line) AS lines \
+             FROM (SELECT file_path, unnest(range(start_line, end_line + 1)) AS line FROM comment) \
+             GROUP BY file_path) \
+         SELECT f.path, f.line_count, LEAST(COALESCE(c.lines, 0), f.line_count) AS comment_lines \
+         FROM file f \
+         LEFT JOIN commented c ON c.file_path = f.path \
+         WHERE f.line_count > 0{}",
+        language_clause(ctx.params, "f.language")?
+    );
+    let found = ctx
+        .store
+        .run_query(&sql, params)
+        .map_err(|e| anyhow!("failed to query comments: {e}"))?;
+
+    let ratio = |comment: i64, code: i64| (code > 0).then(|| comment as f64 / code as f64);
+    let mut files: Vec<(String, i64, i64)> = found
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some((
+                value_to_string(&row[0])?,
+                value_to_i64(&row[1])?,
+                value_to_i64(&row[2])?,
+            ))
+        })
+        .collect();
+    // Worst first: lowest ratio, then files with no code lines at all.
+    files.sort_by(|a, b| {
+        let (ra, rb) = (ratio(a.2, a.1 - a.2), ratio(b.2, b.1 - b.2));
+        ra.map_or(f64::INFINITY, |r| r)
+            .total_cmp(&rb.map_or(f64::INFINITY, |r| r))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    let total = files.iter().fold((0, 0), |acc, (_, lines, comment)| {
+        (acc.0 + lines, acc.1 + comment)
+    });
+
+    let headers = [
+        "file",
+        "lines",
+        "comment_lines",
+        "code_lines",
+        "ratio",
+        "below_threshold",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect();
+    let rows = files
+        .into_iter()
+        .chain(std::iter::once(("(total)".to_string(), total.0, total.1)))
+        .map(|(file, lines, comment)| {
+            let code = lines - comment;
+            let ratio = ratio(comment, code);
+            vec![
+                serde_json::Value::from(file),
+                serde_json::Value::from(lines),
+                serde_json::Value::from(comment),
+                serde_json::Value::from(code),
+                serde_json::Value::from(ratio.map(|r| (r * 1000.0).round() / 1000.0)),
+                serde_json::Value::from(ratio.is_some_and(|r| r < threshold)),
+            ]
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// comments — comments with their line range.
+///
+/// Params: `file` (one workspace-relative path); `lang`. By default each
+/// row names the symbol the comment documents (`documents_id`), null
+/// when the extractor attached it to nothing. `near=N` instead pairs each
+/// comment with the first symbol starting 1 to N lines after the
+/// comment's last line, which recovers docs separated from their symbol
+/// by a blank line or an attribute; comments with no such symbol are
+/// dropped, and `distance` is `start_line - comment_end_line`.
+fn comments(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let mut filter = language_clause(ctx.params, "f.language")?;
+    let mut params = BTreeMap::new();
+    if let Some(file) = ctx.params.get("file") {
+        params.insert("file".to_string(), Value::Text(file.clone()));
+        filter.push_str(" AND c.file_path = $file");
+    }
+    let sql = match parse_bound(ctx.params, "near")? {
+        Some(near) => format!(
+            "SELECT c.file_path AS file, c.start_line AS comment_start_line, \
+                    c.end_line AS comment_end_line, s.name AS symbol, s.kind, \
+                    sp.start_line, sp.start_line - c.end_line AS distance, c.clean_text AS text \
+             FROM comment c \
+             JOIN file f ON f.path = c.file_path \
+             JOIN symbol s ON s.file_path = c.file_path AND s.kind <> 'parameter' \
+             JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+             WHERE sp.start_line > c.end_line AND sp.start_line - c.end_line <= {near}{filter} \
+             QUALIFY row_number() OVER (PARTITION BY c.id ORDER BY sp.start_line, sp.start_byte, s.id) = 1 \
+             ORDER BY c.file_path, c.start_line, c.start_byte"
+        ),
+        None => format!(
+            "SELECT c.file_path AS file, c.start_line, c.end_line, c.kind, c.is_doc, \
+                    s.name AS symbol, c.clean_text AS text \
+             FROM comment c \
+             JOIN file f ON f.path = c.file_path \
+             LEFT JOIN symbol s ON s.id = c.documents_id \
+             WHERE true{filter} \
+             ORDER BY c.file_path, c.start_line, c.start_byte"
+        ),
+    };
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query comments: {e}"))?;
+    Ok(rows_to_output(rows.headers, rows.rows))
+}
+
 /// complexity_hotspots — flag functions whose cyclomatic complexity OR
 /// length exceeds a threshold. Excludes test files via
 /// `file_classification.is_test = true`.
@@ -61,7 +611,7 @@ fn complexity_hotspots(ctx: &Context<'_>) -> Result<QueryOutput> {
              FROM symbol s \
              JOIN file_classification fc ON fc.path = s.file_path AND fc.is_test = false \
              JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
-             WHERE s.kind IN ('function', 'method', 'arrow_function')",
+             WHERE s.kind IN ('function', 'method', 'arrow_function', 'component')",
             BTreeMap::new(),
         )
         .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
@@ -93,7 +643,7 @@ fn complexity_hotspots(ctx: &Context<'_>) -> Result<QueryOutput> {
             Ok(p) => p,
             Err(_) => continue,
         };
-        let Some(tree) = parser.parse(source.as_bytes(), None) else {
+        let Some(tree) = crate::parser::parse_source(&mut parser, &source, lang) else {
             continue;
         };
         let Some(func_node) =
@@ -135,6 +685,3202 @@ fn complexity_hotspots(ctx: &Context<'_>) -> Result<QueryOutput> {
     }
     // Suppress unused-import warning when Value isn't used directly.
     let _: Option<Value> = None;
-    findings.sort_by(|a, b| b.line.cmp(&a.line).then(a.file.cmp(&b.file)));
+    findings.sort_by(|a, b| {
+        b.line
+            .cmp(&a.line)
+            .then(a.file.cmp(&b.file))
+            .then(a.message.cmp(&b.message))
+    });
     Ok(QueryOutput::Findings(findings))
 }
+
+/// Width of the coverage bar rendered by [`doc_coverage`].
+const COVERAGE_BAR_WIDTH: usize = 20;
+
+/// composition — how the languages of a polyglot project fit together.
+///
+/// One row per language: its `files`, `lines` and `symbols` (parameters
+/// left out), then one `imports_<language>` column per language counting
+/// resolved file imports from this language's files into that one's.
+/// Read across a row for what a language depends on, down a column for
+/// what depends on it. Takes no params.
+fn composition(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let totals = ctx
+        .store
+        .run_query(
+            "WITH sym AS (\
+                SELECT language, count(*) AS n FROM symbol \
+                WHERE kind <> 'parameter' GROUP BY language) \
+             SELECT f.language, count(*), CAST(sum(f.line_count) AS BIGINT), \
+                    coalesce(sym.n, 0) \
+             FROM file f LEFT JOIN sym ON sym.language = f.language \
+             GROUP BY f.language, sym.n ORDER BY f.language",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query files: {e}"))?;
+    let edges = ctx
+        .store
+        .run_query(
+            "SELECT a.language, b.language, count(*) FROM imports i \
+             JOIN file a ON a.path = i.importer_file_id \
+             JOIN file b ON b.path = i.imported_id \
+             GROUP BY a.language, b.language",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query imports: {e}"))?;
+
+    let languages: Vec<String> = totals
+        .rows
+        .iter()
+        .filter_map(|row| value_to_string(&row[0]))
+        .collect();
+    let mut matrix: HashMap<(String, String), i64> = HashMap::new();
+    for row in &edges.rows {
+        if let (Some(from), Some(to), Some(count)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_i64(&row[2]),
+        ) {
+            matrix.insert((from, to), count);
+        }
+    }
+
+    let mut headers: Vec<String> = ["language", "files", "lines", "symbols"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    headers.extend(languages.iter().map(|lang| format!("imports_{lang}")));
+    let rows = totals
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let from = value_to_string(&row[0])?;
+            let mut out = vec![
+                serde_json::Value::from(from.clone()),
+                serde_json::Value::from(value_to_i64(&row[1])?),
+                serde_json::Value::from(value_to_i64(&row[2]).unwrap_or(0)),
+                serde_json::Value::from(value_to_i64(&row[3]).unwrap_or(0)),
+            ];
+            out.extend(languages.iter().map(|to| {
+                let count = matrix.get(&(from.clone(), to.clone())).copied();
+                serde_json::Value::from(count.unwrap_or(0))
+            }));
+            Some(out)
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// Default `density` threshold, in symbols per 100 lines.
+const DENSITY_THRESHOLD: f64 = 25.0;
+
+/// density — symbols per 100 lines of each file, densest first.
+///
+/// Counts every symbol except parameters and function locals, as
+/// `collisions` does, so a file of many small declarations scores high
+/// while one long function does not. Params: `lang`; `threshold`
+/// (default 25) sets `split_candidate` on files above it; `exclude_tests=true`
+/// ignores test files and test symbols. Empty files are left out.
+fn density(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let threshold = match ctx.params.get("threshold") {
+        Some(v) => v
+            .parse::<f64>()
+            .map_err(|_| anyhow!("--param threshold expects a number, got '{v}'"))?,
+        None => DENSITY_THRESHOLD,
+    };
+    let sql = format!(
+        "WITH counted AS ( \
+             SELECT s.file_path, COUNT(*) AS symbols \
+             FROM symbol s \
+             LEFT JOIN symbol p ON p.id = s.parent_id \
+             WHERE s.kind <> 'parameter' \
+               AND (p.kind IS NULL OR p.kind NOT IN ('function', 'method', 'arrow_function', 'component')){} \
+             GROUP BY s.file_path) \
+         SELECT f.path AS file, f.language, f.line_count, COALESCE(c.symbols, 0) AS symbols, \
+                round(COALESCE(c.symbols, 0) * 100.0 / f.line_count, 1)::DOUBLE AS density, \
+                COALESCE(c.symbols, 0) * 100.0 / f.line_count > {threshold} AS split_candidate \
+         FROM file f \
+         LEFT JOIN counted c ON c.file_path = f.path \
+         LEFT JOIN file_classification fc ON fc.path = f.path \
+         WHERE f.line_count > 0{}{} \
+         ORDER BY density DESC, f.path",
+        exclude_tests_clause(ctx.params, "s.is_test")?,
+        language_clause(ctx.params, "f.language")?,
+        exclude_tests_clause(ctx.params, "COALESCE(fc.is_test, false)")?
+    );
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query files: {e}"))?;
+    Ok(rows_to_output(rows.headers, rows.rows))
+}
+
+/// doc_coverage — how many exported symbols carry a doc comment.
+///
+/// A symbol counts as documented when some `comment` row with
+/// `is_doc = true` names it via `documents_id`. Params: `lang`
+/// (comma-separated extension filter, as for `--lang`);
+/// `undocumented=true` returns the undocumented symbols as findings
+/// instead of the summary; adding `commented=true` keeps only those
+/// preceded by a plain (non-doc) comment, which is usually a doc comment
+/// written with the wrong marker. The summary has one row per directory
+/// plus a final `(total)` row.
+fn doc_coverage(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let undocumented = parse_flag(ctx.params, "undocumented")?.unwrap_or(false);
+    let commented_only = parse_flag(ctx.params, "commented")?.unwrap_or(false);
+    let sql = format!(
+        "SELECT s.name, s.kind, s.file_path, sp.start_line, \
+         EXISTS (SELECT 1 FROM comment c \
+                 WHERE c.documents_id = s.id AND c.is_doc) AS documented, \
+         EXISTS (SELECT 1 FROM comment c \
+                 WHERE c.documents_id = s.id AND NOT c.is_doc) AS commented \
+         FROM symbol s \
+         JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+         WHERE s.exported = true AND s.kind <> 'parameter'{} \
+         ORDER BY s.file_path, sp.start_line, s.id",
+        language_clause(ctx.params, "s.language")?
+    );
+
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
+
+    if undocumented {
+        let findings = rows
+            .rows
+            .iter()
+            .filter(|row| !matches!(row[4], Value::Boolean(true)))
+            .filter(|row| !commented_only || matches!(row[5], Value::Boolean(true)))
+            .filter_map(|row| {
+                let name = value_to_string(&row[0])?;
+                let kind = value_to_string(&row[1])?;
+                let commented = matches!(row[5], Value::Boolean(true));
+                let message = if commented {
+                    format!("{kind} {name} has a plain comment but no doc comment")
+                } else {
+                    format!("{kind} {name} is exported without a doc comment")
+                };
+                Some(AuditFinding {
+                    file: value_to_string(&row[2])?,
+                    line: value_to_i64(&row[3])?,
+                    severity: "info".to_string(),
+                    pattern: "undocumented_export".to_string(),
+                    message,
+                    extras: vec![("commented".to_string(), serde_json::Value::from(commented))],
+                })
+            })
+            .collect();
+        return Ok(QueryOutput::Findings(findings));
+    }
+
+    // (documented, total) per directory; BTreeMap keeps rows sorted.
+    let mut by_dir: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+    for row in &rows.rows {
+        let Some(file) = value_to_string(&row[2]) else {
+            continue;
+        };
+        let dir = std::path::Path::new(&file)
+            .parent()
+            .map(|p| p.to_string_lossy().into_owned())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        let entry = by_dir.entry(dir).or_default();
+        entry.1 += 1;
+        if matches!(row[4], Value::Boolean(true)) {
+            entry.0 += 1;
+        }
+    }
+    let total = by_dir
+        .values()
+        .fold((0, 0), |acc, (d, t)| (acc.0 + d, acc.1 + t));
+
+    let headers = ["directory", "documented", "total", "coverage_pct", "bar"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    let rows = by_dir
+        .into_iter()
+        .chain(std::iter::once(("(total)".to_string(), total)))
+        .map(|(dir, (documented, total))| {
+            let ratio = if total == 0 {
+                0.0
+            } else {
+                documented as f64 / total as f64
+            };
+            let filled = (ratio * COVERAGE_BAR_WIDTH as f64).round() as usize;
+            vec![
+                serde_json::Value::from(dir),
+                serde_json::Value::from(documented),
+                serde_json::Value::from(total),
+                serde_json::Value::from((ratio * 1000.0).round() / 10.0),
+                serde_json::Value::from(format!(
+                    "{}{}",
+                    "#".repeat(filled),
+                    ".".repeat(COVERAGE_BAR_WIDTH - filled)
+                )),
+            ]
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// File stems that are entrypoints rather than dead code when nothing
+/// imports them: `index.ts`, `main.go`, `mod.rs`, `lib.rs`.
+const ENTRYPOINT_STEMS: &[&str] = &["index", "main", "mod", "lib"];
+
+/// files — workspace files with their language and line count.
+///
+/// Params: `lang`; `min_lines`/`max_lines` (inclusive bounds on
+/// `file.line_count`); `orphans=true` keeps only files that no internal
+/// import resolves to, entrypoints (`index.*`, `main.*`, `mod.rs`,
+/// `lib.rs`) aside, adds their `directory` and sorts by it. Languages
+/// whose imports never resolve to files (Go, Java, ...) report every
+/// file, so pair `orphans` with `lang`. `largest=N` / `smallest=N`
+/// replace the sort with the N files ranked by `by` — `lines` (the
+/// default) or `symbols`, which adds a `symbols` column counting each
+/// file's non-parameter symbols; ties go by path.
+/// `duplicate_names=true` instead groups files sharing a base name
+/// (`index.ts`, `utils.py`) into `name, count, directories` rows, most
+/// repeated first; only `lang` applies to it.
+fn files(ctx: &Context<'_>) -> Result<QueryOutput> {
+    if parse_flag(ctx.params, "duplicate_names")?.unwrap_or(false) {
+        return duplicate_file_names(ctx);
+    }
+    let orphans = parse_flag(ctx.params, "orphans")?.unwrap_or(false);
+    let top = match (
+        parse_count(ctx.params, "largest")?,
+        parse_count(ctx.params, "smallest")?,
+    ) {
+        (Some(_), Some(_)) => bail!("--param largest and smallest are mutually exclusive"),
+        (Some(n), None) => Some((n, "DESC")),
+        (None, Some(n)) => Some((n, "ASC")),
+        (None, None) => None,
+    };
+    let by_symbols = match ctx.params.get("by").map(String::as_str) {
+        None | Some("lines") => false,
+        Some("symbols") => true,
+        Some(other) => bail!("--param by expects lines or symbols, got '{other}'"),
+    };
+    let symbols_column = if by_symbols {
+        ", (SELECT COUNT(*) FROM symbol s \
+            WHERE s.file_path = f.path AND s.kind <> 'parameter') AS symbols"
+    } else {
+        ""
+    };
+    let mut sql = if orphans {
+        format!(
+            "SELECT f.path, f.language, f.line_count{symbols_column}, \
+                    CASE WHEN contains(f.path, '/') \
+                         THEN regexp_replace(f.path, '/[^/]*$', '') ELSE '.' END AS directory \
+             FROM file f \
+             WHERE NOT EXISTS (SELECT 1 FROM imports i WHERE i.imported_id = f.path) \
+               AND split_part(regexp_replace(f.path, '^.*/', ''), '.', 1) NOT IN ({}){}",
+            ENTRYPOINT_STEMS
+                .iter()
+                .map(|stem| format!("'{stem}'"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            language_clause(ctx.params, "f.language")?
+        )
+    } else {
+        format!(
+            "SELECT f.path, f.language, f.line_count{symbols_column} FROM file f WHERE true{}",
+            language_clause(ctx.params, "f.language")?
+        )
+    };
+    if let Some(min) = parse_bound(ctx.params, "min_lines")? {
+        sql.push_str(&format!(" AND f.line_count >= {min}"));
+    }
+    if let Some(max) = parse_bound(ctx.params, "max_lines")? {
+        sql.push_str(&format!(" AND f.line_count <= {max}"));
+    }
+    if let Some((n, direction)) = top {
+        let metric = if by_symbols {
+            "symbols"
+        } else {
+            "f.line_count"
+        };
+        sql.push_str(&format!(" ORDER BY {metric} {direction}, f.path LIMIT {n}"));
+    } else {
+        sql.push_str(if orphans {
+            " ORDER BY directory, f.path"
+        } else {
+            " ORDER BY f.path"
+        });
+    }
+
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query files: {e}"))?;
+    Ok(rows_to_output(rows.headers, rows.rows))
+}
+
+/// `files duplicate_names=true`: base names shared by more than one
+/// file, with the directories holding them.
+fn duplicate_file_names(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let sql = format!(
+        "SELECT name, COUNT(*) AS count, list(directory ORDER BY directory) AS directories \
+         FROM (SELECT regexp_replace(f.path, '^.*/', '') AS name, \
+                      CASE WHEN contains(f.path, '/') \
+                           THEN regexp_replace(f.path, '/[^/]*$', '') ELSE '.' END AS directory \
+               FROM file f WHERE true{}) \
+         GROUP BY name HAVING COUNT(*) > 1 \
+         ORDER BY count DESC, name",
+        language_clause(ctx.params, "f.language")?
+    );
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query files: {e}"))?;
+    Ok(rows_to_output(rows.headers, rows.rows))
+}
+
+/// Escape regex metacharacters so `text` matches literally.
+fn regex_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// `(symbol_id, d)` for every decorator-like marker the attrs tables
+/// record: Python and TS/JS decorators, Java annotations, C# and PHP
+/// attributes. `d` may carry call arguments (`route("/x")`) and a dotted
+/// path (`app.route`); [`search`] matches on the name before any `(`,
+/// whole or as the last dotted segment.
+const DECORATOR_ROWS: &str = "SELECT symbol_id, unnest(decorators) AS d FROM python_attrs \
+     UNION ALL SELECT symbol_id, unnest(decorators) FROM typescript_attrs \
+     UNION ALL SELECT symbol_id, unnest(annotations) FROM java_attrs \
+     UNION ALL SELECT symbol_id, unnest(attributes) FROM csharp_attrs \
+     UNION ALL SELECT symbol_id, unnest(attributes) FROM php_attrs";
+
+/// search — symbols by name, kind and size.
+///
+/// Params: `name` (case-insensitive substring of `symbol.name`, or with
+/// `exact=true` the whole name, case-sensitive, or with `word=true` a
+/// case-insensitive word of it, bounded by the ends of the name or by
+/// non-alphanumerics such as `_`: `main` matches `run_main`, not
+/// `domain`); `match=all|any` splits `name` on whitespace and keeps
+/// symbols matching every term or at least one of them; `in=path`
+/// matches `name` against the file path instead; `kind`
+/// (comma-separated symbol kinds); `lang`; `min_span`/`max_span`
+/// (inclusive bounds on `end_line - start_line`); `min_loc` (inclusive
+/// lower bound on `symbol.loc`, which leaves out blank and comment
+/// lines); `exclude_tests=true` drops symbols flagged `is_test`;
+/// `context=N` adds a `snippet` column holding the symbol's first N
+/// source lines, null when its file can't be read; `decorator=NAME`
+/// keeps symbols carrying that decorator, annotation or attribute (see
+/// [`DECORATOR_ROWS`]); `annotation=NAME` keeps Java symbols annotated
+/// `@NAME`, by simple name, through the `annotation` table;
+/// `extends=NAME` keeps classes naming `NAME` in
+/// their base clause (`base_type`, matched whole or as the last dotted
+/// segment, so `models.Base` counts for `Base`; unresolved bases count
+/// too); `async=true`/`async=false` keeps only async or only non-async
+/// symbols (`symbol.is_async`, set for Rust, Python, TS/JS and C#
+/// functions and methods); `deprecated=true`/`false` likewise on
+/// `symbol.is_deprecated`. Every param is optional; boolean ones accept
+/// only `true`/`false`, and `match` errors on a `name` with no terms.
+/// Parameters are never returned.
+fn search(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let mut sql = format!(
+        "SELECT s.name, s.kind, s.file_path, sp.start_line, sp.end_line, \
+         sp.end_line - sp.start_line AS span, s.loc \
+         FROM symbol s \
+         JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+         WHERE s.kind <> 'parameter'{}{}",
+        language_clause(ctx.params, "s.language")?,
+        exclude_tests_clause(ctx.params, "s.is_test")?
+    );
+    if let Some(name) = ctx.params.get("name") {
+        let column = match ctx.params.get("in").map(String::as_str) {
+            None | Some("name") => "s.name",
+            Some("path") => "s.file_path",
+            Some(other) => bail!("--param in expects name or path, got '{other}'"),
+        };
+        let (terms, joiner): (Vec<&str>, &str) = match ctx.params.get("match").map(String::as_str) {
+            None => (vec![name.as_str()], " AND "),
+            Some("all") => (name.split_whitespace().collect(), " AND "),
+            Some("any") => (name.split_whitespace().collect(), " OR "),
+            Some(other) => bail!("--param match expects any or all, got '{other}'"),
+        };
+        if terms.is_empty() {
+            bail!("--param name expects at least one search term, got '{name}'");
+        }
+        let exact = parse_flag(ctx.params, "exact")?.unwrap_or(false);
+        let word = parse_flag(ctx.params, "word")?.unwrap_or(false);
+        if exact && word {
+            bail!("--param exact and word are mutually exclusive");
+        }
+        let clauses: Vec<String> = terms
+            .iter()
+            .map(|term| {
+                let term = term.replace('\'', "''");
+                if exact {
+                    format!("{column} = '{term}'")
+                } else if word {
+                    let word = regex_escape(&term);
+                    format!(
+                        "regexp_matches({column}, '(^|[^[:alnum:]]){word}([^[:alnum:]]|$)', 'i')"
+                    )
+                } else {
+                    format!("contains(lower({column}), lower('{term}'))")
+                }
+            })
+            .collect();
+        sql.push_str(&format!(" AND ({})", clauses.join(joiner)));
+    }
+    if let Some(kind) = ctx.params.get("kind") {
+        sql.push_str(&format!(" AND s.kind IN ({})", quoted_list(kind)));
+    }
+    if let Some(min) = parse_bound(ctx.params, "min_span")? {
+        sql.push_str(&format!(" AND sp.end_line - sp.start_line >= {min}"));
+    }
+    if let Some(max) = parse_bound(ctx.params, "max_span")? {
+        sql.push_str(&format!(" AND sp.end_line - sp.start_line <= {max}"));
+    }
+    if let Some(min) = parse_bound(ctx.params, "min_loc")? {
+        sql.push_str(&format!(" AND s.loc >= {min}"));
+    }
+    if let Some(decorator) = ctx.params.get("decorator") {
+        let name = decorator.replace('\'', "''");
+        sql.push_str(&format!(
+            " AND s.id IN (SELECT symbol_id FROM ({DECORATOR_ROWS}) \
+             WHERE split_part(d, '(', 1) = '{name}' \
+                OR ends_with(split_part(d, '(', 1), '.{name}'))"
+        ));
+    }
+    if let Some(annotation) = ctx.params.get("annotation") {
+        let name = annotation.replace('\'', "''");
+        sql.push_str(&format!(
+            " AND s.id IN (SELECT symbol_id FROM annotation WHERE name = '{name}')"
+        ));
+    }
+    if let Some(base) = ctx.params.get("extends") {
+        let base = base.replace('\'', "''");
+        sql.push_str(&format!(
+            " AND s.id IN (SELECT symbol_id FROM base_type \
+             WHERE name = '{base}' OR ends_with(name, '.{base}'))"
+        ));
+    }
+    match parse_flag(ctx.params, "async")? {
+        Some(true) => sql.push_str(" AND s.is_async"),
+        Some(false) => sql.push_str(" AND NOT s.is_async"),
+        None => {}
+    }
+    match parse_flag(ctx.params, "deprecated")? {
+        Some(true) => sql.push_str(" AND s.is_deprecated"),
+        Some(false) => sql.push_str(" AND NOT s.is_deprecated"),
+        None => {}
+    }
+    sql.push_str(" ORDER BY s.file_path, sp.start_line, s.id");
+
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
+    let output = rows_to_output(rows.headers, rows.rows);
+    let Some(context) = parse_bound(ctx.params, "context")? else {
+        return Ok(output);
+    };
+    let QueryOutput::Rows {
+        mut headers,
+        mut rows,
+    } = output
+    else {
+        return Ok(output);
+    };
+    headers.push("snippet".to_string());
+    let mut current: Option<(String, Option<std::sync::Arc<str>>)> = None;
+    for row in &mut rows {
+        let located = row[2].as_str().map(str::to_string).zip(row[3].as_i64());
+        let Some((file, start_line)) = located else {
+            row.push(serde_json::Value::Null);
+            continue;
+        };
+        if current.as_ref().is_none_or(|(f, _)| *f != file) {
+            let source = ctx.workspace.read_file(&file);
+            current = Some((file, source));
+        }
+        let snippet = current
+            .as_ref()
+            .and_then(|(_, source)| source.as_deref())
+            .map(|src| {
+                src.lines()
+                    .skip((start_line - 1).max(0) as usize)
+                    .take(context.max(0) as usize)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
+        row.push(serde_json::Value::from(snippet));
+    }
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// Weights of the file score in [`hotspots`]: one point per symbol, per
+/// ten lines, and per five lines of the longest symbol.
+const HOTSPOT_LINES_PER_POINT: f64 = 10.0;
+const HOTSPOT_SPAN_PER_POINT: f64 = 5.0;
+
+/// hotspots — files and types most likely to be "god objects".
+///
+/// Files are ranked by `symbols + lines / 10 + max_span / 5`, where
+/// `max_span` is the longest symbol's `end_line - start_line`. Types
+/// (any symbol that is the `parent_id` of methods) are listed when they
+/// own more than `method_threshold` methods (default 10). Params:
+/// `lang`; `method_threshold`; `limit` (default 20, per section);
+/// `exclude_tests=true` ignores test files and test symbols. File
+/// rows come first, then type rows, each with their contributing
+/// metrics; columns that don't apply to a row are null.
+fn hotspots(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let method_threshold = parse_int(ctx.params, "method_threshold", 10);
+    let limit = parse_int(ctx.params, "limit", 20).max(0) as usize;
+
+    let file_sql = format!(
+        "SELECT f.path, COUNT(s.id) AS symbols, f.line_count, \
+                COALESCE(MAX(sp.end_line - sp.start_line), 0) AS max_span \
+         FROM file f \
+         LEFT JOIN symbol s ON s.file_path = f.path AND s.kind <> 'parameter'{} \
+         LEFT JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+         LEFT JOIN file_classification fc ON fc.path = f.path \
+         WHERE true{}{} \
+         GROUP BY f.path, f.line_count",
+        exclude_tests_clause(ctx.params, "s.is_test")?,
+        language_clause(ctx.params, "f.language")?,
+        exclude_tests_clause(ctx.params, "COALESCE(fc.is_test, false)")?
+    );
+    let file_rows = ctx
+        .store
+        .run_query(&file_sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query files: {e}"))?;
+
+    let mut files: Vec<(String, i64, i64, i64, f64)> = file_rows
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let path = value_to_string(&row[0])?;
+            let symbols = value_to_i64(&row[1]).unwrap_or(0);
+            let lines = value_to_i64(&row[2]).unwrap_or(0);
+            let max_span = value_to_i64(&row[3]).unwrap_or(0);
+            let score = symbols as f64
+                + lines as f64 / HOTSPOT_LINES_PER_POINT
+                + max_span as f64 / HOTSPOT_SPAN_PER_POINT;
+            Some((
+                path,
+                symbols,
+                lines,
+                max_span,
+                (score * 10.0).round() / 10.0,
+            ))
+        })
+        .collect();
+    files.sort_by(|a, b| b.4.total_cmp(&a.4).then_with(|| a.0.cmp(&b.0)));
+    files.truncate(limit);
+
+    let type_sql = format!(
+        "SELECT p.name, p.kind, p.file_path, sp.start_line, \
+                sp.end_line - sp.start_line + 1 AS lines, COUNT(*) AS methods \
+         FROM symbol m \
+         JOIN symbol p ON p.id = m.parent_id \
+         JOIN span sp ON sp.entity_id = p.id AND sp.file_path = p.file_path \
+         WHERE m.kind = 'method'{}{} \
+         GROUP BY p.id, p.name, p.kind, p.file_path, sp.start_line, sp.end_line \
+         HAVING COUNT(*) > {method_threshold} \
+         ORDER BY methods DESC, p.file_path, sp.start_line \
+         LIMIT {limit}",
+        language_clause(ctx.params, "p.language")?,
+        exclude_tests_clause(ctx.params, "p.is_test")?
+    );
+    let type_rows = ctx
+        .store
+        .run_query(&type_sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query methods: {e}"))?;
+
+    let null = serde_json::Value::Null;
+    let headers = [
+        "category", "file", "name", "kind", "line", "symbols", "lines", "max_span", "methods",
+        "score",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect();
+    let mut rows: Vec<Vec<serde_json::Value>> = files
+        .into_iter()
+        .map(|(path, symbols, lines, max_span, score)| {
+            vec![
+                serde_json::Value::from("file"),
+                serde_json::Value::from(path),
+                null.clone(),
+                null.clone(),
+                null.clone(),
+                serde_json::Value::from(symbols),
+                serde_json::Value::from(lines),
+                serde_json::Value::from(max_span),
+                null.clone(),
+                serde_json::Value::from(score),
+            ]
+        })
+        .collect();
+    rows.extend(type_rows.rows.iter().filter_map(|row| {
+        let methods = value_to_i64(&row[5])?;
+        Some(vec![
+            serde_json::Value::from("type"),
+            serde_json::Value::from(value_to_string(&row[2])?),
+            serde_json::Value::from(value_to_string(&row[0])?),
+            serde_json::Value::from(value_to_string(&row[1])?),
+            serde_json::Value::from(value_to_i64(&row[3])?),
+            null.clone(),
+            serde_json::Value::from(value_to_i64(&row[4])?),
+            null.clone(),
+            serde_json::Value::from(methods),
+            serde_json::Value::from(methods as f64),
+        ])
+    }));
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// imports — raw import specifiers per file, with the package each one
+/// belongs to.
+///
+/// Params: `lang`; `external=true` keeps only imports of a package
+/// (drops relative and crate-local paths, and specifiers the build
+/// reclassified with `--internal-prefix`); `external_kind=builtin` or
+/// `external_kind=package` keeps JS/TS imports of that kind (Node core
+/// modules vs npm packages); `manifest` (path to a
+/// `package.json` or `Cargo.toml`) fills the `version` column with the
+/// declared version of the import's package, left null when the
+/// manifest doesn't list it. `duplicates=true` switches to the
+/// redundant-import report of [`duplicate_imports`], `unresolved=true`
+/// to the dangling-import report of [`unresolved_imports`].
+fn imports(ctx: &Context<'_>) -> Result<QueryOutput> {
+    if parse_flag(ctx.params, "duplicates")?.unwrap_or(false) {
+        return duplicate_imports(ctx);
+    }
+    if parse_flag(ctx.params, "unresolved")?.unwrap_or(false) {
+        return unresolved_imports(ctx);
+    }
+    let manifest = ctx
+        .params
+        .get("manifest")
+        .map(|path| Manifest::load(std::path::Path::new(path)))
+        .transpose()?;
+
+    let mut sql = format!(
+        "SELECT r.file_path, r.position, r.raw_path, r.language, r.kind, r.external_kind, \
+                r.is_external \
+         FROM raw_import r WHERE true{}",
+        language_clause(ctx.params, "r.language")?
+    );
+    if let Some(kind) = ctx.params.get("external_kind") {
+        sql.push_str(&format!(" AND r.external_kind IN ({})", quoted_list(kind)));
+    }
+    sql.push_str(" ORDER BY r.file_path, r.position");
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query imports: {e}"))?;
+
+    let external_only = parse_flag(ctx.params, "external")?.unwrap_or(false);
+    let headers = [
+        "file",
+        "position",
+        "module_specifier",
+        "language",
+        "kind",
+        "external_kind",
+        "package",
+        "version",
+        "is_external",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect();
+    let rows = rows
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let specifier = value_to_string(&row[2])?;
+            let language = value_to_string(&row[3])?;
+            let package = Language::from_name(&language)
+                .ok()
+                .and_then(|lang| package_name(&specifier, lang))
+                .map(str::to_string);
+            let is_external = matches!(row[6], Value::Boolean(true));
+            if external_only && (package.is_none() || !is_external) {
+                return None;
+            }
+            let version = package
+                .as_deref()
+                .zip(manifest.as_ref())
+                .and_then(|(package, manifest)| manifest.version_of(package));
+            Some(vec![
+                serde_json::Value::from(value_to_string(&row[0])?),
+                serde_json::Value::from(value_to_i64(&row[1])?),
+                serde_json::Value::from(specifier.clone()),
+                serde_json::Value::from(language),
+                serde_json::Value::from(value_to_string(&row[4])?),
+                serde_json::Value::from(value_to_string(&row[5])),
+                serde_json::Value::from(package),
+                serde_json::Value::from(version),
+                serde_json::Value::from(is_external),
+            ])
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// imports duplicates=true — redundant imports, one row per offence.
+///
+/// `duplicate`: a file imports the same name from the same module more
+/// than once (`imported_name` is `*` for repeated whole-module imports
+/// such as a doubled `#include`). `namespace_and_named`: a file imports a
+/// module whole (`* as ns`, a glob or a side-effect import) and also
+/// named members of it. Re-exports are left out — a barrel re-exporting
+/// what it also imports is not redundant.
+fn duplicate_imports(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let filter = format!(
+        "kind <> 're_export'{}",
+        language_clause(ctx.params, "language")?
+    );
+    let sql = format!(
+        "SELECT file_path, raw_path, imported_name, count(*) AS n, 'duplicate' AS reason \
+         FROM raw_import WHERE {filter} \
+         GROUP BY file_path, raw_path, imported_name HAVING count(*) > 1 \
+         UNION ALL \
+         SELECT file_path, raw_path, '*', count(*), 'namespace_and_named' \
+         FROM raw_import WHERE {filter} \
+         GROUP BY file_path, raw_path \
+         HAVING bool_or(imported_name = '*') AND bool_or(imported_name <> '*') \
+         ORDER BY 1, 2, 3, 5"
+    );
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query duplicate imports: {e}"))?;
+
+    let headers = [
+        "file",
+        "module_specifier",
+        "imported_name",
+        "count",
+        "reason",
+    ]
+    .iter()
+    .map(|h| h.to_string())
+    .collect();
+    let rows = rows
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(vec![
+                serde_json::Value::from(value_to_string(&row[0])?),
+                serde_json::Value::from(value_to_string(&row[1])?),
+                serde_json::Value::from(value_to_string(&row[2])?),
+                serde_json::Value::from(value_to_i64(&row[3])?),
+                serde_json::Value::from(value_to_string(&row[4])?),
+            ])
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// barrel_cycles — barrel files whose re-exports loop back on themselves.
+///
+/// Only `re_export` rows (`export ... from`) between barrel files
+/// (`index.*`, `__init__.py`, `mod.rs`) are followed, so an ordinary
+/// import cycle through a barrel is not reported here; `import_depth`
+/// and `file_dependencies` cover the full import graph. One row per
+/// group of barrels that re-export each other: its members, their count,
+/// and the shortest loop through the group's first file.
+fn barrel_cycles(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let rows = ctx
+        .store
+        .run_query(
+            "SELECT r.file_path, r.raw_path, r.language, r.is_external \
+             FROM raw_import r JOIN file_classification c ON c.path = r.file_path \
+             WHERE r.kind = 're_export' AND c.is_barrel \
+             ORDER BY r.file_path, r.position",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query re-exports: {e}"))?;
+
+    let known: HashSet<String> = ctx.workspace.files().iter().cloned().collect();
+    let mut edges: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for row in &rows.rows {
+        let (Some(file), Some(specifier), Some(language)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_string(&row[2]),
+        ) else {
+            continue;
+        };
+        let Ok(language) = Language::from_name(&language) else {
+            continue;
+        };
+        let is_external = matches!(row[3], Value::Boolean(true));
+        if let Some(GraphNode::File(target)) = resolve_raw_import(
+            &file,
+            &specifier,
+            "re_export",
+            is_external,
+            language,
+            &known,
+        ) && is_barrel_file(&target)
+        {
+            edges.entry(file).or_default().insert(target);
+        }
+    }
+
+    let reach: BTreeMap<&str, HashSet<&str>> = edges
+        .keys()
+        .map(|file| (file.as_str(), reachable(file, &edges)))
+        .collect();
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut out = Vec::new();
+    // Files come in path order, so the first unseen file on a loop is
+    // the smallest member of its group.
+    for (&file, from_file) in &reach {
+        if seen.contains(file) || !from_file.contains(file) {
+            continue;
+        }
+        let members: BTreeSet<&str> = from_file
+            .iter()
+            .copied()
+            .filter(|other| reach.get(other).is_some_and(|r| r.contains(file)))
+            .collect();
+        seen.extend(members.iter().copied());
+        let path = shortest_loop(file, &members, &edges);
+        out.push(vec![
+            serde_json::Value::from(members.iter().copied().collect::<Vec<_>>().join(", ")),
+            serde_json::Value::from(members.len()),
+            serde_json::Value::from(path.join(" -> ")),
+        ]);
+    }
+
+    let headers = ["barrels", "size", "loop"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows: out })
+}
+
+/// Files reachable from `start` in one or more steps.
+fn reachable<'a>(start: &str, edges: &'a BTreeMap<String, BTreeSet<String>>) -> HashSet<&'a str> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut stack: Vec<&str> = edges
+        .get(start)
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    while let Some(file) = stack.pop() {
+        if seen.insert(file) {
+            stack.extend(edges.get(file).into_iter().flatten().map(String::as_str));
+        }
+    }
+    seen
+}
+
+/// The shortest path from `start` back to itself through `members`,
+/// with `start` at both ends.
+fn shortest_loop(
+    start: &str,
+    members: &BTreeSet<&str>,
+    edges: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<String> {
+    let mut prev: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(file) = queue.pop_front() {
+        for next in edges.get(file).into_iter().flatten() {
+            let next = next.as_str();
+            if next == start {
+                let mut path = vec![start.to_string()];
+                let mut at = file;
+                while at != start {
+                    path.push(at.to_string());
+                    at = prev[at];
+                }
+                path.push(start.to_string());
+                path.reverse();
+                return path;
+            }
+            if members.contains(next) && !prev.contains_key(next) {
+                prev.insert(next, file);
+                queue.push_back(next);
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// imports unresolved=true — internal imports that resolve to no
+/// workspace file, likely left dangling by a move or delete. One row per
+/// file and specifier, at its first position. External imports are left
+/// out, and so is C#, whose `using` names a namespace rather than a file.
+fn unresolved_imports(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let sql = format!(
+        "SELECT file_path, min(position), raw_path, language, kind \
+         FROM raw_import WHERE NOT is_external AND language <> 'csharp'{} \
+         GROUP BY file_path, raw_path, language, kind ORDER BY 1, 2",
+        language_clause(ctx.params, "language")?
+    );
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query imports: {e}"))?;
+
+    let known: HashSet<String> = ctx.workspace.files().iter().cloned().collect();
+    let headers = ["file", "position", "module_specifier", "language", "kind"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    let rows = rows
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let file = value_to_string(&row[0])?;
+            let specifier = value_to_string(&row[2])?;
+            let language = value_to_string(&row[3])?;
+            let kind = value_to_string(&row[4])?;
+            let lang = Language::from_name(&language).ok()?;
+            if resolve_raw_import(&file, &specifier, &kind, false, lang, &known).is_some() {
+                return None;
+            }
+            Some(vec![
+                serde_json::Value::from(file),
+                serde_json::Value::from(value_to_i64(&row[1])?),
+                serde_json::Value::from(specifier),
+                serde_json::Value::from(language),
+                serde_json::Value::from(kind),
+            ])
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// Resolve one `raw_import` row the way the build does.
+fn resolve_raw_import(
+    file: &str,
+    specifier: &str,
+    kind: &str,
+    is_external: bool,
+    language: Language,
+    known: &HashSet<String>,
+) -> Option<GraphNode> {
+    let import = ImportInfo {
+        source_file: file.to_string(),
+        module_specifier: specifier.to_string(),
+        imported_name: String::new(),
+        local_name: String::new(),
+        kind: kind.to_string(),
+        is_type_only: false,
+        line: 0,
+        order: 0,
+        is_external,
+        external_kind: None,
+    };
+    languages::resolve_import(file, &import, language, known)
+}
+
+/// module_graph — the resolved import graph aggregated by module.
+///
+/// Params: `group_by` is `directory` (default: `src/a/x.ts` → `src/a`),
+/// `top-dir` (first path component, `src`) or `file` (no grouping).
+/// Each `from → to` row sums the file imports it collapses into
+/// `weight`; imports within one group are dropped. Rows are ordered by
+/// weight, heaviest first, so unexpected cross-layer edges stand out.
+fn module_graph(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let group_by = ctx
+        .params
+        .get("group_by")
+        .map(String::as_str)
+        .unwrap_or("directory");
+    let group: fn(&str) -> &str = match group_by {
+        "file" => |path| path,
+        "directory" => |path| path.rsplit_once('/').map_or(".", |(dir, _)| dir),
+        "top-dir" => |path| path.split_once('/').map_or(".", |(top, _)| top),
+        other => {
+            return Err(anyhow!(
+                "--param group_by expects file, directory or top-dir, got '{other}'"
+            ));
+        }
+    };
+
+    let rows = ctx
+        .store
+        .run_query(
+            "SELECT importer_file_id, imported_id FROM imports",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query imports: {e}"))?;
+    let mut weights: HashMap<(String, String), i64> = HashMap::new();
+    for row in &rows.rows {
+        let (Some(from), Some(to)) = (value_to_string(&row[0]), value_to_string(&row[1])) else {
+            continue;
+        };
+        let (from, to) = (group(&from), group(&to));
+        if from != to {
+            *weights
+                .entry((from.to_string(), to.to_string()))
+                .or_default() += 1;
+        }
+    }
+    let mut edges: Vec<((String, String), i64)> = weights.into_iter().collect();
+    edges.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let headers = ["from", "to", "weight"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    let rows = edges
+        .into_iter()
+        .map(|((from, to), weight)| {
+            vec![
+                serde_json::Value::from(from),
+                serde_json::Value::from(to),
+                serde_json::Value::from(weight),
+            ]
+        })
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// outline — symbols declared in `file`, in declaration order.
+///
+/// Params: `file` (required, workspace-relative path); `nested=true`
+/// renders members as `children` of their enclosing class/impl/module
+/// using `symbol.parent_id`; `depth=N` keeps only the first N nesting
+/// levels, dropping everything below them; `with_comments=true` adds
+/// each symbol's `doc`, the first line of its doc comment (omitted when
+/// undocumented); `kind` (comma-separated symbol kinds, e.g. `method`
+/// or `class,interface`) keeps only those;
+/// `lsp=true` returns the nested tree as LSP `DocumentSymbol`s
+/// ([`QueryOutput::DocumentSymbols`]) for editor outline providers.
+/// Parameters are omitted. Symbols come in source order, by start byte
+/// with enclosing symbols first. A symbol whose parent was filtered out
+/// by `kind` falls back to the top level.
+fn outline(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let file = ctx
+        .params
+        .get("file")
+        .ok_or_else(|| anyhow!("outline requires --param file=<path>"))?;
+    let lsp = parse_flag(ctx.params, "lsp")?.unwrap_or(false);
+    let nested = lsp || parse_flag(ctx.params, "nested")?.unwrap_or(false);
+    let max_depth = parse_count(ctx.params, "depth")?;
+    let with_comments = parse_flag(ctx.params, "with_comments")?.unwrap_or(false);
+    let kind_clause = ctx
+        .params
+        .get("kind")
+        .map(|kinds| format!(" AND s.kind IN ({})", quoted_list(kinds)))
+        .unwrap_or_default();
+
+    let mut params = BTreeMap::new();
+    params.insert("file".to_string(), Value::Text(file.clone()));
+    // Line-style doc comments (`///`, `#`) are one row per line; the
+    // earliest is the doc's first line.
+    let rows = ctx
+        .store
+        .run_query(
+            &format!(
+                "SELECT s.id, s.parent_id, s.name, s.kind, sp.start_line, sp.end_line, \
+                        (SELECT c.text FROM comment c \
+                         WHERE c.documents_id = s.id AND c.is_doc \
+                         ORDER BY c.start_byte LIMIT 1) AS doc, \
+                        sp.start_col, sp.end_col \
+                 FROM symbol s \
+                 JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+                 WHERE s.file_path = $file AND s.kind <> 'parameter'{kind_clause} \
+                 ORDER BY sp.start_byte, sp.end_byte DESC, s.id"
+            ),
+            params,
+        )
+        .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
+
+    // Rows arrive outer-first, so a parent is always indexed (or known
+    // to be cut by `depth`) before any of its children.
+    let mut index_of: HashMap<String, usize> = HashMap::new();
+    let mut too_deep: HashSet<String> = HashSet::new();
+    let mut parent_of: Vec<Option<usize>> = Vec::new();
+    let mut depth_of: Vec<usize> = Vec::new();
+    let mut nodes: Vec<Option<OutlineNode>> = Vec::new();
+    for row in rows.rows {
+        let Some(id) = value_to_string(&row[0]) else {
+            continue;
+        };
+        let parent_id = value_to_string(&row[1]);
+        let parent = parent_id.as_ref().and_then(|p| index_of.get(p).copied());
+        let depth = parent.map(|p| depth_of[p] + 1).unwrap_or(0);
+        if parent_id.is_some_and(|p| too_deep.contains(&p))
+            || max_depth.is_some_and(|max| depth >= max)
+        {
+            too_deep.insert(id);
+            continue;
+        }
+        index_of.insert(id, nodes.len());
+        parent_of.push(parent);
+        depth_of.push(depth);
+        nodes.push(Some(OutlineNode {
+            name: value_to_string(&row[2]).unwrap_or_default(),
+            kind: value_to_string(&row[3]).unwrap_or_default(),
+            start_line: value_to_i64(&row[4]).unwrap_or(0),
+            end_line: value_to_i64(&row[5]).unwrap_or(0),
+            doc: if with_comments {
+                value_to_string(&row[6]).and_then(|text| doc_first_line(&text))
+            } else {
+                None
+            },
+            children: Vec::new(),
+            start_col: value_to_i64(&row[7]).unwrap_or(0),
+            end_col: value_to_i64(&row[8]).unwrap_or(0),
+        }));
+    }
+
+    if !nested {
+        let mut headers: Vec<String> =
+            ["name", "kind", "start_line", "end_line", "depth", "parent"]
+                .iter()
+                .map(|h| h.to_string())
+                .collect();
+        if with_comments {
+            headers.push("doc".to_string());
+        }
+        let rows = nodes
+            .iter()
+            .zip(&parent_of)
+            .zip(&depth_of)
+            .filter_map(|((node, parent), depth)| {
+                let node = node.as_ref()?;
+                let parent = parent
+                    .and_then(|p| nodes[p].as_ref())
+                    .map(|p| serde_json::Value::from(p.name.clone()))
+                    .unwrap_or(serde_json::Value::Null);
+                let mut row = vec![
+                    serde_json::Value::from(node.name.clone()),
+                    serde_json::Value::from(node.kind.clone()),
+                    serde_json::Value::from(node.start_line),
+                    serde_json::Value::from(node.end_line),
+                    serde_json::Value::from(*depth as i64),
+                    parent,
+                ];
+                if with_comments {
+                    row.push(serde_json::Value::from(node.doc.clone()));
+                }
+                Some(row)
+            })
+            .collect();
+        return Ok(QueryOutput::Rows { headers, rows });
+    }
+
+    // Fold children into parents innermost-first: walking backwards, every
+    // child is attached before its parent is itself moved.
+    let mut roots = Vec::new();
+    for i in (0..nodes.len()).rev() {
+        let mut node = nodes[i].take().expect("each node is moved once");
+        node.children.reverse();
+        match parent_of[i] {
+            Some(p) => nodes[p]
+                .as_mut()
+                .expect("parent precedes its children")
+                .children
+                .push(node),
+            None => roots.push(node),
+        }
+    }
+    roots.reverse();
+    if lsp {
+        let source = ctx.workspace.read_file(file);
+        let lines: Vec<&str> = source
+            .as_deref()
+            .map(|s| s.lines().collect())
+            .unwrap_or_default();
+        return Ok(QueryOutput::DocumentSymbols(
+            roots
+                .into_iter()
+                .map(|node| document_symbol(node, &lines))
+                .collect(),
+        ));
+    }
+    Ok(QueryOutput::Outline(roots))
+}
+
+/// Convert an outline node and its children to LSP form. Columns are
+/// converted from bytes to the UTF-16 code units LSP counts. The
+/// selection range is the symbol's unqualified name (`User` for
+/// `App\Models\User`) on its first line, or an empty range at the
+/// symbol's start when the name isn't found there (or the source can't
+/// be read).
+fn document_symbol(node: OutlineNode, lines: &[&str]) -> DocumentSymbol {
+    let position = |line: i64, byte_col: i64| {
+        let line = (line - 1).max(0);
+        LspPosition {
+            line,
+            character: utf16_column(lines.get(line as usize).copied(), byte_col),
+        }
+    };
+    let start = position(node.start_line, node.start_col);
+    let short_name = node
+        .name
+        .rsplit(['.', ':', '\\', '/'])
+        .next()
+        .unwrap_or(&node.name);
+    let name_at = lines
+        .get(start.line as usize)
+        .and_then(|line| line.get(node.start_col as usize..))
+        .filter(|_| !short_name.is_empty())
+        .and_then(|rest| rest.find(short_name))
+        .map(|at| node.start_col + at as i64);
+    let selection_range = match name_at {
+        Some(at) => LspRange {
+            start: position(node.start_line, at),
+            end: position(node.start_line, at + short_name.len() as i64),
+        },
+        None => LspRange { start, end: start },
+    };
+    DocumentSymbol {
+        kind: SymbolKind::from_str(&node.kind).map_or(13, SymbolKind::lsp_kind),
+        range: LspRange {
+            start,
+            end: position(node.end_line, node.end_col),
+        },
+        selection_range,
+        children: node
+            .children
+            .into_iter()
+            .map(|child| document_symbol(child, lines))
+            .collect(),
+        name: node.name,
+        detail: node.kind,
+    }
+}
+
+/// UTF-16 length of the first `byte_col` bytes of `line`. Falls back to
+/// the byte column when the line is unknown or the column isn't on a
+/// char boundary.
+fn utf16_column(line: Option<&str>, byte_col: i64) -> i64 {
+    line.and_then(|line| line.get(..byte_col.max(0) as usize))
+        .map_or(byte_col, |prefix| prefix.encode_utf16().count() as i64)
+}
+
+/// First non-empty line of a doc comment with its comment markers
+/// (`///`, `//!`, `/**`, ` * `, `#`, `"""`) stripped.
+fn doc_first_line(text: &str) -> Option<String> {
+    text.lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches("/**")
+                .trim_start_matches("/*!")
+                .trim_start_matches("//!")
+                .trim_start_matches("///")
+                .trim_start_matches("//")
+                .trim_start_matches('#')
+                .trim_start_matches('*')
+                .trim_end_matches("*/")
+                .trim_matches(|c| c == '"' || c == '\'')
+                .trim()
+        })
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Tokens per shingle in [`similar_symbols`]. Three is enough to make
+/// reordered statements count as different without letting a single
+/// renamed local sink an otherwise identical body.
+const SHINGLE_SIZE: usize = 3;
+
+/// similar_symbols — cluster function bodies that are near-duplicates.
+///
+/// Params: `threshold` (Jaccard similarity in `0..=1`, default 0.8),
+/// `kind` (comma-separated symbol kinds, default `function,method`),
+/// `min_lines` (skip bodies shorter than this, default 5). Each symbol's
+/// source span is tokenised (whitespace-insensitive, its own name masked
+/// so renamed copies still match) into [`SHINGLE_SIZE`]-token shingles.
+/// Pairs above the threshold are unioned into clusters; one finding is
+/// emitted per clustered symbol, pointing at its closest match.
+///
+/// Pairwise comparison is quadratic in the number of candidate symbols,
+/// which is why `kind` and `min_lines` default to narrow values.
+fn similar_symbols(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let threshold = match ctx.params.get("threshold") {
+        Some(v) => v
+            .parse::<f64>()
+            .ok()
+            .filter(|t| (0.0..=1.0).contains(t))
+            .ok_or_else(|| anyhow!("--param threshold expects a number in 0..=1, got '{v}'"))?,
+        None => 0.8,
+    };
+    let min_lines = parse_int(ctx.params, "min_lines", 5);
+    let kinds: Vec<String> = ctx
+        .params
+        .get("kind")
+        .map(String::as_str)
+        .unwrap_or("function,method")
+        .split(',')
+        .map(|k| format!("'{}'", k.trim().replace('\'', "''")))
+        .collect();
+
+    let rows = ctx
+        .store
+        .run_query(
+            &format!(
+                "SELECT s.name, s.file_path, sp.start_line, sp.start_byte, sp.end_byte \
+                 FROM symbol s \
+                 JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+                 WHERE s.kind IN ({}) AND sp.end_line - sp.start_line + 1 >= {min_lines} \
+                 ORDER BY s.file_path, sp.start_byte",
+                kinds.join(", ")
+            ),
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to query symbols: {e}"))?;
+
+    struct Candidate {
+        name: String,
+        file: String,
+        line: i64,
+        shingles: HashSet<u64>,
+    }
+    let mut candidates = Vec::new();
+    for row in rows.rows {
+        let (Some(name), Some(file), Some(line), Some(start), Some(end)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_i64(&row[2]),
+            value_to_i64(&row[3]),
+            value_to_i64(&row[4]),
+        ) else {
+            continue;
+        };
+        let Some(source) = ctx.workspace.read_file(&file) else {
+            continue;
+        };
+        let Some(body) = source.get(start as usize..end as usize) else {
+            continue;
+        };
+        let shingles = shingles(body, &name);
+        candidates.push(Candidate {
+            name,
+            file,
+            line,
+            shingles,
+        });
+    }
+
+    // Union-find over pairs above the threshold; remember each symbol's
+    // closest match for the finding message.
+    let mut root: Vec<usize> = (0..candidates.len()).collect();
+    fn find(root: &mut [usize], i: usize) -> usize {
+        let mut i = i;
+        while root[i] != i {
+            root[i] = root[root[i]];
+            i = root[i];
+        }
+        i
+    }
+    let mut best: Vec<Option<(usize, f64)>> = vec![None; candidates.len()];
+    for i in 0..candidates.len() {
+        for j in (i + 1)..candidates.len() {
+            let (a, b) = (&candidates[i].shingles, &candidates[j].shingles);
+            // |A ∩ B| / |A ∪ B| can't exceed min/max of the set sizes.
+            let (small, large) = (a.len().min(b.len()), a.len().max(b.len()));
+            if large == 0 || (small as f64) < threshold * large as f64 {
+                continue;
+            }
+            let score = jaccard(a, b);
+            if score < threshold {
+                continue;
+            }
+            for (x, y) in [(i, j), (j, i)] {
+                if best[x].is_none_or(|(_, s)| score > s) {
+                    best[x] = Some((y, score));
+                }
+            }
+            let (ri, rj) = (find(&mut root, i), find(&mut root, j));
+            root[ri.max(rj)] = ri.min(rj);
+        }
+    }
+
+    let mut cluster_ids: HashMap<usize, i64> = HashMap::new();
+    let mut findings = Vec::new();
+    for (i, c) in candidates.iter().enumerate() {
+        let Some((other, score)) = best[i] else {
+            continue;
+        };
+        let r = find(&mut root, i);
+        let next_id = cluster_ids.len() as i64;
+        let cluster = *cluster_ids.entry(r).or_insert(next_id);
+        let o = &candidates[other];
+        findings.push(AuditFinding {
+            file: c.file.clone(),
+            line: c.line,
+            severity: "info".to_string(),
+            pattern: "near_duplicate".to_string(),
+            message: format!(
+                "{}: {:.0}% similar to {} ({}:{})",
+                c.name,
+                score * 100.0,
+                o.name,
+                o.file,
+                o.line
+            ),
+            extras: vec![
+                ("cluster".to_string(), serde_json::Value::from(cluster)),
+                ("similarity".to_string(), serde_json::Value::from(score)),
+            ],
+        });
+    }
+    Ok(QueryOutput::Findings(findings))
+}
+
+/// Hashed [`SHINGLE_SIZE`]-token windows over `body`. Occurrences of the
+/// symbol's own `name` are masked so a copy-pasted-and-renamed function
+/// scores the same as an exact copy.
+fn shingles(body: &str, name: &str) -> HashSet<u64> {
+    let tokens: Vec<&str> = tokenize(body)
+        .into_iter()
+        .map(|t| if t == name { "$name" } else { t })
+        .collect();
+    tokens
+        .windows(SHINGLE_SIZE.min(tokens.len()).max(1))
+        .map(|w| {
+            let mut h = DefaultHasher::new();
+            w.hash(&mut h);
+            h.finish()
+        })
+        .collect()
+}
+
+/// Split source text into identifier/number runs and single punctuation
+/// characters, dropping whitespace.
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut word_start: Option<usize> = None;
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() || c == '_' {
+            word_start.get_or_insert(i);
+            continue;
+        }
+        if let Some(s) = word_start.take() {
+            out.push(&text[s..i]);
+        }
+        if !c.is_whitespace() {
+            out.push(&text[i..i + c.len_utf8()]);
+        }
+    }
+    if let Some(s) = word_start {
+        out.push(&text[s..]);
+    }
+    out
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Java annotations that mark a JUnit test method.
+const JUNIT_ANNOTATIONS: &str = "'Test', 'ParameterizedTest', 'RepeatedTest', 'TestFactory'";
+
+/// tests — test functions and methods, one row per test.
+///
+/// Recognised markers: a Rust `#[test]` attribute, or any attribute
+/// path ending in `::test` (`#[tokio::test]`), read from the lines
+/// above each function; a JUnit `@Test` (or `@ParameterizedTest`,
+/// `@RepeatedTest`, `@TestFactory`) annotation; a Python function or
+/// method named `test*` in a test file, as pytest and unittest collect
+/// them; and a JS/TS `it(...)` or `test(...)` call, named by its first
+/// string argument. `describe` blocks group tests and aren't listed.
+///
+/// Params: `lang`, `file` (exact path). Rows: `file`, `name`, `line`,
+/// `marker`, ordered by file and line.
+fn tests(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let lang = language_clause(ctx.params, "language")?;
+    let mut params = BTreeMap::new();
+    let file = match ctx.params.get("file") {
+        Some(path) => {
+            params.insert("file".to_string(), Value::Text(path.clone()));
+            " AND file_path = $file"
+        }
+        None => "",
+    };
+    let symbol_rows = |language: &str, filter: &str, marker: &str| {
+        format!(
+            "SELECT s.file_path, s.name, sp.start_line, sp.start_byte, s.language, \
+                    '{marker}' AS marker \
+             FROM symbol s \
+             JOIN span sp ON sp.entity_id = s.id AND sp.file_path = s.file_path \
+             WHERE s.language = '{language}' AND s.kind IN ('function', 'method') {filter}"
+        )
+    };
+    let sql = format!(
+        "SELECT * FROM ({} UNION ALL {} UNION ALL {} UNION ALL \
+           SELECT c.file_path, c.callee_name, NULL, c.start_byte, f.language, 'call' \
+           FROM call_site c JOIN file f ON f.path = c.file_path \
+           WHERE f.language IN ('javascript', 'jsx', 'typescript', 'tsx') \
+             AND c.receiver IS NULL AND c.callee_name IN ('it', 'test')) \
+         WHERE true{lang}{file} ORDER BY file_path, start_byte",
+        symbol_rows("rust", "", "attribute"),
+        symbol_rows(
+            "java",
+            &format!(
+                "AND s.id IN (SELECT symbol_id FROM annotation WHERE name IN ({JUNIT_ANNOTATIONS}))"
+            ),
+            "annotation",
+        ),
+        symbol_rows(
+            "python",
+            "AND s.is_test AND starts_with(s.name, 'test')",
+            "name",
+        ),
+    );
+    let found = ctx
+        .store
+        .run_query(&sql, params)
+        .map_err(|e| anyhow!("failed to query test candidates: {e}"))?;
+
+    // Rows are grouped by file, so each source is read once.
+    let mut current: Option<(String, Option<String>)> = None;
+    let mut rows = Vec::new();
+    for row in found.rows {
+        let (Some(file), Some(name), Some(start_byte), Some(marker)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_i64(&row[3]),
+            value_to_string(&row[5]),
+        ) else {
+            continue;
+        };
+        if current.as_ref().is_none_or(|(f, _)| *f != file) {
+            let source = ctx.workspace.read_file(&file);
+            current = Some((file.clone(), source));
+        }
+        let source = current.as_ref().and_then(|(_, source)| source.as_deref());
+        let line = value_to_i64(&row[2]).unwrap_or_else(|| {
+            source
+                .and_then(|src| src.get(..start_byte as usize))
+                .map(|prefix| prefix.matches('\n').count() as i64 + 1)
+                .unwrap_or(0)
+        });
+        let (name, marker) = match marker.as_str() {
+            "attribute" => {
+                let Some(attr) = source.and_then(|src| rust_test_attribute(src, line)) else {
+                    continue;
+                };
+                (name, attr)
+            }
+            "annotation" => (name, "@Test".to_string()),
+            "name" => (name, "test*".to_string()),
+            _ => {
+                let title = source
+                    .and_then(|src| src.get(start_byte as usize..))
+                    .and_then(first_string_argument);
+                let marker = format!("{name}(...)");
+                (title.unwrap_or(name), marker)
+            }
+        };
+        rows.push(vec![
+            serde_json::Value::from(file),
+            serde_json::Value::from(name),
+            serde_json::Value::from(line),
+            serde_json::Value::from(marker),
+        ]);
+    }
+    let headers = ["file", "name", "line", "marker"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    Ok(QueryOutput::Rows { headers, rows })
+}
+
+/// The test attribute (`#[test]`, `#[tokio::test]`, ...) on the Rust
+/// function starting at 1-based `line`, looking at that line and the
+/// attribute and comment lines directly above it.
+fn rust_test_attribute(source: &str, line: i64) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let start = usize::try_from(line).ok()?.checked_sub(1)?;
+    let above = lines[..start.min(lines.len())]
+        .iter()
+        .rev()
+        .take_while(|l| {
+            let l = l.trim_start();
+            l.starts_with("#[") || l.starts_with("//")
+        });
+    std::iter::once(lines.get(start).copied().unwrap_or(""))
+        .chain(above.copied())
+        .flat_map(|l| l.match_indices("#[").map(move |(i, _)| &l[i + 2..]))
+        .find_map(|attr| {
+            let path = attr[..attr.find([']', '(']).unwrap_or(attr.len())].trim();
+            (path.rsplit("::").next() == Some("test")).then(|| format!("#[{path}]"))
+        })
+}
+
+/// The first string literal in `call`, the source text from a call's
+/// callee onward: the title of `it("adds", ...)`.
+fn first_string_argument(call: &str) -> Option<String> {
+    let args = &call[call.find('(')? + 1..];
+    let open = args.find(|c: char| !c.is_whitespace())?;
+    let quote = args[open..].chars().next()?;
+    if !matches!(quote, '"' | '\'' | '`') {
+        return None;
+    }
+    let body = &args[open + 1..];
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return Some(body[..i].to_string()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// todos — code-debt markers recorded in `comment.todo_kind`.
+///
+/// Params: `marker` (comma-separated, case-insensitive, e.g.
+/// `TODO,FIXME`). `FIXME`, `HACK` and `XXX` report as warnings; `TODO`
+/// and `NOTE` as info. The message is the text after the marker.
+fn todos(ctx: &Context<'_>) -> Result<QueryOutput> {
+    let mut sql = "SELECT c.file_path, c.start_byte, c.todo_kind, c.todo_text \
+                   FROM comment c WHERE c.todo_kind IS NOT NULL"
+        .to_string();
+    if let Some(marker) = ctx.params.get("marker") {
+        let markers: Vec<String> = marker
+            .split(',')
+            .map(|m| format!("'{}'", m.trim().to_uppercase().replace('\'', "''")))
+            .collect();
+        sql.push_str(&format!(" AND c.todo_kind IN ({})", markers.join(", ")));
+    }
+    sql.push_str(" ORDER BY c.file_path, c.start_byte");
+
+    let rows = ctx
+        .store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to query comments: {e}"))?;
+
+    // Rows are grouped by file, so each source is read once.
+    let mut current: Option<(String, Option<String>)> = None;
+    let mut findings = Vec::new();
+    for row in rows.rows {
+        let (Some(file), Some(start_byte), Some(kind)) = (
+            value_to_string(&row[0]),
+            value_to_i64(&row[1]),
+            value_to_string(&row[2]),
+        ) else {
+            continue;
+        };
+        if current.as_ref().is_none_or(|(f, _)| *f != file) {
+            let source = ctx.workspace.read_file(&file);
+            current = Some((file.clone(), source));
+        }
+        let line = current
+            .as_ref()
+            .and_then(|(_, source)| source.as_deref())
+            .and_then(|src| src.get(..start_byte as usize))
+            .map(|prefix| prefix.matches('\n').count() as i64 + 1)
+            .unwrap_or(0);
+        let severity = match kind.as_str() {
+            "FIXME" | "HACK" | "XXX" => "warning",
+            _ => "info",
+        };
+        findings.push(AuditFinding {
+            file,
+            line,
+            severity: severity.to_string(),
+            pattern: kind.to_lowercase(),
+            message: value_to_string(&row[3]).unwrap_or_else(|| kind.clone()),
+            extras: vec![("marker".to_string(), serde_json::Value::from(kind))],
+        });
+    }
+    Ok(QueryOutput::Findings(findings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use crate::graph::builder::GraphBuilder;
+
+    fn run_template(
+        dir: &std::path::Path,
+        langs: &[Language],
+        handler: Handler,
+        params: &[(&str, &str)],
+    ) -> QueryOutput {
+        try_run_template(dir, langs, handler, params).unwrap()
+    }
+
+    fn try_run_template(
+        dir: &std::path::Path,
+        langs: &[Language],
+        handler: Handler,
+        params: &[(&str, &str)],
+    ) -> Result<QueryOutput> {
+        let ws = Workspace::load(dir, langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, langs).build(&store).unwrap();
+        db::populate(&store, &graph, Some(&ws)).unwrap();
+        let params: BTreeMap<String, String> = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        handler(&Context {
+            store: &store,
+            workspace: &ws,
+            params: &params,
+        })
+    }
+
+    #[test]
+    fn outline_keeps_source_order_and_filters_by_kind() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("mix.ts"),
+            "function first() {}\nclass Alpha {\n  run() {}\n}\nfunction second() {}\nclass Beta {\n  stop() {}\n}\nfunction third() {}\n",
+        )
+        .unwrap();
+        let names = |params: &[(&str, &str)]| {
+            let out = run_template(dir.path(), &[Language::TypeScript], outline, params);
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| r[0].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&[("file", "mix.ts")]),
+            vec!["first", "Alpha", "run", "second", "Beta", "stop", "third"]
+        );
+        assert_eq!(
+            names(&[("file", "mix.ts"), ("kind", "method")]),
+            vec!["run", "stop"]
+        );
+        assert_eq!(
+            names(&[("file", "mix.ts"), ("kind", "class,function")]),
+            vec!["first", "Alpha", "second", "Beta", "third"]
+        );
+    }
+
+    #[test]
+    fn outline_nests_methods_under_their_class() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "class Greeter {\n  hello() {}\n  bye() {}\n}\nfunction after() {}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            outline,
+            &[("file", "a.ts"), ("nested", "true")],
+        );
+        let QueryOutput::Outline(roots) = out else {
+            panic!("expected nested outline");
+        };
+        let names: Vec<&str> = roots.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["Greeter", "after"]);
+        let children: Vec<&str> = roots[0].children.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(children, vec!["hello", "bye"]);
+    }
+
+    #[test]
+    fn outline_lsp_emits_nested_document_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "class Greeter {\n  hello() {}\n}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            outline,
+            &[("file", "a.ts"), ("lsp", "true")],
+        );
+        let QueryOutput::DocumentSymbols(roots) = out else {
+            panic!("expected document symbols");
+        };
+        assert_eq!(roots.len(), 1);
+        let class = &roots[0];
+        assert_eq!((class.name.as_str(), class.kind), ("Greeter", 5));
+        let pos = |line, character| LspPosition { line, character };
+        assert_eq!(class.range.start, pos(0, 0));
+        assert_eq!(class.range.end, pos(2, 1));
+        assert_eq!(class.selection_range.start, pos(0, 6));
+        assert_eq!(class.selection_range.end, pos(0, 13));
+
+        assert_eq!(class.children.len(), 1);
+        let method = &class.children[0];
+        assert_eq!((method.name.as_str(), method.kind), ("hello", 6));
+        assert_eq!(method.selection_range.start, pos(1, 2));
+        assert!(method.children.is_empty());
+
+        let json = serde_json::to_value(class).unwrap();
+        assert!(json.get("selectionRange").is_some());
+        assert_eq!(json["children"][0]["detail"], "method");
+    }
+
+    #[test]
+    fn outline_lsp_counts_utf16_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.ts"), "/* 😀 */ class Greeter {}\n").unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            outline,
+            &[("file", "a.ts"), ("lsp", "true")],
+        );
+        let QueryOutput::DocumentSymbols(roots) = out else {
+            panic!("expected document symbols");
+        };
+        let pos = |line, character| LspPosition { line, character };
+        // `/* 😀 */ ` is 11 bytes but 8 UTF-16 code units.
+        assert_eq!(roots[0].range.start, pos(0, 8));
+        assert_eq!(roots[0].range.end, pos(0, 24));
+        assert_eq!(roots[0].selection_range.start, pos(0, 14));
+        assert_eq!(roots[0].selection_range.end, pos(0, 21));
+    }
+
+    #[test]
+    fn document_symbol_selects_the_last_segment_of_a_qualified_name() {
+        let node = OutlineNode {
+            name: "App\\Models\\User".to_string(),
+            kind: "class".to_string(),
+            start_line: 1,
+            end_line: 1,
+            doc: None,
+            children: Vec::new(),
+            start_col: 0,
+            end_col: 13,
+        };
+        let symbol = document_symbol(node, &["class User {}"]);
+        let pos = |line, character| LspPosition { line, character };
+        assert_eq!(symbol.selection_range.start, pos(0, 6));
+        assert_eq!(symbol.selection_range.end, pos(0, 10));
+        assert_eq!(symbol.name, "App\\Models\\User");
+    }
+
+    #[test]
+    fn outline_with_comments_shows_first_doc_line() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "/// Adds two numbers.\n/// Wraps on overflow.\npub fn add() {}\n\nfn plain() {}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            outline,
+            &[
+                ("file", "lib.rs"),
+                ("nested", "true"),
+                ("with_comments", "true"),
+            ],
+        );
+        let QueryOutput::Outline(roots) = out else {
+            panic!("expected nested outline");
+        };
+        let docs: Vec<(&str, Option<&str>)> = roots
+            .iter()
+            .map(|n| (n.name.as_str(), n.doc.as_deref()))
+            .collect();
+        assert_eq!(
+            docs,
+            vec![("add", Some("Adds two numbers.")), ("plain", None)]
+        );
+    }
+
+    #[test]
+    fn outline_depth_limits_nesting() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "class Greeter {\n  hello() {}\n}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            outline,
+            &[("file", "a.ts"), ("depth", "1")],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected flat rows");
+        };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][0], serde_json::Value::from("Greeter"));
+
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "mod outer {\n    mod inner {\n        fn leaf() {}\n    }\n}\n",
+        )
+        .unwrap();
+        let names = |depth: &str| {
+            let out = run_template(
+                dir.path(),
+                &[Language::Rust],
+                outline,
+                &[("file", "lib.rs"), ("depth", depth)],
+            );
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected flat rows");
+            };
+            rows.into_iter()
+                .map(|row| row[0].clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("1"), vec![serde_json::Value::from("outer")]);
+        assert_eq!(
+            names("2"),
+            vec![
+                serde_json::Value::from("outer"),
+                serde_json::Value::from("inner")
+            ]
+        );
+        assert_eq!(names("3").len(), 3);
+
+        for (key, value, expects) in [
+            ("depth", "-1", "expects a non-negative integer"),
+            ("nested", "yes", "expects true or false"),
+            ("with_comments", "1", "expects true or false"),
+            ("lsp", "True", "expects true or false"),
+        ] {
+            let err = try_run_template(
+                dir.path(),
+                &[Language::Rust],
+                outline,
+                &[("file", "lib.rs"), (key, value)],
+            )
+            .err()
+            .unwrap();
+            assert!(err.to_string().contains(expects), "{key}={value}: {err}");
+        }
+    }
+
+    const SUM_A: &str = "fn sum_a(xs: &[i32]) -> i32 {\n    let mut total = 0;\n    for x in xs {\n        total += x * 2;\n    }\n    total\n}\n";
+    const SUM_B: &str = "fn sum_b(xs: &[i32]) -> i32 {\n    let mut total = 0;\n    for x in xs { total += x * 2; }\n    total\n}\n";
+    const OTHER: &str = "fn render(name: &str) -> String {\n    let greeting = format!(\"hi {name}\");\n    greeting.to_uppercase()\n}\n";
+
+    #[test]
+    fn shingle_similarity_separates_copies_from_distinct_bodies() {
+        let a = shingles(SUM_A, "sum_a");
+        let b = shingles(SUM_B, "sum_b");
+        let c = shingles(OTHER, "render");
+        assert!(jaccard(&a, &b) > 0.9, "got {}", jaccard(&a, &b));
+        assert!(jaccard(&a, &c) < 0.9, "got {}", jaccard(&a, &c));
+    }
+
+    #[test]
+    fn similar_symbols_clusters_near_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), format!("{SUM_A}\n{OTHER}")).unwrap();
+        std::fs::write(dir.path().join("b.rs"), SUM_B).unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            similar_symbols,
+            &[("threshold", "0.9"), ("min_lines", "1")],
+        );
+        let QueryOutput::Findings(findings) = out else {
+            panic!("expected findings");
+        };
+        let mut files: Vec<&str> = findings.iter().map(|f| f.file.as_str()).collect();
+        files.sort();
+        assert_eq!(files, vec!["a.rs", "b.rs"], "got {findings:?}");
+        assert!(findings.iter().all(|f| f.message.contains("sum_")));
+
+        for bad in ["high", "8", "-0.1"] {
+            let err = try_run_template(
+                dir.path(),
+                &[Language::Rust],
+                similar_symbols,
+                &[("threshold", bad)],
+            )
+            .err()
+            .unwrap();
+            assert!(
+                err.to_string().contains("--param threshold expects"),
+                "{bad}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn doc_coverage_counts_documented_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "/// Adds one.\npub fn documented(x: i32) -> i32 { x + 1 }\n\
+             pub fn undocumented() {}\n\
+             fn private() {}\n",
+        )
+        .unwrap();
+
+        let out = run_template(dir.path(), &[Language::Rust], doc_coverage, &[]);
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(headers[3], "coverage_pct");
+        let total = rows.last().unwrap();
+        assert_eq!(total[0], "(total)");
+        assert_eq!(total[1], 1);
+        assert_eq!(total[2], 2);
+        assert_eq!(total[3], 50.0);
+        assert_eq!(total[4], "##########..........");
+        assert_eq!(rows[0][0], "src");
+
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            doc_coverage,
+            &[("undocumented", "true")],
+        );
+        let QueryOutput::Findings(findings) = out else {
+            panic!("expected findings");
+        };
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("undocumented"));
+    }
+
+    #[test]
+    fn doc_coverage_commented_lists_plain_commented_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "/// Adds one.\npub fn documented() {}\n\n\
+             // Adds two.\npub fn plain_commented() {}\n\n\
+             pub fn bare() {}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            doc_coverage,
+            &[("undocumented", "true"), ("commented", "true")],
+        );
+        let QueryOutput::Findings(findings) = out else {
+            panic!("expected findings");
+        };
+        assert_eq!(findings.len(), 1, "got {findings:?}");
+        assert_eq!(findings[0].file, "lib.rs");
+        assert_eq!(findings[0].line, 5);
+        assert!(findings[0].message.contains("plain_commented"));
+
+        let err = try_run_template(
+            dir.path(),
+            &[Language::Rust],
+            doc_coverage,
+            &[("undocumented", "1")],
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("expects true or false"), "{err}");
+    }
+
+    #[test]
+    fn files_filters_on_line_count() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.path().join("big.rs"), "fn b() {}\n".repeat(30)).unwrap();
+        let out = run_template(dir.path(), &[Language::Rust], files, &[("min_lines", "10")]);
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][0], "big.rs");
+        assert_eq!(rows[0][2], 30);
+    }
+
+    #[test]
+    fn density_ranks_a_dense_small_file_above_a_sparse_large_one() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("dense.rs"),
+            "struct A;\nstruct B;\nfn c() {}\nfn d() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("sparse.rs"),
+            format!("fn long() {{\n{}}}\n", "    let x = 1;\n".repeat(38)),
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            density,
+            &[("threshold", "50")],
+        );
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(
+            headers,
+            vec![
+                "file",
+                "language",
+                "line_count",
+                "symbols",
+                "density",
+                "split_candidate"
+            ]
+        );
+        let summary: Vec<(&str, i64, bool)> = rows
+            .iter()
+            .map(|r| {
+                (
+                    r[0].as_str().unwrap(),
+                    r[3].as_i64().unwrap(),
+                    r[5].as_bool().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("dense.rs", 4, true), ("sparse.rs", 1, false)]
+        );
+        assert_eq!(rows[0][4], 100.0);
+    }
+
+    #[test]
+    fn files_duplicate_names_groups_shared_base_names() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["app", "app/admin", "lib"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+            std::fs::write(dir.path().join(sub).join("index.ts"), "export {};\n").unwrap();
+        }
+        std::fs::write(dir.path().join("app/util.ts"), "export {};\n").unwrap();
+        std::fs::write(dir.path().join("util.ts"), "export {};\n").unwrap();
+        std::fs::write(dir.path().join("main.ts"), "export {};\n").unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            files,
+            &[("duplicate_names", "true")],
+        );
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(headers, vec!["name", "count", "directories"]);
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    serde_json::json!("index.ts"),
+                    serde_json::json!(3),
+                    serde_json::json!(["app", "app/admin", "lib"]),
+                ],
+                vec![
+                    serde_json::json!("util.ts"),
+                    serde_json::json!(2),
+                    serde_json::json!([".", "app"]),
+                ],
+            ]
+        );
+        let err = try_run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            files,
+            &[("duplicate_names", "on")],
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("expects true or false"), "{err}");
+    }
+
+    #[test]
+    fn files_largest_and_smallest_rank_by_lines_or_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.path().join("big.rs"), "\n".repeat(40) + "fn b() {}\n").unwrap();
+        std::fs::write(dir.path().join("busy.rs"), "fn c() {}\n".repeat(5)).unwrap();
+        let paths = |params: &[(&str, &str)]| {
+            let out = run_template(dir.path(), &[Language::Rust], files, params);
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| r[0].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(&[("largest", "1")]), vec!["big.rs"]);
+        assert_eq!(paths(&[("smallest", "2")]), vec!["small.rs", "busy.rs"]);
+        assert_eq!(
+            paths(&[("largest", "1"), ("by", "symbols")]),
+            vec!["busy.rs"]
+        );
+        let err = try_run_template(dir.path(), &[Language::Rust], files, &[("largest", "-1")])
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string()
+                .contains("--param largest expects a non-negative integer"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn files_orphans_lists_never_imported_non_entrypoints() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/index.ts"),
+            "import { used } from './used';\nused();\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/used.ts"),
+            "export function used() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/dead.ts"),
+            "export function dead() {}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            files,
+            &[("orphans", "true")],
+        );
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(headers[3], "directory");
+        assert_eq!(rows.len(), 1, "got {rows:?}");
+        assert_eq!(rows[0][0], "src/dead.ts");
+        assert_eq!(rows[0][3], "src");
+        let err = try_run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            files,
+            &[("orphans", "TRUE")],
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("expects true or false"), "{err}");
+    }
+
+    #[test]
+    fn search_min_span_excludes_short_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        let long_body = "    let _ = 1;\n".repeat(12);
+        std::fs::write(
+            dir.path().join("a.rs"),
+            format!("fn short() {{\n    let _ = 1;\n}}\nfn long() {{\n{long_body}}}\n"),
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            search,
+            &[("kind", "function"), ("min_span", "10")],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        let names: Vec<&str> = rows.iter().filter_map(|r| r[0].as_str()).collect();
+        assert_eq!(names, vec!["long"]);
+    }
+
+    #[test]
+    fn search_loc_skips_blank_and_comment_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.rs"),
+            "fn documented() {\n    // step one\n\n    let a = 1;\n    /* block\n       comment */\n    let b = a;\n}\nfn tiny() {}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            search,
+            &[("kind", "function"), ("min_loc", "2")],
+        );
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(headers[6], "loc");
+        assert_eq!(rows.len(), 1, "tiny() has one code line: {rows:?}");
+        assert_eq!(rows[0][0], "documented");
+        let span = rows[0][5].as_i64().unwrap();
+        let loc = rows[0][6].as_i64().unwrap();
+        assert_eq!(loc, 4);
+        assert!(loc < span + 1, "loc {loc} vs {} physical lines", span + 1);
+    }
+
+    #[test]
+    fn search_context_adds_the_signature_line_as_snippet() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.rs"),
+            "// header\npub fn greet(name: &str) -> String {\n    format!(\"hi {name}\")\n}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust],
+            search,
+            &[("name", "greet"), ("kind", "function"), ("context", "2")],
+        );
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(headers[7], "snippet");
+        assert_eq!(
+            rows[0][7],
+            "pub fn greet(name: &str) -> String {\n    format!(\"hi {name}\")"
+        );
+    }
+
+    #[test]
+    fn search_decorator_matches_ts_and_python_decorators() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("foo.ts"),
+            "@Component({ selector: 'app-foo' })\nexport class Foo {}\nclass Bar {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("util.py"),
+            "class Util:\n    @staticmethod\n    def bar():\n        pass\n\n    def baz(self):\n        pass\n",
+        )
+        .unwrap();
+        let names = |decorator: &str| {
+            let out = run_template(
+                dir.path(),
+                &[Language::TypeScript, Language::Python],
+                search,
+                &[("decorator", decorator)],
+            );
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| r[0].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("Component"), vec!["Foo"]);
+        assert_eq!(names("staticmethod"), vec!["bar"]);
+        assert!(names("Injectable").is_empty());
+    }
+
+    #[test]
+    fn search_extends_finds_cpp_and_python_subclasses() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("shapes.cpp"),
+            "class B {};\nclass D : public B {};\nclass E : public ext::Widget {};\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("models.py"),
+            "class D(B):\n    pass\n\nclass M(ext.Widget):\n    pass\n\nclass Plain:\n    pass\n",
+        )
+        .unwrap();
+        let found = |base: &str| {
+            let out = run_template(
+                dir.path(),
+                &[Language::Cpp, Language::Python],
+                search,
+                &[("extends", base)],
+            );
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| format!("{}:{}", r[2].as_str().unwrap(), r[0].as_str().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        // `B` is defined in shapes.cpp only; the Python base is unresolved.
+        assert_eq!(found("B"), vec!["models.py:D", "shapes.cpp:D"]);
+        assert_eq!(found("Widget"), vec!["models.py:M", "shapes.cpp:E"]);
+        assert!(found("Plain").is_empty());
+    }
+
+    #[test]
+    fn search_annotation_finds_annotated_java_classes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("User.java"),
+            "@Entity\n@Table(name = \"users\")\npublic class User { }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Api.java"),
+            "@RestController\npublic class Api { @GetMapping(\"/\") public void list() {} }\n",
+        )
+        .unwrap();
+        let names = |annotation: &str| {
+            let out = run_template(
+                dir.path(),
+                &[Language::Java],
+                search,
+                &[("annotation", annotation)],
+            );
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| r[0].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("Entity"), vec!["User"]);
+        assert_eq!(names("Table"), vec!["User"]);
+        assert_eq!(names("RestController"), vec!["Api"]);
+        assert_eq!(names("GetMapping"), vec!["list"]);
+        assert!(names("Service").is_empty());
+    }
+
+    #[test]
+    fn search_exact_and_word_narrow_the_name_match() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("main.rs"),
+            "fn main() {}\nfn main_loop() {}\nfn domain() {}\nfn run_main() {}\n",
+        )
+        .unwrap();
+        let names = |params: &[(&str, &str)]| {
+            let out = run_template(dir.path(), &[Language::Rust], search, params);
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| r[0].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&[("name", "main")]),
+            vec!["main", "main_loop", "domain", "run_main"]
+        );
+        assert_eq!(names(&[("name", "main"), ("exact", "true")]), vec!["main"]);
+        assert!(names(&[("name", "Main"), ("exact", "true")]).is_empty());
+        assert_eq!(
+            names(&[("name", "main"), ("word", "true")]),
+            vec!["main", "main_loop", "run_main"]
+        );
+        assert_eq!(
+            names(&[("name", "main"), ("exact", "true"), ("kind", "function")]),
+            vec!["main"]
+        );
+        let err = try_run_template(
+            dir.path(),
+            &[Language::Rust],
+            search,
+            &[("name", "main"), ("exact", "true"), ("word", "true")],
+        )
+        .err()
+        .unwrap();
+        assert!(
+            err.to_string()
+                .contains("--param exact and word are mutually exclusive"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn search_match_all_narrows_match_any() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("user")).unwrap();
+        std::fs::write(
+            dir.path().join("user/service.py"),
+            "def user_service():\n    pass\n\ndef user_model():\n    pass\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("billing.py"),
+            "def billing_service():\n    pass\n\ndef render():\n    pass\n",
+        )
+        .unwrap();
+        let names = |params: &[(&str, &str)]| {
+            let out = run_template(dir.path(), &[Language::Python], search, params);
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            let mut names: Vec<String> = rows
+                .iter()
+                .map(|r| r[0].as_str().unwrap().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(
+            names(&[("name", "user service"), ("match", "any")]),
+            vec!["billing_service", "user_model", "user_service"]
+        );
+        assert_eq!(
+            names(&[("name", "user service"), ("match", "all")]),
+            vec!["user_service"]
+        );
+        assert_eq!(
+            names(&[("name", "user service"), ("match", "all"), ("in", "path")]),
+            vec!["user_model", "user_service"]
+        );
+        assert!(names(&[("name", "user service")]).is_empty());
+    }
+
+    #[test]
+    fn search_rejects_blank_terms_and_non_boolean_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "pub fn parse_args() {}\n").unwrap();
+        let err = |params: &[(&str, &str)]| {
+            try_run_template(dir.path(), &[Language::Rust], search, params)
+                .err()
+                .expect("expected an error")
+                .to_string()
+        };
+        let blank = err(&[("name", "   "), ("match", "all")]);
+        assert!(
+            blank.contains("--param name expects at least one search term"),
+            "{blank}"
+        );
+        let message = err(&[("name", "parse"), ("in", "doc")]);
+        assert!(
+            message.contains("--param in expects name or path, got 'doc'"),
+            "{message}"
+        );
+        let message = err(&[("name", "parse"), ("match", "some")]);
+        assert!(
+            message.contains("--param match expects any or all, got 'some'"),
+            "{message}"
+        );
+        for flag in ["exact", "word", "exclude_tests", "async", "deprecated"] {
+            let message = err(&[("name", "parse"), (flag, "yes")]);
+            assert!(
+                message.contains(&format!("--param {flag} expects true or false")),
+                "{message}"
+            );
+        }
+    }
+
+    #[test]
+    fn search_deprecated_finds_marked_symbols_across_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("api.ts"),
+            "/** @deprecated use next() */\nexport function prev() {}\n\n\
+             /** Current. */\nexport const next = () => 1;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("api.py"),
+            "import warnings\n\n\
+             def old():\n    warnings.warn(\"gone\", DeprecationWarning)\n\n\
+             @deprecated(\"use new\")\ndef older():\n    pass\n\n\
+             def new():\n    pass\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("Api.cs"),
+            "public class Api {\n    [Obsolete(\"use Run\")]\n    public void Go() {}\n    \
+             public void Run() {}\n}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript, Language::Python, Language::CSharp],
+            search,
+            &[("deprecated", "true")],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        let mut names: Vec<&str> = rows.iter().map(|r| r[0].as_str().unwrap()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Go", "old", "older", "prev"]);
+    }
+
+    #[test]
+    fn search_async_splits_async_and_sync_functions() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("a.rs", "async fn rs_async() {}\nfn rs_sync() {}\n"),
+            (
+                "b.py",
+                "async def py_async():\n    pass\n\ndef py_sync():\n    pass\n",
+            ),
+            (
+                "c.ts",
+                "export async function tsAsync() {}\nexport function tsSync() {}\n",
+            ),
+            (
+                "d.js",
+                "async function jsAsync() {}\nfunction jsSync() {}\n",
+            ),
+            (
+                "e.cs",
+                "class E {\n    async Task CsAsync() {}\n    void CsSync() {}\n}\n",
+            ),
+        ];
+        for (file, source) in files {
+            std::fs::write(dir.path().join(file), source).unwrap();
+        }
+        let langs = [
+            Language::Rust,
+            Language::Python,
+            Language::TypeScript,
+            Language::JavaScript,
+            Language::CSharp,
+        ];
+        let names = |is_async: &str| {
+            let out = run_template(
+                dir.path(),
+                &langs,
+                search,
+                &[("kind", "function,method"), ("async", is_async)],
+            );
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| r[0].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names("true"),
+            vec!["rs_async", "py_async", "tsAsync", "jsAsync", "CsAsync"]
+        );
+        assert_eq!(
+            names("false"),
+            vec!["rs_sync", "py_sync", "tsSync", "jsSync", "CsSync"]
+        );
+    }
+
+    #[test]
+    fn search_exclude_tests_drops_test_file_symbols() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("foo.ts"), "export function prod() {}\n").unwrap();
+        std::fs::write(
+            dir.path().join("foo.test.ts"),
+            "export function checkProd() {}\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            search,
+            &[("kind", "function"), ("exclude_tests", "true")],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        let names: Vec<&str> = rows.iter().filter_map(|r| r[0].as_str()).collect();
+        assert_eq!(names, vec!["prod"]);
+    }
+
+    #[test]
+    fn search_rejects_non_integer_bounds() {
+        let mut params = BTreeMap::new();
+        params.insert("min_span".to_string(), "ten".to_string());
+        assert!(parse_bound(&params, "min_span").is_err());
+        assert_eq!(parse_bound(&params, "max_span").unwrap(), None);
+    }
+
+    #[test]
+    fn comment_ratio_ranks_an_uncommented_file_below_a_commented_one() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("noted.py"),
+            "# Adds numbers.\n# Both must be ints.\ndef add(a, b):\n    \"\"\"Sum.\"\"\"\n    return a + b  # plain\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("bare.py"),
+            "def sub(a, b):\n    return a - b\n\ndef mul(a, b):\n    return a * b\n",
+        )
+        .unwrap();
+        let out = run_template(dir.path(), &[Language::Python], comment_ratio, &[]);
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        let files: Vec<&str> = rows.iter().map(|r| r[0].as_str().unwrap()).collect();
+        assert_eq!(files, vec!["bare.py", "noted.py", "(total)"]);
+        assert_eq!(rows[0][2], 0);
+        assert_eq!(rows[0][4], 0.0);
+        assert_eq!(rows[0][5], true);
+        assert_eq!(rows[1][2], 4);
+        assert_eq!(rows[1][3], 1);
+        assert!(rows[1][4].as_f64().unwrap() > rows[0][4].as_f64().unwrap());
+        assert_eq!(rows[1][5], false);
+        assert_eq!(rows[2][1], 10);
+        assert_eq!(rows[2][2], 4);
+    }
+
+    #[test]
+    fn comments_near_pairs_a_detached_comment_with_the_next_symbol() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.py"),
+            "# Loads the settings file.\nimport os\ndef load():\n    pass\n",
+        )
+        .unwrap();
+        let symbols = |params: &[(&str, &str)]| {
+            let out = run_template(dir.path(), &[Language::Python], comments, params);
+            let QueryOutput::Rows { headers, rows } = out else {
+                panic!("expected rows");
+            };
+            let symbol = headers.iter().position(|h| h == "symbol").unwrap();
+            rows.iter()
+                .map(|r| r[symbol].as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(symbols(&[]), vec![None]);
+        assert_eq!(symbols(&[("near", "3")]), vec![Some("load".to_string())]);
+        assert!(symbols(&[("near", "1")]).is_empty());
+
+        std::fs::write(dir.path().join("it's.py"), "# Quoted.\nx = 1\n").unwrap();
+        assert_eq!(symbols(&[("file", "it's.py")]), vec![None]);
+        assert_eq!(symbols(&[("file", "a.py")]), vec![None]);
+    }
+
+    #[test]
+    fn tests_finds_rust_test_functions_and_js_it_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "pub fn add() {}\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn adds() {}\n\n    \
+             #[tokio::test]\n    // async\n    async fn adds_later() {}\n\n    fn helper() {}\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("add.test.js"),
+            "describe(\"add\", () => {\n  it(\"adds two numbers\", () => {\n    expect(1).toBe(1);\n  });\n});\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust, Language::JavaScript],
+            tests,
+            &[],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        let rows: Vec<(&str, &str, i64, &str)> = rows
+            .iter()
+            .map(|r| {
+                (
+                    r[0].as_str().unwrap(),
+                    r[1].as_str().unwrap(),
+                    r[2].as_i64().unwrap(),
+                    r[3].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("add.test.js", "adds two numbers", 2, "it(...)"),
+                ("lib.rs", "adds", 6, "#[test]"),
+                ("lib.rs", "adds_later", 10, "#[tokio::test]"),
+            ]
+        );
+
+        std::fs::write(dir.path().join("it's.rs"), "#[test]\nfn quoted() {}\n").unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::Rust, Language::JavaScript],
+            tests,
+            &[("file", "it's.rs")],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows.len(), 1, "{rows:?}");
+        assert_eq!(rows[0][1], "quoted");
+    }
+
+    #[test]
+    fn todos_reports_marker_and_text() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.rs"),
+            "fn a() {}\n// TODO: fix this\nfn b() {}\n// FIXME handle errors\n",
+        )
+        .unwrap();
+        let out = run_template(dir.path(), &[Language::Rust], todos, &[("marker", "todo")]);
+        let QueryOutput::Findings(findings) = out else {
+            panic!("expected findings");
+        };
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].pattern, "todo");
+        assert_eq!(findings[0].line, 2);
+        assert_eq!(findings[0].message, "fix this");
+    }
+
+    #[test]
+    fn collisions_reports_names_defined_in_several_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.py"), "def init():\n    i = 1\n").unwrap();
+        std::fs::write(dir.path().join("b.py"), "def init():\n    i = 2\n").unwrap();
+        std::fs::write(dir.path().join("c.py"), "def other():\n    pass\n").unwrap();
+        let out = run_template(dir.path(), &[Language::Python], collisions, &[]);
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(rows.len(), 1, "locals must not collide: {rows:?}");
+        assert_eq!(rows[0][0], "init");
+        assert_eq!(rows[0][1], 2);
+        assert_eq!(rows[0][2], serde_json::json!(["a.py", "b.py"]));
+        assert_eq!(rows[0][3], serde_json::json!(["function"]));
+    }
+
+    #[test]
+    fn hotspots_rank_the_class_with_many_methods_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let methods: String = (0..20).map(|i| format!("  m{i}() {{}}\n")).collect();
+        std::fs::write(
+            dir.path().join("god.ts"),
+            format!("class God {{\n{methods}}}\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("small.ts"),
+            "class Small {\n  a() {}\n  b() {}\n}\n",
+        )
+        .unwrap();
+        let out = run_template(dir.path(), &[Language::TypeScript], hotspots, &[]);
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        let files: Vec<&serde_json::Value> = rows
+            .iter()
+            .filter(|r| r[0] == "file")
+            .map(|r| &r[1])
+            .collect();
+        assert_eq!(files, vec!["god.ts", "small.ts"]);
+        let types: Vec<&serde_json::Value> = rows
+            .iter()
+            .filter(|r| r[0] == "type")
+            .map(|r| &r[2])
+            .collect();
+        assert_eq!(types, vec!["God"], "Small is under the threshold");
+        let god = rows.iter().find(|r| r[0] == "type").unwrap();
+        assert_eq!(god[8], 20);
+    }
+
+    #[test]
+    fn callers_follow_reexports_through_a_barrel() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/lib")).unwrap();
+        let files = [
+            ("src/lib/foo.ts", "export function Foo() {}\n"),
+            ("src/lib/index.ts", "export { Foo as Bar } from './foo';\n"),
+            (
+                "src/app.ts",
+                "import { Bar } from './lib';\nexport function main() {\n  Bar();\n}\n",
+            ),
+            (
+                "src/direct.ts",
+                "import { Foo } from './lib/foo';\nexport function run() {\n  Foo();\n}\n",
+            ),
+        ];
+        for (path, source) in files {
+            std::fs::write(dir.path().join(path), source).unwrap();
+        }
+
+        let callers_of = |params: &[(&str, &str)]| {
+            let out = run_template(dir.path(), &[Language::TypeScript], callers, params);
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.iter()
+                .map(|r| {
+                    (
+                        r[0].as_str().unwrap().to_string(),
+                        r[4].as_str().unwrap().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            callers_of(&[("name", "Foo")]),
+            vec![("run".to_string(), "Foo".to_string())]
+        );
+        assert_eq!(
+            callers_of(&[("name", "Foo"), ("follow_reexports", "true")]),
+            vec![
+                ("main".to_string(), "Bar".to_string()),
+                ("run".to_string(), "Foo".to_string()),
+            ]
+        );
+        let err = try_run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            callers,
+            &[("name", "Foo"), ("follow_reexports", "yes")],
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("expects true or false"), "{err}");
+    }
+
+    #[test]
+    fn barrel_cycles_reports_index_files_re_exporting_each_other() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["src/a", "src/b", "src/c"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        let files = [
+            (
+                "src/a/index.ts",
+                "export * from '../b';\nexport { x } from './x';\n",
+            ),
+            ("src/a/x.ts", "export const x = 1;\n"),
+            ("src/b/index.ts", "export { y } from '../a/index';\n"),
+            // A plain import loop through a barrel is not a re-export loop.
+            (
+                "src/c/index.ts",
+                "import { z } from './z';\nexport const c = z;\n",
+            ),
+            (
+                "src/c/z.ts",
+                "import { c } from './index';\nexport const z = 1;\n",
+            ),
+        ];
+        for (path, source) in files {
+            std::fs::write(dir.path().join(path), source).unwrap();
+        }
+
+        let out = run_template(dir.path(), &[Language::TypeScript], barrel_cycles, &[]);
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(headers, vec!["barrels", "size", "loop"]);
+        assert_eq!(
+            rows,
+            vec![vec![
+                serde_json::json!("src/a/index.ts, src/b/index.ts"),
+                serde_json::json!(2),
+                serde_json::json!("src/a/index.ts -> src/b/index.ts -> src/a/index.ts"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn imports_unresolved_lists_only_dangling_internal_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "import { b } from './b';\nimport { x, y } from './gone';\nimport React from 'react';\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("b.ts"), "export const b = 1;\n").unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            imports,
+            &[("unresolved", "true")],
+        );
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(headers[2], "module_specifier");
+        assert_eq!(rows.len(), 1, "{rows:?}");
+        assert_eq!(rows[0][0], "a.ts");
+        assert_eq!(rows[0][2], "./gone");
+    }
+
+    #[test]
+    fn composition_counts_imports_across_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("app.js"),
+            "import { greet } from './lib';\nimport { other } from './util';\ngreet();\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("util.js"), "export function other() {}\n").unwrap();
+        std::fs::write(
+            dir.path().join("lib.ts"),
+            "export function greet(): void {}\nexport const x = 1;\n",
+        )
+        .unwrap();
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript, Language::JavaScript],
+            composition,
+            &[],
+        );
+        let QueryOutput::Rows { headers, rows } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(
+            headers,
+            vec![
+                "language",
+                "files",
+                "lines",
+                "symbols",
+                "imports_javascript",
+                "imports_typescript"
+            ]
+        );
+        let js = rows.iter().find(|r| r[0] == "javascript").unwrap();
+        assert_eq!(js[1], 2);
+        assert_eq!(js[2], 4);
+        assert_eq!((js[4].as_i64(), js[5].as_i64()), (Some(1), Some(1)));
+        let ts = rows.iter().find(|r| r[0] == "typescript").unwrap();
+        assert_eq!(
+            (ts[1].as_i64(), ts[4].as_i64(), ts[5].as_i64()),
+            (Some(1), Some(0), Some(0))
+        );
+        assert!(ts[3].as_i64().unwrap() >= 2, "greet and x: {ts:?}");
+    }
+
+    #[test]
+    fn module_graph_sums_cross_directory_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["src/a", "src/b", "lib"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        let files = [
+            (
+                "src/a/x.ts",
+                "import { y } from '../b/y';\nimport { z } from '../b/z';\nimport { w } from './w';\n",
+            ),
+            (
+                "src/a/w.ts",
+                "import { u } from '../../lib/u';\nexport const w = 1;\n",
+            ),
+            ("src/b/y.ts", "export const y = 1;\n"),
+            ("src/b/z.ts", "export const z = 1;\n"),
+            ("lib/u.ts", "export const u = 1;\n"),
+        ];
+        for (path, source) in files {
+            std::fs::write(dir.path().join(path), source).unwrap();
+        }
+
+        let edges = |group_by: &str| {
+            let out = run_template(
+                dir.path(),
+                &[Language::TypeScript],
+                module_graph,
+                &[("group_by", group_by)],
+            );
+            let QueryOutput::Rows { rows, .. } = out else {
+                panic!("expected rows");
+            };
+            rows.into_iter()
+                .map(|r| {
+                    format!(
+                        "{} -> {} ({})",
+                        r[0].as_str().unwrap(),
+                        r[1].as_str().unwrap(),
+                        r[2]
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            edges("directory"),
+            vec!["src/a -> src/b (2)", "src/a -> lib (1)"],
+            "the src/a-internal import is dropped"
+        );
+        assert_eq!(edges("top-dir"), vec!["src -> lib (1)"]);
+        assert_eq!(edges("file").len(), 4);
+    }
+
+    #[test]
+    fn imports_carry_manifest_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("app.ts"),
+            "import React from 'react';\nimport { createRoot } from 'react-dom/client';\nimport { x } from './x';\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("x.ts"), "export const x = 1;\n").unwrap();
+        let manifest = dir.path().join("package.json");
+        std::fs::write(&manifest, r#"{"dependencies": {"react": "^18.2.0"}}"#).unwrap();
+        let manifest = manifest.to_string_lossy().into_owned();
+
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            imports,
+            &[("manifest", &manifest), ("external", "true")],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        let found: Vec<(&serde_json::Value, &serde_json::Value)> =
+            rows.iter().map(|r| (&r[6], &r[7])).collect();
+        assert_eq!(
+            found,
+            vec![
+                (&serde_json::json!("react"), &serde_json::json!("^18.2.0")),
+                (&serde_json::json!("react-dom"), &serde_json::Value::Null),
+            ]
+        );
+        let err = try_run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            imports,
+            &[("external", "yes")],
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("expects true or false"), "{err}");
+    }
+
+    #[test]
+    fn imports_duplicates_reports_redundant_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("app.ts"),
+            "import { a } from './x';\nimport { a, b } from './x';\n\
+             import * as y from './y';\nimport { c } from './y';\n\
+             import { d } from './z';\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("x.ts"), "export const a = 1, b = 2;\n").unwrap();
+        std::fs::write(dir.path().join("y.ts"), "export const c = 1;\n").unwrap();
+        std::fs::write(dir.path().join("z.ts"), "export const d = 1;\n").unwrap();
+
+        let out = run_template(
+            dir.path(),
+            &[Language::TypeScript],
+            imports,
+            &[("duplicates", "true")],
+        );
+        let QueryOutput::Rows { rows, .. } = out else {
+            panic!("expected rows");
+        };
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    serde_json::json!("app.ts"),
+                    serde_json::json!("./x"),
+                    serde_json::json!("a"),
+                    serde_json::json!(2),
+                    serde_json::json!("duplicate"),
+                ],
+                vec![
+                    serde_json::json!("app.ts"),
+                    serde_json::json!("./y"),
+                    serde_json::json!("*"),
+                    serde_json::json!(2),
+                    serde_json::json!("namespace_and_named"),
+                ],
+            ]
+        );
+        for key in ["duplicates", "unresolved"] {
+            let err = try_run_template(dir.path(), &[Language::TypeScript], imports, &[(key, "1")])
+                .err()
+                .unwrap();
+            assert!(err.to_string().contains("expects true or false"), "{err}");
+        }
+    }
+}
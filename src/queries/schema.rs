@@ -0,0 +1,195 @@
+//! Column statistics for a built fact store.
+//!
+//! Backs `projects schema <name>`: every base table with its row count,
+//! and per column the declared type, null count and distinct count. Text
+//! columns with few distinct values (`kind`, `language`, `error_type`)
+//! also list each value with its count, so the vocabulary is visible
+//! before writing SQL against it.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, anyhow};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::db::DbStore;
+
+use super::runner::{value_to_i64, value_to_string};
+
+/// Text columns with at most this many distinct values list them.
+const MAX_LISTED_VALUES: i64 = 20;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TableStats {
+    pub name: String,
+    pub rows: i64,
+    pub columns: Vec<ColumnStats>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ColumnStats {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub nulls: i64,
+    pub distinct: i64,
+    /// `(value, count)` pairs, most frequent first. Empty unless the
+    /// column is text with at most [`MAX_LISTED_VALUES`] distinct values.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<ValueCount>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Describe every base table in `store`, ordered by table name.
+pub fn describe_store(store: &DbStore) -> Result<Vec<TableStats>> {
+    let rows = store
+        .run_query(
+            "SELECT table_name, column_name, data_type FROM information_schema.columns \
+             WHERE table_schema = 'main' \
+               AND table_name IN (SELECT table_name FROM information_schema.tables \
+                                  WHERE table_schema = 'main' AND table_type = 'BASE TABLE') \
+             ORDER BY table_name, ordinal_position",
+            BTreeMap::new(),
+        )
+        .map_err(|e| anyhow!("failed to list columns: {e}"))?;
+
+    let mut columns_by_table: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    for row in rows.rows {
+        let (Some(table), Some(column), Some(data_type)) = (
+            value_to_string(&row[0]),
+            value_to_string(&row[1]),
+            value_to_string(&row[2]),
+        ) else {
+            continue;
+        };
+        columns_by_table
+            .entry(table)
+            .or_default()
+            .push((column, data_type));
+    }
+
+    columns_by_table
+        .into_iter()
+        .map(|(table, columns)| describe_table(store, table, columns))
+        .collect()
+}
+
+fn describe_table(
+    store: &DbStore,
+    table: String,
+    columns: Vec<(String, String)>,
+) -> Result<TableStats> {
+    // One scan for every column's counts: COUNT(*), then a
+    // (non-null, distinct) pair per column.
+    let mut select = vec!["COUNT(*)".to_string()];
+    for (column, _) in &columns {
+        let col = quote_ident(column);
+        select.push(format!("COUNT({col})"));
+        select.push(format!("COUNT(DISTINCT {col})"));
+    }
+    let sql = format!("SELECT {} FROM {}", select.join(", "), quote_ident(&table));
+    let counts = store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to count {table}: {e}"))?;
+    let counts = counts.rows.into_iter().next().unwrap_or_default();
+    let count_at = |i: usize| counts.get(i).and_then(value_to_i64).unwrap_or(0);
+    let rows = count_at(0);
+
+    let mut stats = Vec::with_capacity(columns.len());
+    for (i, (column, data_type)) in columns.into_iter().enumerate() {
+        let non_null = count_at(1 + 2 * i);
+        let distinct = count_at(2 + 2 * i);
+        let values = if data_type == "VARCHAR" && distinct > 0 && distinct <= MAX_LISTED_VALUES {
+            value_counts(store, &table, &column)?
+        } else {
+            Vec::new()
+        };
+        stats.push(ColumnStats {
+            name: column,
+            data_type,
+            nulls: rows - non_null,
+            distinct,
+            values,
+        });
+    }
+
+    Ok(TableStats {
+        name: table,
+        rows,
+        columns: stats,
+    })
+}
+
+fn value_counts(store: &DbStore, table: &str, column: &str) -> Result<Vec<ValueCount>> {
+    let col = quote_ident(column);
+    let sql = format!(
+        "SELECT {col}, COUNT(*) AS n FROM {} WHERE {col} IS NOT NULL \
+         GROUP BY {col} ORDER BY n DESC, {col}",
+        quote_ident(table)
+    );
+    let rows = store
+        .run_query(&sql, BTreeMap::new())
+        .map_err(|e| anyhow!("failed to count values of {table}.{column}: {e}"))?;
+    Ok(rows
+        .rows
+        .iter()
+        .filter_map(|row| {
+            Some(ValueCount {
+                value: value_to_string(&row[0])?,
+                count: value_to_i64(&row[1]).unwrap_or(0),
+            })
+        })
+        .collect())
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db;
+    use crate::graph::builder::GraphBuilder;
+    use crate::language::Language;
+    use crate::storage::workspace::Workspace;
+
+    #[test]
+    fn symbol_kind_lists_seeded_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "pub struct Point;\npub fn origin() {}\npub fn unit() {}\n",
+        )
+        .unwrap();
+        let langs = [Language::Rust];
+        let ws = Workspace::load(dir.path(), &langs, None).unwrap();
+        let store = DbStore::open_in_memory().unwrap();
+        let graph = GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        db::populate(&store, &graph, Some(&ws)).unwrap();
+
+        let tables = describe_store(&store).unwrap();
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+
+        let symbol = tables.iter().find(|t| t.name == "symbol").unwrap();
+        assert_eq!(symbol.rows, 3);
+        let kind = symbol.columns.iter().find(|c| c.name == "kind").unwrap();
+        assert_eq!(kind.data_type, "VARCHAR");
+        assert_eq!(kind.nulls, 0);
+        assert_eq!(kind.distinct, 2);
+        let values: Vec<(&str, i64)> = kind
+            .values
+            .iter()
+            .map(|v| (v.value.as_str(), v.count))
+            .collect();
+        assert_eq!(values, vec![("function", 2), ("struct", 1)]);
+    }
+}
@@ -10,6 +10,11 @@
 //! in the fact store) live in `rust_templates.rs` and short-circuit
 //! the SQL path; their names are kept disjoint from the `.sql` file
 //! names.
+//!
+//! Every template ends in an `ORDER BY` that yields a total order. Cold
+//! builds append rows in rayon completion order, so DuckDB's scan order
+//! differs from one build to the next; without a full tie-break, golden
+//! diffs of template output flap between rebuilds.
 
 use include_dir::{Dir, include_dir};
 
@@ -47,6 +52,7 @@ mod tests {
             names,
             vec![
                 "export_surface".to_string(),
+                "file_dependencies".to_string(),
                 "find_callees".to_string(),
                 "find_callers".to_string(),
                 "find_cycles".to_string(),
@@ -57,6 +57,9 @@ struct QueryBody {
     template: Option<String>,
     #[serde(default)]
     params: HashMap<String, String>,
+    /// Values for positional `?` placeholders, bound by DuckDB.
+    #[serde(default)]
+    args: Vec<String>,
     /// Advisory wall-clock timeout. On expiry the job is marked
     /// `timed_out`; the query keeps running in the background (no
     /// DuckDB interrupt) and its result is discarded.
@@ -72,6 +75,7 @@ enum Body {
 struct QuerySpec {
     body: Body,
     params: Vec<(String, String)>,
+    args: Vec<String>,
 }
 
 async fn post_query(
@@ -79,14 +83,17 @@ async fn post_query(
     Json(body): Json<QueryBody>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     let params: Vec<(String, String)> = body.params.into_iter().collect();
+    let args = body.args;
     let spec = match (body.sql, body.template) {
         (Some(s), None) => QuerySpec {
             body: Body::Sql(s),
             params,
+            args,
         },
         (None, Some(t)) => QuerySpec {
             body: Body::Template(t),
             params,
+            args,
         },
         (Some(_), Some(_)) => {
             return Err((
@@ -256,6 +263,7 @@ fn execute(
     run_query(QueryRequest {
         source,
         params: spec.params.clone(),
+        args: spec.args.clone(),
         store,
         workspace,
     })
@@ -37,7 +37,7 @@ pub fn run(name: String, port: u16, max_concurrency: usize, result_ttl_secs: u64
     let project = registry::get_project(&name)?;
     let languages = match &project.languages {
         Some(f) => language::parse_language_filter(f),
-        None => Language::all().to_vec(),
+        None => Language::defaults(),
     };
     let workspace = Workspace::load(&project.path, &languages, None)?;
 
@@ -0,0 +1,265 @@
+//! Source trees packed in a `.tar`, `.tar.gz`/`.tgz` or `.zip` archive,
+//! read into memory without extracting them to disk.
+//!
+//! Entry paths are taken relative to the archive root. Directories,
+//! symlinks and other non-regular entries are skipped, as are hidden
+//! paths (a component starting with `.`, which the directory walk also
+//! skips) and paths that climb out of the root. Entries over
+//! `max_file_size` are listed as oversized and never read. Languages are
+//! detected as for files on disk: by extension, then well-known file
+//! name, then a `#!` line.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::{Component, Path};
+
+use anyhow::{Context, Result};
+
+use crate::language::Language;
+
+use super::workspace::Workspace;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// The format `path`'s file name implies, if it names an archive.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read the source files of `languages` out of `archive` into a
+/// [`Workspace`] whose root is the archive itself.
+pub fn load(
+    archive: &Path,
+    languages: &[Language],
+    max_file_size: Option<u64>,
+) -> Result<Workspace> {
+    let format = ArchiveFormat::from_path(archive).with_context(|| {
+        format!(
+            "not a .tar, .tar.gz, .tgz or .zip archive: {}",
+            archive.display()
+        )
+    })?;
+    let file =
+        File::open(archive).with_context(|| format!("failed to open {}", archive.display()))?;
+    let reader = BufReader::new(file);
+    let mut collector = Collector::new(languages, max_file_size);
+    match format {
+        ArchiveFormat::Tar => read_tar(reader, &mut collector)?,
+        ArchiveFormat::TarGz => read_tar(flate2::read::GzDecoder::new(reader), &mut collector)?,
+        ArchiveFormat::Zip => read_zip(reader, &mut collector)?,
+    }
+    Ok(collector.into_workspace(archive))
+}
+
+fn read_tar<R: Read>(reader: R, collector: &mut Collector<'_>) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries().context("failed to read tar archive")? {
+        let mut entry = entry.context("failed to read tar entry")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .context("tar entry has an invalid path")?
+            .into_owned();
+        let size = entry.size();
+        collector.offer(&path, size, &mut entry)?;
+    }
+    Ok(())
+}
+
+fn read_zip<R: Read + Seek>(reader: R, collector: &mut Collector<'_>) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(reader).context("failed to read zip archive")?;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .context("failed to read zip entry")?;
+        if !entry.is_file() || entry.is_symlink() {
+            continue;
+        }
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+        let size = entry.size();
+        collector.offer(&path, size, &mut entry)?;
+    }
+    Ok(())
+}
+
+/// Entries kept so far, filtered by language and size.
+struct Collector<'a> {
+    languages: &'a [Language],
+    max_file_size: Option<u64>,
+    files: Vec<(String, Language, Vec<u8>)>,
+    oversized: Vec<(String, Language, u64)>,
+}
+
+impl<'a> Collector<'a> {
+    fn new(languages: &'a [Language], max_file_size: Option<u64>) -> Self {
+        Self {
+            languages,
+            max_file_size,
+            files: Vec::new(),
+            oversized: Vec::new(),
+        }
+    }
+
+    /// Keep the entry at `path` if it is a source file of a selected
+    /// language. `content` is only read for entries that may be kept.
+    fn offer(&mut self, path: &Path, size: u64, content: &mut dyn Read) -> Result<()> {
+        let Some(relative) = relative_path(path) else {
+            return Ok(());
+        };
+        let name = relative.rsplit('/').next().unwrap_or(&relative);
+        let by_name = match Path::new(name).extension().and_then(|e| e.to_str()) {
+            Some(ext) => match Language::from_extension(ext) {
+                Some(lang) => Some(lang),
+                None => return Ok(()),
+            },
+            None => Language::from_filename(name),
+        };
+        if by_name.is_some_and(|lang| !self.languages.contains(&lang)) {
+            return Ok(());
+        }
+        if let Some(max) = self.max_file_size
+            && size > max
+        {
+            if let Some(lang) = by_name {
+                self.oversized.push((relative, lang, size));
+            }
+            return Ok(());
+        }
+        let mut bytes = Vec::with_capacity(size as usize);
+        content
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("failed to read {relative} from archive"))?;
+        let language = by_name.or_else(|| {
+            let head = String::from_utf8_lossy(&bytes[..bytes.len().min(256)]);
+            Language::from_shebang(head.lines().next()?)
+        });
+        if let Some(lang) = language
+            && self.languages.contains(&lang)
+        {
+            self.files.push((relative, lang, bytes));
+        }
+        Ok(())
+    }
+
+    fn into_workspace(self, archive: &Path) -> Workspace {
+        Workspace::from_memory(archive.to_path_buf(), self.files, self.oversized)
+    }
+}
+
+/// `path` as a `/`-separated relative path, or `None` for an absolute,
+/// escaping or hidden one.
+fn relative_path(path: &Path) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => {
+                let part = part.to_str()?;
+                if part.starts_with('.') {
+                    return None;
+                }
+                parts.push(part);
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::db::DbStore;
+    use crate::graph::builder::GraphBuilder;
+
+    fn rust_tar() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut append = |path: &str, data: &[u8]| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, path, data).unwrap();
+        };
+        append("proj/src/lib.rs", b"pub fn hello() -> u32 {\n    1\n}\n");
+        append("proj/README.md", b"# proj\n");
+        append("proj/.git/hook.rs", b"fn hidden() {}\n");
+        let mut link = tar::Header::new_gnu();
+        link.set_entry_type(tar::EntryType::Symlink);
+        link.set_size(0);
+        builder
+            .append_link(&mut link, "proj/src/alias.rs", "lib.rs")
+            .unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn parses_a_rust_file_from_an_in_memory_tar() {
+        let langs = [Language::Rust];
+        let mut collector = Collector::new(&langs, None);
+        read_tar(Cursor::new(rust_tar()), &mut collector).unwrap();
+        let ws = collector.into_workspace(Path::new("proj.tar"));
+        assert_eq!(ws.files(), &["proj/src/lib.rs"]);
+
+        let store = DbStore::open_in_memory().unwrap();
+        GraphBuilder::new(&ws, &langs).build(&store).unwrap();
+        let rows = store
+            .run_query(
+                "SELECT name, file_path FROM symbol WHERE kind = 'function'",
+                BTreeMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            rows.rows,
+            vec![vec![
+                duckdb::types::Value::Text("hello".to_string()),
+                duckdb::types::Value::Text("proj/src/lib.rs".to_string()),
+            ]]
+        );
+    }
+
+    #[test]
+    fn oversized_entries_are_listed_not_read() {
+        let langs = [Language::Rust];
+        let mut collector = Collector::new(&langs, Some(8));
+        read_tar(Cursor::new(rust_tar()), &mut collector).unwrap();
+        let ws = collector.into_workspace(Path::new("proj.tar"));
+        assert!(ws.files().is_empty());
+        assert_eq!(
+            ws.oversized_files(),
+            &[("proj/src/lib.rs".to_string(), Language::Rust, 32)]
+        );
+    }
+
+    #[test]
+    fn detects_archive_formats_by_name() {
+        let format = |name: &str| ArchiveFormat::from_path(Path::new(name));
+        assert_eq!(format("ci/build.tar.gz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(format("build.TGZ"), Some(ArchiveFormat::TarGz));
+        assert_eq!(format("build.tar"), Some(ArchiveFormat::Tar));
+        assert_eq!(format("build.zip"), Some(ArchiveFormat::Zip));
+        assert_eq!(format("src/lib.rs"), None);
+    }
+}
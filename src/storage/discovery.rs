@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use ignore::WalkBuilder;
+use serde::Serialize;
 
 use crate::language::Language;
 
@@ -19,13 +22,34 @@ pub fn discover_all_files(root: &Path) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-pub fn discover_files(root: &Path, languages: &[Language]) -> Result<Vec<PathBuf>> {
-    let extensions: Vec<&str> = languages
-        .iter()
-        .flat_map(|l| l.all_extensions())
-        .copied()
-        .collect();
+/// Bytes read from the head of an extensionless file when sniffing for a
+/// `#!` line. Long enough for any realistic interpreter path + flags.
+const SHEBANG_SNIFF_BYTES: u64 = 256;
+
+/// Resolve the language of a file on disk. The extension wins when
+/// present; extensionless files fall back to well-known filenames
+/// ([`Language::from_filename`]) and then to a `#!` sniff of the first
+/// line ([`Language::from_shebang`]).
+pub fn detect_language(path: &Path) -> Option<Language> {
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        return Language::from_extension(ext);
+    }
+    let name = path.file_name()?.to_str()?;
+    Language::from_filename(name).or_else(|| sniff_shebang(path))
+}
 
+fn sniff_shebang(path: &Path) -> Option<Language> {
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .ok()?
+        .take(SHEBANG_SNIFF_BYTES)
+        .read_to_end(&mut head)
+        .ok()?;
+    let head = String::from_utf8_lossy(&head);
+    Language::from_shebang(head.lines().next()?)
+}
+
+pub fn discover_files(root: &Path, languages: &[Language]) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
     for entry in WalkBuilder::new(root).build() {
         let entry = entry?;
@@ -33,9 +57,7 @@ pub fn discover_files(root: &Path, languages: &[Language]) -> Result<Vec<PathBuf
             continue;
         }
         let path = entry.path();
-        if let Some(ext) = path.extension().and_then(|e| e.to_str())
-            && extensions.contains(&ext)
-        {
+        if detect_language(path).is_some_and(|l| languages.contains(&l)) {
             files.push(path.to_path_buf());
         }
     }
@@ -44,6 +66,47 @@ pub fn discover_files(root: &Path, languages: &[Language]) -> Result<Vec<PathBuf
     Ok(files)
 }
 
+/// What a build over a root would read (`projects create --dry-run`):
+/// file counts and sizes per language, found without parsing anything.
+#[derive(Debug, Default, Serialize)]
+pub struct DiscoveryPlan {
+    /// Files of the selected languages, per language.
+    pub languages: BTreeMap<String, LanguagePlan>,
+    /// Files of a known language that the language filter leaves out.
+    pub filtered: BTreeMap<String, usize>,
+    /// Files no language is detected for.
+    pub unsupported: usize,
+    pub total_files: usize,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct LanguagePlan {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Walk `root` the way [`discover_files`] does and tally every file by
+/// detected language instead of collecting the selected ones.
+pub fn plan_files(root: &Path, languages: &[Language]) -> Result<DiscoveryPlan> {
+    let mut plan = DiscoveryPlan::default();
+    for path in discover_all_files(root)? {
+        match detect_language(&path) {
+            Some(lang) if languages.contains(&lang) => {
+                let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                let entry = plan.languages.entry(lang.as_str().to_string()).or_default();
+                entry.files += 1;
+                entry.bytes += bytes;
+                plan.total_files += 1;
+                plan.total_bytes += bytes;
+            }
+            Some(lang) => *plan.filtered.entry(lang.as_str().to_string()).or_default() += 1,
+            None => plan.unsupported += 1,
+        }
+    }
+    Ok(plan)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +182,34 @@ mod tests {
         assert!(files[0].ends_with("keep.ts"));
     }
 
+    #[test]
+    fn discover_extensionless_python_by_shebang() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("manage"),
+            "#!/usr/bin/env python\nprint('hi')\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("run"), "#!/bin/bash\necho hi\n").unwrap();
+        std::fs::write(dir.path().join("README"), "plain text\n").unwrap();
+
+        assert_eq!(
+            detect_language(&dir.path().join("manage")),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            detect_language(&dir.path().join("run")),
+            Some(Language::Bash)
+        );
+        let files = discover_files(dir.path(), &[Language::Python]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("manage"));
+
+        // The language filter still applies to sniffed files.
+        let files = discover_files(dir.path(), &[Language::TypeScript]).unwrap();
+        assert!(files.is_empty());
+    }
+
     #[test]
     fn discover_all_files_returns_everything() {
         let dir = create_test_dir();
@@ -167,4 +258,15 @@ mod tests {
             .collect();
         assert_eq!(names, vec!["a.ts", "m.ts", "z.ts"]);
     }
+
+    #[test]
+    fn plan_counts_files_and_bytes_per_language() {
+        let dir = create_test_dir();
+        let plan = plan_files(dir.path(), &[Language::TypeScript]).unwrap();
+        let ts = &plan.languages["typescript"];
+        assert_eq!((ts.files, ts.bytes), (1, "const x = 1;".len() as u64));
+        assert_eq!(plan.filtered["javascript"], 1);
+        assert_eq!(plan.unsupported, 1, "style.css has no language");
+        assert_eq!((plan.total_files, plan.total_bytes), (1, 12));
+    }
 }
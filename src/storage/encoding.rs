@@ -0,0 +1,126 @@
+//! Source text decoding.
+//!
+//! Tree-sitter and every byte offset in the fact store work on UTF-8, so
+//! files are decoded once on read. A byte-order mark selects UTF-8 or
+//! UTF-16; anything else is tried as UTF-8 and, failing that, decoded as
+//! Latin-1 (every byte maps to one char, so it never fails). The BOM is
+//! dropped so line/column positions match what an editor shows.
+
+/// How a file's bytes were decoded. Recorded in `file.encoding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+impl SourceEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceEncoding::Utf8 => "utf-8",
+            SourceEncoding::Utf8Bom => "utf-8-bom",
+            SourceEncoding::Utf16Le => "utf-16le",
+            SourceEncoding::Utf16Be => "utf-16be",
+            SourceEncoding::Latin1 => "latin-1",
+        }
+    }
+}
+
+/// Decode raw file bytes to UTF-8 text. Returns `None` only for a
+/// UTF-16 file whose body isn't valid UTF-16.
+pub fn decode(bytes: Vec<u8>) -> Option<(String, SourceEncoding)> {
+    if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        return match std::str::from_utf8(rest) {
+            Ok(s) => Some((s.to_string(), SourceEncoding::Utf8Bom)),
+            Err(_) => Some((latin1(rest), SourceEncoding::Latin1)),
+        };
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        return utf16(rest, u16::from_le_bytes).map(|s| (s, SourceEncoding::Utf16Le));
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        return utf16(rest, u16::from_be_bytes).map(|s| (s, SourceEncoding::Utf16Be));
+    }
+    match String::from_utf8(bytes) {
+        Ok(s) => Some((s, SourceEncoding::Utf8)),
+        Err(e) => Some((latin1(e.as_bytes()), SourceEncoding::Latin1)),
+    }
+}
+
+/// Byte offset of the first invalid UTF-8 sequence in the bytes that
+/// `text` was decoded from as Latin-1, where each char stands for one
+/// byte; counted after a dropped UTF-8 BOM. `None` when those bytes are
+/// valid UTF-8, which [`decode`] would not have read as Latin-1.
+pub fn invalid_utf8_offset(text: &str) -> Option<usize> {
+    let bytes: Vec<u8> = text.chars().map(|c| c as u32 as u8).collect();
+    std::str::from_utf8(&bytes).err().map(|e| e.valid_up_to())
+}
+
+fn utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Option<String> {
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le_with_bom(text: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    }
+
+    #[test]
+    fn plain_utf8_passes_through() {
+        let (text, enc) = decode(b"fn a() {}".to_vec()).unwrap();
+        assert_eq!(text, "fn a() {}");
+        assert_eq!(enc, SourceEncoding::Utf8);
+    }
+
+    #[test]
+    fn utf8_bom_is_stripped() {
+        let (text, enc) = decode(b"\xEF\xBB\xBFfn a() {}".to_vec()).unwrap();
+        assert_eq!(text, "fn a() {}");
+        assert_eq!(enc, SourceEncoding::Utf8Bom);
+    }
+
+    #[test]
+    fn utf16_le_and_be_decode() {
+        let (text, enc) = decode(utf16le_with_bom("def f(): pass")).unwrap();
+        assert_eq!(text, "def f(): pass");
+        assert_eq!(enc, SourceEncoding::Utf16Le);
+
+        let mut be = vec![0xFE, 0xFF];
+        be.extend("x = 1".encode_utf16().flat_map(u16::to_be_bytes));
+        assert_eq!(
+            decode(be).unwrap(),
+            ("x = 1".to_string(), SourceEncoding::Utf16Be)
+        );
+    }
+
+    #[test]
+    fn invalid_utf8_falls_back_to_latin1() {
+        let (text, enc) = decode(b"// caf\xE9\nint x;".to_vec()).unwrap();
+        assert_eq!(text, "// café\nint x;");
+        assert_eq!(enc, SourceEncoding::Latin1);
+        assert_eq!(invalid_utf8_offset(&text), Some(6));
+    }
+
+    #[test]
+    fn odd_length_utf16_is_rejected() {
+        assert!(decode(vec![0xFF, 0xFE, 0x61]).is_none());
+    }
+}
@@ -5,9 +5,11 @@ use std::sync::{Arc, Mutex};
 
 use lru::LruCache;
 
-/// Read-only file source abstraction. Only implementation is
-/// [`DiskFileSource`]; the prior `MemoryFileSource` / `S3FileSource`
-/// dropped with the S3 + serve scope (see
+use super::encoding::{self, SourceEncoding};
+
+/// Read-only file source abstraction: [`DiskFileSource`] for a source
+/// tree on disk, [`MemoryFileSource`] for files read out of an archive.
+/// The prior `S3FileSource` dropped with the S3 + serve scope (see
 /// `docs/experiments/duckdb-swap.md`).
 pub trait FileSource: Send + Sync {
     /// Read file content by relative path. Returns None if not found.
@@ -21,6 +23,10 @@ pub trait FileSource: Send + Sync {
 
     /// Get file size in bytes (without reading content).
     fn file_size(&self, relative_path: &str) -> Option<u64>;
+
+    /// Encoding the file was decoded from. Only known once the file has
+    /// been read; before that (and for unknown paths) returns `None`.
+    fn file_encoding(&self, relative_path: &str) -> Option<SourceEncoding>;
 }
 
 /// Default capacity for the disk LRU cache. The working set during a build is
@@ -37,6 +43,9 @@ pub struct DiskFileSource {
     file_list: Vec<String>,
     sizes: HashMap<String, u64>,
     cache: Mutex<LruCache<String, Arc<str>>>,
+    /// Decoding outcome per file read so far. Outlives LRU eviction so
+    /// the encoding stays queryable after the text is dropped.
+    encodings: Mutex<HashMap<String, SourceEncoding>>,
 }
 
 impl DiskFileSource {
@@ -49,6 +58,7 @@ impl DiskFileSource {
             file_list,
             sizes,
             cache: Mutex::new(LruCache::new(cap)),
+            encodings: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -67,7 +77,11 @@ impl FileSource for DiskFileSource {
             return Some(hit);
         }
         let bytes = std::fs::read(self.root.join(relative_path)).ok()?;
-        let s: Arc<str> = String::from_utf8(bytes).ok()?.into();
+        let (text, encoding) = encoding::decode(bytes)?;
+        let s: Arc<str> = text.into();
+        if let Ok(mut e) = self.encodings.lock() {
+            e.insert(relative_path.to_string(), encoding);
+        }
         if let Ok(mut c) = self.cache.lock() {
             c.put(relative_path.to_string(), s.clone());
         }
@@ -85,6 +99,58 @@ impl FileSource for DiskFileSource {
     fn file_size(&self, relative_path: &str) -> Option<u64> {
         self.sizes.get(relative_path).copied()
     }
+
+    fn file_encoding(&self, relative_path: &str) -> Option<SourceEncoding> {
+        self.encodings.lock().ok()?.get(relative_path).copied()
+    }
+}
+
+/// Files held in memory, e.g. the entries of a `.tar.gz` read by
+/// [`crate::storage::archive`]. Content is decoded on each read, like
+/// [`DiskFileSource`], so the raw bytes are the only copy kept.
+pub struct MemoryFileSource {
+    file_list: Vec<String>,
+    files: HashMap<String, Vec<u8>>,
+    encodings: Mutex<HashMap<String, SourceEncoding>>,
+}
+
+impl MemoryFileSource {
+    pub fn new(files: HashMap<String, Vec<u8>>) -> Self {
+        let mut file_list: Vec<String> = files.keys().cloned().collect();
+        file_list.sort();
+        Self {
+            file_list,
+            files,
+            encodings: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl FileSource for MemoryFileSource {
+    fn read_file(&self, relative_path: &str) -> Option<Arc<str>> {
+        let bytes = self.files.get(relative_path)?.clone();
+        let (text, encoding) = encoding::decode(bytes)?;
+        if let Ok(mut e) = self.encodings.lock() {
+            e.insert(relative_path.to_string(), encoding);
+        }
+        Some(text.into())
+    }
+
+    fn list_files(&self) -> &[String] {
+        &self.file_list
+    }
+
+    fn file_exists(&self, relative_path: &str) -> bool {
+        self.files.contains_key(relative_path)
+    }
+
+    fn file_size(&self, relative_path: &str) -> Option<u64> {
+        self.files.get(relative_path).map(|b| b.len() as u64)
+    }
+
+    fn file_encoding(&self, relative_path: &str) -> Option<SourceEncoding> {
+        self.encodings.lock().ok()?.get(relative_path).copied()
+    }
 }
 
 #[cfg(test)]
@@ -143,4 +209,19 @@ mod tests {
             assert!(content.contains(&format!("{i}")));
         }
     }
+
+    #[test]
+    fn disk_file_source_decodes_utf16_with_bom() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend("fn a() {}".encode_utf16().flat_map(u16::to_le_bytes));
+        let size = bytes.len() as u64;
+        std::fs::write(dir.path().join("a.rs"), bytes).unwrap();
+
+        let sizes: HashMap<String, u64> = [("a.rs".to_string(), size)].into_iter().collect();
+        let source = DiskFileSource::new(dir.path().to_path_buf(), vec!["a.rs".to_string()], sizes);
+        assert_eq!(source.file_encoding("a.rs"), None);
+        assert_eq!(source.read_file("a.rs").unwrap().as_ref(), "fn a() {}");
+        assert_eq!(source.file_encoding("a.rs"), Some(SourceEncoding::Utf16Le));
+    }
 }
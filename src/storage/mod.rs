@@ -1,4 +1,6 @@
+pub mod archive;
 pub mod discovery;
+pub mod encoding;
 pub mod file_source;
 pub mod registry;
 pub mod workspace;
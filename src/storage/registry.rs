@@ -7,6 +7,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::language::{self, Language};
+use crate::storage::archive::{self, ArchiveFormat};
 use crate::storage::discovery;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,26 +74,34 @@ pub fn create_project(
 
     let languages = match lang_filter {
         Some(f) => language::parse_language_filter(f),
-        None => Language::all().to_vec(),
+        None => Language::defaults(),
     };
 
-    let files = discovery::discover_files(&canonical, &languages)?;
-
     let mut breakdown: HashMap<String, usize> = HashMap::new();
-    for file in &files {
-        if let Some(ext) = file.extension().and_then(|e| e.to_str())
-            && let Some(lang) = Language::from_extension(ext)
-        {
-            *breakdown.entry(lang.as_str().to_string()).or_default() += 1;
+    let file_count = if canonical.is_file() && ArchiveFormat::from_path(&canonical).is_some() {
+        let workspace = archive::load(&canonical, &languages, None)?;
+        for file in workspace.files() {
+            if let Some(lang) = workspace.file_language(file) {
+                *breakdown.entry(lang.as_str().to_string()).or_default() += 1;
+            }
         }
-    }
+        workspace.file_count()
+    } else {
+        let files = discovery::discover_files(&canonical, &languages)?;
+        for file in &files {
+            if let Some(lang) = discovery::detect_language(file) {
+                *breakdown.entry(lang.as_str().to_string()).or_default() += 1;
+            }
+        }
+        files.len()
+    };
 
     let entry = ProjectEntry {
         name: name.to_string(),
         path: canonical,
         exclude,
         languages: lang_filter.map(|s| s.to_string()),
-        file_count: files.len(),
+        file_count,
         language_breakdown: breakdown,
         created_at: Utc::now(),
     };
@@ -6,37 +6,43 @@ use anyhow::{Context, Result};
 use rayon::prelude::*;
 
 use crate::language::Language;
+use crate::storage::archive::{self, ArchiveFormat};
 use crate::storage::discovery;
-use crate::storage::file_source::{DiskFileSource, FileSource};
+use crate::storage::encoding::SourceEncoding;
+use crate::storage::file_source::{DiskFileSource, FileSource, MemoryFileSource};
 
 pub struct Workspace {
     root: PathBuf,
     source: Box<dyn FileSource>,
     languages: HashMap<String, Language>,
+    /// Files over `max_file_size`, kept out of `source` so they are
+    /// never read. Sorted by path.
+    oversized: Vec<(String, Language, u64)>,
 }
 
 impl Workspace {
     /// Discover files, record sizes + languages, return ready-to-use workspace.
     /// File content is read on demand by `DiskFileSource` and cached in a small LRU.
+    /// Files larger than `max_file_size` bytes are left out and listed by
+    /// [`Workspace::oversized_files`]. A `root` that is a `.tar`,
+    /// `.tar.gz`/`.tgz` or `.zip` file is read with [`archive::load`]
+    /// instead.
     pub fn load(root: &Path, languages: &[Language], max_file_size: Option<u64>) -> Result<Self> {
         let root = root
             .canonicalize()
             .with_context(|| format!("invalid directory: {}", root.display()))?;
+        if root.is_file() && ArchiveFormat::from_path(&root).is_some() {
+            return archive::load(&root, languages, max_file_size);
+        }
 
         let files = discovery::discover_files(&root, languages)?;
 
         let discovered: Vec<(String, u64, Language)> = files
             .par_iter()
             .filter_map(|path| {
-                let ext = path.extension()?.to_str()?;
-                let lang = Language::from_extension(ext)?;
+                let lang = discovery::detect_language(path)?;
 
                 let size = std::fs::metadata(path).ok()?.len();
-                if let Some(max_size) = max_file_size
-                    && size > max_size
-                {
-                    return None;
-                }
 
                 let relative = path
                     .strip_prefix(&root)
@@ -51,27 +57,65 @@ impl Workspace {
         let mut size_map: HashMap<String, u64> = HashMap::with_capacity(discovered.len());
         let mut lang_map: HashMap<String, Language> = HashMap::with_capacity(discovered.len());
         let mut file_list: Vec<String> = Vec::with_capacity(discovered.len());
+        let mut oversized = Vec::new();
 
         for (rel_path, size, lang) in discovered {
+            if let Some(max_size) = max_file_size
+                && size > max_size
+            {
+                oversized.push((rel_path, lang, size));
+                continue;
+            }
             size_map.insert(rel_path.clone(), size);
             lang_map.insert(rel_path.clone(), lang);
             file_list.push(rel_path);
         }
 
+        oversized.sort_by(|a, b| a.0.cmp(&b.0));
+
         let source = Box::new(DiskFileSource::new(root.clone(), file_list, size_map));
 
         Ok(Self {
             root,
             source,
             languages: lang_map,
+            oversized,
         })
     }
 
+    /// Workspace over files already in memory, as
+    /// `(relative path, language, bytes)`. `root` is only a label here
+    /// (the archive the files came from); nothing is read from it.
+    pub fn from_memory(
+        root: PathBuf,
+        files: Vec<(String, Language, Vec<u8>)>,
+        mut oversized: Vec<(String, Language, u64)>,
+    ) -> Self {
+        let mut contents: HashMap<String, Vec<u8>> = HashMap::with_capacity(files.len());
+        let mut languages: HashMap<String, Language> = HashMap::with_capacity(files.len());
+        for (path, lang, bytes) in files {
+            languages.insert(path.clone(), lang);
+            contents.insert(path, bytes);
+        }
+        oversized.sort_by(|a, b| a.0.cmp(&b.0));
+        Self {
+            root,
+            source: Box::new(MemoryFileSource::new(contents)),
+            languages,
+            oversized,
+        }
+    }
+
     /// Read file content by relative path.
     pub fn read_file(&self, relative_path: &str) -> Option<Arc<str>> {
         self.source.read_file(relative_path)
     }
 
+    /// Encoding a file was decoded from; see [`FileSource::file_encoding`].
+    pub fn file_encoding(&self, relative_path: &str) -> Option<SourceEncoding> {
+        self.source.file_encoding(relative_path)
+    }
+
     /// Get language for a loaded file.
     pub fn file_language(&self, relative_path: &str) -> Option<Language> {
         self.languages.get(relative_path).copied()
@@ -87,6 +131,12 @@ impl Workspace {
         &self.root
     }
 
+    /// Files skipped for exceeding `max_file_size`, as
+    /// `(relative path, language, size in bytes)`.
+    pub fn oversized_files(&self) -> &[(String, Language, u64)] {
+        &self.oversized
+    }
+
     /// Total files loaded.
     pub fn file_count(&self) -> usize {
         self.source.list_files().len()
@@ -122,6 +172,7 @@ impl Workspace {
             root: self.root.clone(),
             source,
             languages: langs,
+            oversized: Vec::new(),
         }
     }
 }
@@ -174,6 +225,15 @@ mod tests {
         assert!(ws.read_file("big.rs").is_none());
     }
 
+    #[test]
+    fn workspace_detects_shebang_language() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("tool"), "#!/usr/bin/env python3\nx = 1\n").unwrap();
+
+        let ws = Workspace::load(dir.path(), &[Language::Python], None).unwrap();
+        assert_eq!(ws.file_language("tool"), Some(Language::Python));
+    }
+
     #[test]
     fn workspace_subdirectories() {
         let dir = tempfile::tempdir().expect("tempdir");